@@ -30,13 +30,30 @@ pub enum AdapterCommand<'a> {
     RemoveAll,
 
     /// Команда для запуска цикла работы приложения. Принимает название для окна (для desktop,
-    /// для других платформ это не требуется, но поле обязательно), ширину, высоту и слушатель
-    /// замыкание которое принимает AdapterEvent. В реализации адаптера при получении RunLoop
-    /// нужно запустить цикл и при событии пробрасывать его в listener замыкание
+    /// для других платформ это не требуется, но поле обязательно), ширину, высоту, параметры
+    /// окна и слушатель замыкание которое принимает AdapterEvent. В реализации адаптера при
+    /// получении RunLoop нужно запустить цикл и при событии пробрасывать его в listener
+    /// замыкание. Параметры окна которые платформа не поддерживает можно игнорировать
     RunLoop {
         title: &'static str,
         width: u32,
         height: u32,
+
+        /// Минимальный размер окна в логических пикселях
+        min_size: Option<(u32, u32)>,
+
+        /// Максимальный размер окна в логических пикселях
+        max_size: Option<(u32, u32)>,
+
+        /// Может ли пользователь менять размер окна
+        resizable: bool,
+
+        /// Целевая частота кадров (частота Tick), 0 если частоту выбирает платформа
+        fps: u16,
+
+        /// R, G, B, A цвет фона окна под всеми примитивами
+        background: (u8, u8, u8, u8),
+
         listener: fn(AdapterEvent),
     },
 
@@ -142,6 +159,73 @@ pub enum AdapterCommand<'a> {
 
     /// Принимает два хэндла и применяет обрезку по форме
     SetClipTo(usize, usize),
+
+    /// Декодирует изображение (PNG, JPEG и другие форматы которые поддерживает адаптер) из
+    /// байтов и возвращает Handle текстуры. Текстура не является рендер объектом, RemoveAll
+    /// её не удаляет, она живёт до FreeTexture. Если формат не поддерживается адаптер
+    /// возвращает Fail
+    LoadImage {
+        // Адаптер должен скопировать байты если не декодирует их сразу
+        bytes: &'a [u8],
+    },
+
+    /// То же что и LoadImage, но изображение читается из файла или ресурса платформы.
+    /// Путь интерпретирует адаптер (файловая система на desktop, assets на Android)
+    LoadImageFromPath {
+        path: &'a str,
+    },
+
+    /// Создаёт новое изображение, возвращая Handle. Пока текстура не задана через SetImage
+    /// изображение ничего не рисует
+    NewImage {
+        layout: u16,
+    },
+
+    /// Принимает хэндл изображения и хэндл текстуры из LoadImage. Одна текстура может
+    /// быть показана несколькими изображениями
+    SetImage(usize, usize),
+
+    /// Способ вписывания текстуры в размер изображения из SetSize
+    ///  - 0 contain, текстура целиком внутри с сохранением пропорций (по умолчанию)
+    ///  - 1 cover, текстура заполняет размер с сохранением пропорций, лишнее обрезается
+    ///  - 2 fill, текстура растягивается без сохранения пропорций
+    SetImageFit(usize, u8),
+
+    /// Освобождает текстуру. Изображения которые её показывали перестают рисовать
+    FreeTexture(usize),
+
+    /// Регистрирует шрифт из байтов TTF или OTF файла и возвращает его Handle. Как и
+    /// текстуры шрифт не удаляется RemoveAll. Если формат не поддерживается адаптер
+    /// возвращает Fail
+    LoadFont {
+        // Адаптер должен скопировать байты
+        bytes: &'a [u8],
+    },
+
+    /// Принимает хэндл текста и хэндл шрифта из LoadFont, шрифт применяется ко всем
+    /// сегментам текста. Стиль сегмента из PushText (жирный, курсив) адаптер применяет
+    /// поверх шрифта если может
+    SetFont(usize, usize),
+
+    /// Начало перехода между экранами. Все текущие рендер объекты переходят в уходящую
+    /// сцену и продолжают рисоваться, новые объекты создаются в текущей сцене. Объекты
+    /// уходящей сцены не участвуют в ResolveHit и не меняются командами кроме
+    /// SetSceneOffset, SetSceneOpacity и RemoveDetached. Если уходящая сцена уже есть
+    /// (новый переход начался до конца прошлого) то её объекты удаляются. RemoveAll
+    /// удаляет объекты обеих сцен и сбрасывает их смещение и прозрачность
+    DetachAll,
+
+    /// Смещение всех объектов сцены при рисовании в логических координатах, сцена 0 это
+    /// текущая, 1 уходящая. Позиции объектов (SetPosition) не меняются
+    SetSceneOffset(/* Сцена */ u8, (i32, i32)),
+
+    /// Прозрачность всех объектов сцены при рисовании (0 прозрачная, 255 непрозрачная),
+    /// умножается на альфа канал объектов
+    SetSceneOpacity(/* Сцена */ u8, u8),
+
+    /// Конец перехода, удаляет объекты уходящей сцены и сбрасывает её смещение и
+    /// прозрачность
+    RemoveDetached,
 }
 
 /// Перечисления для реализации абстракции над событием EventLoop. В своей реализации
@@ -155,9 +239,24 @@ pub enum AdapterEvent {
     /// embedded. Нужно вызывать. Третий аргумент это фаза клика
     Touch(u32, u32, AdapterClickPhase),
 
-    /// > ENG: Keyboard input. The argument is the character code that was typed.
-    /// > RU: Ввод с клавиатуры. Аргумент это код символа который был набран
-    Key(u32),
+    /// > ENG: Hardware key pressed or released. Arguments are the key code (see key_code),
+    /// > the phase and the modifiers held at that moment.
+    /// > RU: Нажатие или отпускание физической клавиши. Аргументы это код клавиши (см.
+    /// > key_code), фаза и модификаторы зажатые в этот момент. Для ввода текста
+    /// > используйте Char, так как код клавиши не учитывает раскладку и регистр
+    Key(u32, AdapterKeyPhase, AdapterKeyModifiers),
+
+    /// > ENG: Typed character, already with the layout and modifiers applied.
+    /// > RU: Набранный символ с уже применённой раскладкой и модификаторами
+    Char(char),
+
+    /// > ENG: Window size or screen scale changed. Size is in logical pixels, scale is in
+    /// > hundredths (100 = 1.0).
+    /// > RU: Изменился размер окна или масштаб экрана (поворот, перенос на другой монитор).
+    /// > Размер в логических пикселях, масштаб в сотых долях (100 = 1.0). Адаптер должен
+    /// > отправить Resize после запуска RunLoop если реальный размер окна отличается от
+    /// > запрошенного
+    Resize { width: u32, height: u32, scale: u16 },
 
     /// > ENG: Must be called every frame.
     /// > RU: Должен вызываться каждый кадр
@@ -176,8 +275,10 @@ pub enum AdapterClickPhase {
     /// > RU: Палец только нажал и держит
     Began,
 
-    /// > ENG: Finger pressed (Began) and is moving across the screen.
-    /// > RU: Палец нажал (Began) и водит по экрану
+    /// > ENG: Finger pressed (Began) and is moving across the screen. Desktop adapters may
+    /// > also send Moved without Began when the cursor moves, it is used for hover.
+    /// > RU: Палец нажал (Began) и водит по экрану. Desktop адаптеры могут отправлять Moved
+    /// > и без Began при движении курсора, по нему скины показывают наведение
     Moved,
 
     /// > ENG: Finger was released.
@@ -189,6 +290,62 @@ pub enum AdapterClickPhase {
     Cancelled,
 }
 
+/// > ENG: Enumeration for specifying the key phase.
+/// > RU: Перечисление для указания фазы нажатия клавиши
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdapterKeyPhase {
+    /// > ENG: Key was pressed. Adapters may repeat Down while the key is held.
+    /// > RU: Клавиша нажата. Адаптер может повторять Down пока клавиша удерживается
+    Down,
+
+    /// > ENG: Key was released.
+    /// > RU: Клавиша отпущена
+    Up,
+}
+
+/// > ENG: Modifier keys held during a key event.
+/// > RU: Клавиши модификаторы зажатые во время события клавиатуры
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AdapterKeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+
+    /// Cmd на macOS, Win на Windows
+    pub meta: bool,
+}
+
+/// Коды клавиш для AdapterEvent::Key. Буквы и цифры кодируются ASCII кодом символа на
+/// клавише (буквы в верхнем регистре), управляющие клавиши которые есть в ASCII своим
+/// ASCII кодом, остальные клавиши начинаются с 0x1_0000. Адаптер должен сопоставлять
+/// клавиши платформы с этими кодами, неизвестные клавиши можно не отправлять
+pub mod key_code {
+    pub const BACKSPACE: u32 = 8;
+    pub const TAB: u32 = 9;
+    pub const ENTER: u32 = 13;
+    pub const ESCAPE: u32 = 27;
+    pub const SPACE: u32 = 32;
+    pub const DELETE: u32 = 127;
+
+    pub const A: u32 = b'A' as u32;
+    pub const Z: u32 = b'Z' as u32;
+    pub const DIGIT_0: u32 = b'0' as u32;
+    pub const DIGIT_9: u32 = b'9' as u32;
+
+    pub const LEFT: u32 = 0x1_0000;
+    pub const RIGHT: u32 = 0x1_0001;
+    pub const UP: u32 = 0x1_0002;
+    pub const DOWN: u32 = 0x1_0003;
+    pub const HOME: u32 = 0x1_0004;
+    pub const END: u32 = 0x1_0005;
+    pub const PAGE_UP: u32 = 0x1_0006;
+    pub const PAGE_DOWN: u32 = 0x1_0007;
+    pub const INSERT: u32 = 0x1_0008;
+
+    /// F1..F12 идут подряд: F1 + n - 1
+    pub const F1: u32 = 0x1_0100;
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum AdapterResult {
     Void,
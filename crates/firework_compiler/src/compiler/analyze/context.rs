@@ -3,6 +3,8 @@
 
 pub use super::*;
 
+use crate::compiler::codegen::ir::LayoutNode;
+
 pub struct AnalyzeContext {
     // Ошибки компиляции, они накапливаются весь парсинг чтобы по завершению анализа
     // вывести их в терминал. Подробнее про сообщения ошибок можно узнать в файле
@@ -77,6 +79,10 @@ pub struct AnalyzeContext {
 
     // Хук на последний лайаут
     pub layout_hook: Option<IrHook>,
+
+    // Стэк лайаутов в которых сейчас находится анализатор. Виджеты записываются в
+    // детей верхнего лайаута, а закрытый вложенный лайаут становится ребёнком родителя
+    pub layout_stack: Vec<LayoutNode>,
 }
 
 impl AnalyzeContext {
@@ -123,6 +129,7 @@ impl AnalyzeContext {
             first_cycle: None,
             now_component: None,
             layout_hook: None,
+            layout_stack: Vec::new(),
         }
     }
 }
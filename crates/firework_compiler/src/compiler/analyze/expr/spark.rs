@@ -13,9 +13,10 @@ use syn::*;
 
 use super::super::Scope;
 
+use crate::compiler::codegen::ir::actions::{SparkAnimation, SparkAsyncFn};
 use crate::compiler::error::{
-    SPARK_ASYNC_CLOSURE_ERROR, SPARK_ASYNC_KEYWORD_ERROR, SPARK_ASYNC_MOVE_ERROR,
-    SPARK_ASYNC_SYNTAX_ERROR,
+    SPARK_ANIMATE_SYNTAX_ERROR, SPARK_ASYNC_CLOSURE_ERROR, SPARK_ASYNC_KEYWORD_ERROR,
+    SPARK_ASYNC_MOVE_ERROR, SPARK_ASYNC_SYNTAX_ERROR,
 };
 
 /// Валидатор реактивных инициализаций (спарков). Собирает все инициализации спарков в
@@ -33,19 +34,75 @@ pub struct SparkValidator {
     // Выражение внутри spark!()
     pub spark_expr: Option<Expr>,
 
-    // Спарк поддерживает синтаксис spark!(v, async), здесь хранятся аргументы замыкания
-    // кроме первого аргумента (там всегда контроллер)
-    pub spark_async_closure: Option<(Vec<(String, String)>, TokenStream)>,
+    // Спарк поддерживает синтаксис spark!(v, async), здесь хранятся паттерн первого
+    // аргумента замыкания (там всегда контроллер), остальные аргументы и тело
+    pub spark_async_closure: Option<SparkAsyncFn>,
+
+    // Спарк поддерживает синтаксис spark!(v, animate(300ms, ease_out)), здесь хранятся
+    // параметры анимации
+    pub spark_animation: Option<SparkAnimation>,
 
     // Ошибки парсинга
     pub spark_parse_error: Option<(String, Span)>,
 }
 
 impl SparkValidator {
+    /// Парсинг animate(duration, easing), длительность это литерал в миллисекундах
+    /// (без суффикса или ms) или секундах (s), плавность можно не указывать
+    fn parse_animation(call: &ExprCall) -> std::result::Result<SparkAnimation, String> {
+        if call.args.is_empty() || call.args.len() > 2 {
+            return Err(SPARK_ANIMATE_SYNTAX_ERROR.to_string());
+        }
+
+        let duration_ms = match &call.args[0] {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => {
+                let value = lit
+                    .base10_parse::<u32>()
+                    .map_err(|_| SPARK_ANIMATE_SYNTAX_ERROR.to_string())?;
+
+                match lit.suffix() {
+                    "" | "ms" => value,
+                    "s" => value.saturating_mul(1000),
+                    _ => return Err(SPARK_ANIMATE_SYNTAX_ERROR.to_string()),
+                }
+            }
+
+            _ => return Err(SPARK_ANIMATE_SYNTAX_ERROR.to_string()),
+        };
+
+        let easing = match call.args.get(1) {
+            None => quote::quote!(EaseOut),
+
+            Some(Expr::Path(path)) => {
+                match path
+                    .path
+                    .get_ident()
+                    .map(|ident| ident.to_string())
+                    .as_deref()
+                {
+                    Some("linear") => quote::quote!(Linear),
+                    Some("ease_in") => quote::quote!(EaseIn),
+                    Some("ease_out") => quote::quote!(EaseOut),
+                    Some("ease_in_out") => quote::quote!(EaseInOut),
+                    _ => return Err(SPARK_ANIMATE_SYNTAX_ERROR.to_string()),
+                }
+            }
+
+            Some(_) => return Err(SPARK_ANIMATE_SYNTAX_ERROR.to_string()),
+        };
+
+        Ok(SparkAnimation {
+            duration_ms,
+            easing: quote::quote!(firework_ui::Easing::#easing),
+        })
+    }
+
     fn parse_async_closure(
         &mut self,
         tokens: TokenStream,
-    ) -> std::result::Result<(Vec<(String, String)>, TokenStream), String> {
+    ) -> std::result::Result<SparkAsyncFn, String> {
         use syn::parse::Parser;
 
         let args = syn::punctuated::Punctuated::<Expr, Token![,]>::parse_terminated
@@ -70,6 +127,13 @@ impl SparkValidator {
             None => return Err(SPARK_ASYNC_MOVE_ERROR.to_string()),
         }
 
+        // Первый аргумент это контроллер, его паттерн (например mut bridge) переносится
+        // в сгенерированное замыкание как есть
+        let bridge = match closure.inputs.first() {
+            Some(pat) => pat.to_token_stream(),
+            None => return Err(SPARK_ASYNC_SYNTAX_ERROR.to_string()),
+        };
+
         // Пропускаем первый аргумент
         let mut additional_args = Vec::new();
         for arg in closure.inputs.iter().skip(1) {
@@ -80,7 +144,11 @@ impl SparkValidator {
             }
         }
 
-        Ok((additional_args, closure.body.to_token_stream()))
+        Ok(SparkAsyncFn {
+            bridge,
+            args: additional_args,
+            body: closure.body.to_token_stream(),
+        })
     }
 }
 
@@ -95,6 +163,19 @@ impl<'ast> Visit<'ast> for SparkValidator {
                 .parse2(i.mac.tokens.clone());
 
             match args {
+                // Второй аргумент animate(...) делает спарк анимированным
+                Ok(args) if args.len() == 2 && is_animate_call(&args[1]) => {
+                    self.spark_expr = Some(args[0].clone());
+                    self.spark_tokens = Some(args[0].to_token_stream());
+
+                    if let Expr::Call(call) = &args[1] {
+                        match Self::parse_animation(call) {
+                            Ok(animation) => self.spark_animation = Some(animation),
+                            Err(error) => self.spark_parse_error = Some((error, call.span())),
+                        }
+                    }
+                }
+
                 // Если есть второй аргумент то это spark!(v, async), необходимо распарсить
                 // это как асинхронный спарк
                 Ok(args) if args.len() == 2 => {
@@ -102,8 +183,8 @@ impl<'ast> Visit<'ast> for SparkValidator {
                     self.spark_tokens = Some(args[0].to_token_stream());
 
                     match self.parse_async_closure(i.mac.tokens.clone()) {
-                        Ok((args, body)) => {
-                            self.spark_async_closure = Some((args, body));
+                        Ok(async_fn) => {
+                            self.spark_async_closure = Some(async_fn);
                         }
 
                         Err(_) => {
@@ -132,6 +213,10 @@ impl<'ast> Visit<'ast> for SparkValidator {
     }
 }
 
+fn is_animate_call(expr: &Expr) -> bool {
+    matches!(expr, Expr::Call(call) if matches!(&*call.func, Expr::Path(path) if path.path.is_ident("animate")))
+}
+
 pub struct SparkFinder<'a> {
     pub scope: &'a Scope,
     pub found: &'a mut Vec<String>,
@@ -388,6 +473,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -409,6 +495,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -428,6 +515,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -440,6 +528,54 @@ mod tests {
         assert_eq!(validator.spark_count, 1);
     }
 
+    #[test]
+    fn test_spark_validator_parses_animation() {
+        let mut validator = SparkValidator {
+            spark_count: 0,
+            spark_tokens: None,
+            spark_expr: None,
+            spark_async_closure: None,
+            spark_animation: None,
+            spark_parse_error: None,
+        };
+
+        let expr: Expr = parse_quote! {
+            spark!(0.0, animate(2s, linear))
+        };
+
+        validator.visit_expr(&expr);
+
+        let animation = validator.spark_animation.expect("Animation not parsed");
+        assert_eq!(animation.duration_ms, 2000);
+        assert_eq!(
+            animation.easing.to_string(),
+            quote::quote!(firework_ui::Easing::Linear).to_string()
+        );
+        assert!(validator.spark_async_closure.is_none());
+        assert!(validator.spark_parse_error.is_none());
+    }
+
+    #[test]
+    fn test_spark_validator_rejects_invalid_animation() {
+        let mut validator = SparkValidator {
+            spark_count: 0,
+            spark_tokens: None,
+            spark_expr: None,
+            spark_async_closure: None,
+            spark_animation: None,
+            spark_parse_error: None,
+        };
+
+        let expr: Expr = parse_quote! {
+            spark!(0.0, animate(300ms, bounce))
+        };
+
+        validator.visit_expr(&expr);
+
+        assert!(validator.spark_animation.is_none());
+        assert!(validator.spark_parse_error.is_some());
+    }
+
     #[test]
     fn test_spark_finder_detects_spark_variables() {
         let mut scope = Scope::new();
@@ -505,6 +641,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -520,7 +657,9 @@ mod tests {
         assert!(validator.spark_async_closure.is_some());
         assert!(validator.spark_parse_error.is_none());
 
-        let (args, body) = validator.spark_async_closure.unwrap();
+        let async_fn = validator.spark_async_closure.unwrap();
+        let (args, body) = (async_fn.args, async_fn.body);
+        assert_eq!(async_fn.bridge.to_string(), "bridge");
         assert_eq!(args.len(), 1);
         assert_eq!(args[0].0, "ctx");
         assert_eq!(args[0].1, "Context");
@@ -534,6 +673,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -548,7 +688,7 @@ mod tests {
         assert_eq!(validator.spark_count, 1);
         assert!(validator.spark_async_closure.is_some());
 
-        let (args, _) = validator.spark_async_closure.unwrap();
+        let args = validator.spark_async_closure.unwrap().args;
         assert_eq!(args.len(), 3);
         assert_eq!(args[0], ("ctx".to_string(), "Context".to_string()));
         assert_eq!(args[1], ("data".to_string(), "String".to_string()));
@@ -562,6 +702,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -583,6 +724,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -604,6 +746,7 @@ mod tests {
             spark_tokens: None,
             spark_expr: None,
             spark_async_closure: None,
+            spark_animation: None,
             spark_parse_error: None,
         };
 
@@ -96,6 +96,10 @@ impl Parse for WidgetArgs {
 pub fn map_skin(widget_name: &str) -> Option<String> {
     match widget_name {
         "rect" => Some("firework_ui::DefaultRectSkin".to_string()),
+        "text" => Some("firework_ui::DefaultTextSkin".to_string()),
+        "button" => Some("firework_ui::DefaultButtonSkin".to_string()),
+        "app_bar" => Some("firework_ui::DefaultAppBarSkin".to_string()),
+        "image" => Some("firework_ui::DefaultImageSkin".to_string()),
 
         // Не имеет скина так как явлется функциональным виджетом
         _ => None,
@@ -528,7 +528,7 @@ mod tests {
         let result = lifetime_manager.update_scope(new_scope, true, &base_stmt);
 
         assert_eq!(lifetime_manager.scope.depth, 5);
-        assert_eq!(lifetime_manager.scope.is_cycle, true);
+        assert!(lifetime_manager.scope.is_cycle);
         assert_eq!(lifetime_manager.scope.label, Some("loop_label".to_string()));
         assert_eq!(lifetime_manager.scope.screen_index, 12345);
         assert!(
@@ -6,5 +6,6 @@
 // код без угадываний
 
 pub mod effect;
+pub mod restart;
 pub mod spark;
 pub mod spark_ref;
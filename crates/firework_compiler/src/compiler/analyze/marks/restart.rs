@@ -0,0 +1,52 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+pub use super::super::*;
+
+use crate::compiler::codegen::consts::RESTART_FLAG_PREFIX;
+
+impl Analyzer {
+    /// Маркер restart!(spark) перезапускает задачу асинхронного спарка, например для
+    /// pull-to-refresh. Вместе с эффектом получается асинхронный эффект который
+    /// перезапускает задачу при изменении зависимостей:
+    ///
+    /// ```ignore
+    /// effect!(query, {
+    ///     restart!(results);
+    /// });
+    /// ```
+    ///
+    /// Задача создаётся заново на следующем кадре с текущими значениями аргументов
+    /// замыкания, прошлая задача отменяется. Десахаринг уже развернул маркер во взвод
+    /// флага _fwc_restart_name.set(true), здесь проверяется что name это асинхронный спарк
+    pub(crate) fn restart_marker(&mut self, i: &ExprMethodCall) {
+        let Expr::Path(receiver) = &*i.receiver else {
+            return;
+        };
+
+        let Some(name) = receiver.path.get_ident().and_then(|ident| {
+            ident
+                .to_string()
+                .strip_prefix(RESTART_FLAG_PREFIX)
+                .map(String::from)
+        }) else {
+            return;
+        };
+
+        let is_async_spark = self
+            .lifetime_manager
+            .scope
+            .variables
+            .get(&name)
+            .is_some_and(|variable| {
+                variable.is_spark && self.context.ir.async_sparks.contains(&variable.spark_id)
+            });
+
+        // FE036, перезапустить можно только асинхронный спарк
+        if !is_async_spark {
+            self.context
+                .errors
+                .push(compile_error_spanned(i, RESTART_SYNTAX_ERROR));
+        }
+    }
+}
@@ -6,7 +6,6 @@ pub use super::super::*;
 use super::super::type_inference::auto_type::guess_type_from_expr;
 
 use crate::compiler::codegen::ir::actions::SparkAsyncFn;
-
 impl Analyzer {
     /// Маркер spark!()
     pub(crate) fn spark_marker(&mut self, i: &Local) {
@@ -17,6 +16,7 @@ impl Analyzer {
                 spark_tokens: None,
                 spark_expr: None,
                 spark_async_closure: None,
+                spark_animation: None,
                 spark_parse_error: None,
             };
 
@@ -36,6 +36,12 @@ impl Analyzer {
                 return;
             }
 
+            // Неверный синтаксис второго аргумента spark!(v, ...)
+            if let Some((message, span)) = &validator.spark_parse_error {
+                self.context.errors.push(syn::Error::new(*span, message));
+                return;
+            }
+
             // Временный вектор чтобы сложить туда поля, так как пушить нельзя из-за
             // мутабельной ссылки от drain
             let mut temp_fields_to_struct: Vec<(String, String)> = Vec::new();
@@ -87,6 +93,15 @@ impl Analyzer {
                     }
                 }
 
+                // Анимированный спарк хранится в статике вместе с состоянием анимации
+                if validator.spark_animation.is_some() {
+                    spark_type = format!("firework_ui::Animated<{}>", spark_type);
+                    self.context
+                        .ir
+                        .animated_sparks
+                        .insert(self.context.spark_counter);
+                }
+
                 temp_fields_to_struct
                     .push((format!("spark_{}", self.context.spark_counter), spark_type));
 
@@ -95,24 +110,32 @@ impl Analyzer {
                 self.linter
                     .add_spark(id, name.clone(), i.to_token_stream().to_string());
 
-                let async_fn =
-                    if let Some((async_args, async_body)) = &validator.spark_async_closure {
-                        // Для асинхронных спарков нужен рантайм, а он включается фичей async
-                        if !cfg!(feature = "async") {
-                            self.context.errors.push(compile_error_spanned(
-                                &local_init.expr,
-                                SPARK_ASYNC_FEATURE_ERROR,
-                            ));
-                            return;
-                        }
-
-                        Some(SparkAsyncFn {
-                            args: async_args.clone(),
-                            body: async_body.clone(),
-                        })
-                    } else {
-                        None
-                    };
+                let async_fn = if let Some(async_fn) = &validator.spark_async_closure {
+                    // Для асинхронных спарков нужен рантайм, а он включается фичей async
+                    if !cfg!(feature = "async") {
+                        self.context.errors.push(compile_error_spanned(
+                            &local_init.expr,
+                            SPARK_ASYNC_FEATURE_ERROR,
+                        ));
+                        return;
+                    }
+
+                    // Задача спарка хранится в статике рядом со значением, её опрашивает
+                    // тик функция экрана
+                    let (task_type, _) = SparkAsyncFn::task_type(&var_data.variable_type);
+                    temp_fields_to_struct.push((
+                        format!("async_{}", id),
+                        format!(
+                            "firework_ui::runtime::async_spark::AsyncSpark<{}>",
+                            task_type
+                        ),
+                    ));
+                    self.context.ir.async_sparks.insert(id);
+
+                    Some(async_fn.clone())
+                } else {
+                    None
+                };
 
                 self.context.statement.action = FireworkAction::InitialSpark {
                     name: name.clone(),
@@ -122,6 +145,7 @@ impl Analyzer {
                     expr_body_tokens: spark_tokens.clone(),
                     is_mut: var_data.is_mut,
                     async_fn,
+                    animation: validator.spark_animation.clone(),
                 };
                 self.context.ir.push(self.context.statement.clone());
 
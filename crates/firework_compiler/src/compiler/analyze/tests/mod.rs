@@ -33,7 +33,7 @@ fn assert_ir_equal(actual: &[FireworkStatement], expected: &[FireworkAction]) {
 
 fn extract_ir(tokens: proc_macro2::TokenStream) -> FireworkIR {
     // let tokens_vec: Vec<_> = tokens.into_iter().collect();
-    let file: File = syn::parse2(tokens.into()).unwrap();
+    let file: File = syn::parse2(tokens).unwrap();
     prepare_tokens(file, CompileFlags::new(), 0)
         .2
         .expect("IR not found")
@@ -54,6 +54,7 @@ fn create_initial_spark(
         expr_body_tokens: TokenStream::new(),
         is_mut,
         async_fn: None,
+        animation: None,
     }
 }
 
@@ -479,6 +480,7 @@ fn test_with_macro_helper() {
             expr_body_tokens: TokenStream::new(),
             is_mut: true,
             async_fn: None,
+            animation: None,
         },
         Terminator,
     ];
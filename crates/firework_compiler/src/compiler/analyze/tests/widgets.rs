@@ -31,6 +31,7 @@ fn test_analyze_basic_widget() {
             has_microruntime: false,
             skin: "firework_ui::skins::DefaultRectSkin".to_string(),
             is_maybe: None,
+            is_lazy: false,
         }),
         Terminator,
     ];
@@ -69,6 +70,7 @@ fn test_analyze_widget_with_sparks() {
             expr_body_tokens: TokenStream::new(),
             is_mut: true,
             async_fn: None,
+            animation: None,
         },
         InitialSpark {
             name: "y".to_string(),
@@ -78,6 +80,7 @@ fn test_analyze_widget_with_sparks() {
             expr_body_tokens: TokenStream::new(),
             is_mut: true,
             async_fn: None,
+            animation: None,
         },
         WidgetBlock(WidgetDescription {
             widget_type: "rect".to_string(),
@@ -87,6 +90,7 @@ fn test_analyze_widget_with_sparks() {
             has_microruntime: false,
             skin: "firework_ui::skins::DefaultRectSkin".to_string(),
             is_maybe: None,
+            is_lazy: false,
         }),
         DropSpark {
             name: "y".to_string(),
@@ -174,6 +178,7 @@ fn test_analyze_layout_widget() {
             has_microruntime: false,
             skin: "".to_string(),
             is_maybe: None,
+            is_lazy: false,
         }),
         Terminator,
     ];
@@ -212,11 +217,11 @@ fn test_analyze_conditional_widget() {
     let mut found_conditional_widget = false;
 
     for stmt in &ir.statements {
-        if let WidgetBlock(desc) = &stmt.action {
-            if desc.is_maybe.is_some() {
-                found_conditional_widget = true;
-                assert_eq!(desc.widget_type, "rect");
-            }
+        if let WidgetBlock(desc) = &stmt.action
+            && desc.is_maybe.is_some()
+        {
+            found_conditional_widget = true;
+            assert_eq!(desc.widget_type, "rect");
         }
     }
 
@@ -252,14 +257,14 @@ fn test_analyze_widget_in_loop() {
     let mut has_microruntime = false;
     let mut has_dynamic_loop = false;
 
-    for (_span, statements) in &ir.snapshot.statements {
+    for statements in ir.snapshot.statements.values() {
         for stmt in statements {
-            if let WidgetBlock(desc) = &stmt.action {
-                if desc.has_microruntime {
-                    has_microruntime = true;
-                    assert_eq!(desc.widget_type, "rect");
-                    assert!(desc.skin.contains("DynList") || desc.has_microruntime);
-                }
+            if let WidgetBlock(desc) = &stmt.action
+                && desc.has_microruntime
+            {
+                has_microruntime = true;
+                assert_eq!(desc.widget_type, "rect");
+                assert!(desc.skin.contains("DynList") || desc.has_microruntime);
             }
 
             if let DynamicLoopBegin(depth, widgets) = &stmt.action {
@@ -309,6 +314,71 @@ fn test_analyze_nested_widgets() {
     assert_eq!(widget_count, 2);
 }
 
+/// Тест дерева лайаутов, корневой лайаут хранит детей и вложенные лайауты, а у вложенного
+/// лайаута своего дерева нет
+#[test]
+fn test_analyze_layout_tree() {
+    use crate::compiler::codegen::ir::LayoutChild;
+
+    let tokens = quote::quote! {
+        fn screen() {
+            vertical! {
+                layout! {
+                    padding: (10, 10, 10, 10),
+                }
+
+                rect! {
+                    color: (255, 0, 0),
+                }
+
+                horizontal! {
+                    rect! {
+                        color: (0, 255, 0),
+                    }
+                }
+            }
+        }
+    };
+
+    let ir = extract_ir(tokens);
+
+    // Дерево записывается через хук, поэтому оно есть только в снапшоте
+    let layouts: Vec<_> = ir
+        .snapshot
+        .order
+        .iter()
+        .flat_map(|key| ir.snapshot.statements[key].iter())
+        .filter_map(|s| match &s.action {
+            LayoutBlock(name, _, _, tree, _) => Some((name.clone(), tree.clone())),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(layouts.len(), 2);
+    assert!(layouts[1].1.is_none());
+
+    let root = layouts[0].1.clone().expect("Root layout tree not found");
+    assert_eq!(root.name, "vertical");
+    assert!(root.descriptor.is_some());
+    assert_eq!(root.children.len(), 2);
+    assert!(matches!(
+        root.children[0],
+        LayoutChild::Widget { id: 0, .. }
+    ));
+
+    match &root.children[1] {
+        LayoutChild::Layout(inner) => {
+            assert_eq!(inner.name, "horizontal");
+            assert!(matches!(
+                inner.children[0],
+                LayoutChild::Widget { id: 1, .. }
+            ));
+        }
+
+        _ => panic!("Nested layout expected"),
+    }
+}
+
 /// Тест ошибки при отсутствии пропса key у виджета
 #[test]
 fn test_analyze_widget_missing_key_error() {
@@ -368,6 +438,90 @@ fn test_analyze_widget_with_key_type() {
     assert!(found_widget);
 }
 
+/// Тест ёмкости списка цикла через атрибут capacity
+#[test]
+fn test_analyze_widget_with_capacity() {
+    let tokens = quote::quote! {
+        fn screen() {
+            for i in 0..3 {
+                rect! {
+                    #[key_type(i32)]
+                    #[capacity(128)]
+                    key: i,
+                }
+            }
+        }
+    };
+
+    let ir = extract_ir(tokens);
+
+    // Поле скина в структуре экрана получает ёмкость третьим параметром
+    let has_field = ir
+        .screen_structs
+        .values()
+        .flatten()
+        .any(|(_, ty)| ty == "firework_ui::DynList<i32, firework_ui::DefaultRectSkin, 128>");
+
+    assert!(has_field);
+}
+
+#[test]
+fn test_analyze_scroll_state() {
+    let tokens = quote::quote! {
+        fn screen() {
+            scroll! {
+                rect! {
+                    color: (255, 0, 0),
+                }
+            }
+        }
+    };
+
+    let ir = extract_ir(tokens);
+
+    // Состояние прокрутки получает поле виджета, а ребёнок следующий айди
+    let fields: Vec<_> = ir.screen_structs.values().flatten().cloned().collect();
+    assert!(fields.contains(&(
+        "_fwc_widget_object_0".to_string(),
+        "firework_ui::ScrollView".to_string()
+    )));
+
+    let root = ir
+        .snapshot
+        .order
+        .iter()
+        .flat_map(|key| ir.snapshot.statements[key].iter())
+        .find_map(|s| match &s.action {
+            LayoutBlock(_, _, _, Some(tree), Some(0)) => Some(tree.clone()),
+            _ => None,
+        })
+        .expect("Scroll layout not found");
+
+    assert_eq!(root.scroll, Some(0));
+    assert!(matches!(
+        root.children[0],
+        crate::compiler::codegen::ir::LayoutChild::Widget { id: 1, .. }
+    ));
+}
+
+#[test]
+fn test_analyze_widget_capacity_error() {
+    let tokens = quote::quote! {
+        fn screen() {
+            for i in 0..3 {
+                rect! {
+                    #[capacity]
+                    key: i,
+                }
+            }
+        }
+    };
+
+    let (_, error, _) = prepare_tokens(syn::parse2(tokens).unwrap(), CompileFlags::new(), 0);
+
+    assert!(error.unwrap().to_string().contains("FE037"));
+}
+
 /// Тест множества различных виджетов
 #[test]
 fn test_analyze_multiple_widget_types() {
@@ -448,18 +602,17 @@ fn test_analyze_widget_closure_with_sparks() {
     let mut found_closure_with_updates = false;
 
     for stmt in &ir.statements {
-        if let WidgetBlock(desc) = &stmt.action {
-            if let Some(on_click) = desc
+        if let WidgetBlock(desc) = &stmt.action
+            && let Some(on_click) = desc
                 .fields
                 .iter()
                 .find(|(k, _)| k == "on_click")
                 .map(|(_, v)| v)
-            {
-                assert!(on_click.is_fn);
-                found_closure_with_updates = true;
+        {
+            assert!(on_click.is_fn);
+            found_closure_with_updates = true;
 
-                assert!(!on_click.sparks.is_empty());
-            }
+            assert!(!on_click.sparks.is_empty());
         }
     }
 
@@ -361,40 +361,40 @@ mod tests {
 
     #[test]
     fn test_check_mut_primitive() {
-        assert_eq!(is_mutable_method("i32", "push"), false);
-        assert_eq!(is_mutable_method("i64", "push"), false);
-        assert_eq!(is_mutable_method("f32", "push"), false);
-        assert_eq!(is_mutable_method("f64", "push"), false);
-        assert_eq!(is_mutable_method("u32", "push"), false);
+        assert!(!is_mutable_method("i32", "push"));
+        assert!(!is_mutable_method("i64", "push"));
+        assert!(!is_mutable_method("f32", "push"));
+        assert!(!is_mutable_method("f64", "push"));
+        assert!(!is_mutable_method("u32", "push"));
     }
 
     #[test]
     fn test_check_mut_option() {
-        assert_eq!(is_mutable_method("Option<i32>", "take"), true);
+        assert!(is_mutable_method("Option<i32>", "take"));
     }
 
     #[test]
     fn test_check_mut_containers() {
-        assert_eq!(is_mutable_method("Vec<i32>", "push"), true);
-        assert_eq!(is_mutable_method("Vec<i32>", "pop"), true);
-        assert_eq!(is_mutable_method("Vec<i32>", "remove"), true);
-        assert_eq!(is_mutable_method("Vec<i32>", "sort"), true);
-        assert_eq!(is_mutable_method("Vec<i32>", "last"), false);
-        assert_eq!(is_mutable_method("Vec<i32>", "iter"), false);
+        assert!(is_mutable_method("Vec<i32>", "push"));
+        assert!(is_mutable_method("Vec<i32>", "pop"));
+        assert!(is_mutable_method("Vec<i32>", "remove"));
+        assert!(is_mutable_method("Vec<i32>", "sort"));
+        assert!(!is_mutable_method("Vec<i32>", "last"));
+        assert!(!is_mutable_method("Vec<i32>", "iter"));
 
         // String
-        assert_eq!(is_mutable_method("String", "push"), true);
-        assert_eq!(is_mutable_method("String", "push_str"), true);
-        assert_eq!(is_mutable_method("String", "as_str"), false);
+        assert!(is_mutable_method("String", "push"));
+        assert!(is_mutable_method("String", "push_str"));
+        assert!(!is_mutable_method("String", "as_str"));
     }
 
     #[test]
     fn test_check_mut_box_deref() {
-        assert_eq!(is_mutable_method("Box<Vec<i32>>", "push"), true);
-        assert_eq!(is_mutable_method("Box<String>", "push_str"), true);
-        assert_eq!(is_mutable_method("Rc<Vec<i32>>", "push"), true);
-        assert_eq!(is_mutable_method("Rc<String>", "push_str"), true);
-        assert_eq!(is_mutable_method("Arc<Vec<i32>>", "push"), true);
-        assert_eq!(is_mutable_method("Arc<String>", "push_str"), true);
+        assert!(is_mutable_method("Box<Vec<i32>>", "push"));
+        assert!(is_mutable_method("Box<String>", "push_str"));
+        assert!(is_mutable_method("Rc<Vec<i32>>", "push"));
+        assert!(is_mutable_method("Rc<String>", "push_str"));
+        assert!(is_mutable_method("Arc<Vec<i32>>", "push"));
+        assert!(is_mutable_method("Arc<String>", "push_str"));
     }
 }
@@ -169,6 +169,7 @@ impl<'ast> Analyzer {
                                 spark_tokens: None,
                                 spark_expr: None,
                                 spark_async_closure: None,
+                                spark_animation: None,
                                 spark_parse_error: None,
                             };
                             validator.visit_expr(&arm.body);
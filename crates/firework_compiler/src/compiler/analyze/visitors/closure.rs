@@ -9,10 +9,7 @@ impl<'ast> Analyzer {
         let old_in_closure = self.lifetime_manager.in_closure;
 
         if self.lifetime_manager.in_closure.is_none() {
-            let is_reduced = match &*i.body {
-                Expr::Block(_) => false,
-                _ => true,
-            };
+            let is_reduced = !matches!(&*i.body, Expr::Block(_));
 
             self.lifetime_manager.in_closure = Some(ClosureData { reduce: is_reduced });
         }
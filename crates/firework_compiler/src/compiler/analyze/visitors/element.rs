@@ -2,11 +2,14 @@
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
 use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 
 pub use super::super::*;
 
-use crate::compiler::codegen::ir::WidgetDescription;
+use crate::compiler::analyze::expr::widget::WidgetAttributeArg;
+use crate::compiler::codegen::ir::{LayoutChild, LayoutNode, WidgetDescription};
 use crate::compiler::common::widget_kind::is_functional_widget;
+use crate::compiler::desugaring::DesugarVisitor;
 
 impl<'ast> Analyzer {
     /// Макрос который используются не в выражении, а как отдельный statement (команда)
@@ -58,6 +61,7 @@ impl<'ast> Analyzer {
             let mut fields_map: Vec<(String, FireworkWidgetField)> = Vec::new();
 
             let mut key_type = "u64".to_string();
+            let mut capacity: Option<String> = None;
             let mut has_key = false;
             let mut has_skin: Option<String> = None;
 
@@ -84,6 +88,14 @@ impl<'ast> Analyzer {
                 this_field.sparks.extend(analyze_result.sparks);
 
                 if let Expr::Closure(closure) = &prop.value {
+                    // Токены макроса не проходят через общий десахаринг, поэтому короткое
+                    // замыкание (|| a += 1) сворачивается в блок здесь. Иначе обновление
+                    // спарка попадёт под спан виджета и бит будет выставляться при каждом
+                    // проходе, а не при вызове замыкания
+                    let mut closure = closure.clone();
+                    DesugarVisitor.visit_expr_closure_mut(&mut closure);
+                    this_field.token_stream = closure.to_token_stream();
+
                     let saved_parent = self.context.statement.parent_widget_id;
 
                     self.context.statement.parent_widget_id = Some(self.context.widget_counter);
@@ -102,6 +114,23 @@ impl<'ast> Analyzer {
                 {
                     key_type = first.to_string();
                 }
+
+                // FE037: ёмкость это один литерал или константа
+                if let Some(attr) = prop.get_attribute("capacity") {
+                    match attr.args.as_deref() {
+                        Some(
+                            [
+                                arg @ (WidgetAttributeArg::Lit(Lit::Int(_))
+                                | WidgetAttributeArg::Ident(_)),
+                            ],
+                        ) => capacity = Some(arg.to_string()),
+
+                        _ => self.context.errors.push(compile_error_spanned(
+                            &attr.name,
+                            LIST_CAPACITY_SYNTAX_ERROR,
+                        )),
+                    }
+                }
             }
 
             // Если в инициализации виджета есть поле skin то это должна быть структура с
@@ -129,7 +158,14 @@ impl<'ast> Analyzer {
                 has_microruntime = true;
                 self.context.microruntime_widgets.has_widgets = true;
 
-                skin_field = format!("firework_ui::DynList<{}, {}>", key_type, skin_field);
+                skin_field = match &capacity {
+                    Some(capacity) => format!(
+                        "firework_ui::DynList<{}, {}, {}>",
+                        key_type, skin_field, capacity
+                    ),
+
+                    None => format!("firework_ui::DynList<{}, {}>", key_type, skin_field),
+                };
                 self.context
                     .ir
                     .screen_data
@@ -138,6 +174,32 @@ impl<'ast> Analyzer {
                     .dynamic_widgets
                     .push(self.context.widget_counter);
 
+                let is_stacked = self
+                    .context
+                    .layout_stack
+                    .last()
+                    .is_some_and(|node| node.name == "stack");
+
+                if is_stacked && !is_functional_widget(&name) {
+                    self.context
+                        .ir
+                        .stacked_lists
+                        .insert(self.context.widget_counter);
+                }
+
+                let is_lazy = self
+                    .context
+                    .layout_stack
+                    .last()
+                    .is_some_and(|node| node.name == "lazy_list");
+
+                if is_lazy && !is_functional_widget(&name) {
+                    self.context
+                        .ir
+                        .lazy_lists
+                        .insert(self.context.widget_counter);
+                }
+
                 if !has_key {
                     self.context.errors.push(compile_error_spanned(
                         i.tokens.clone(),
@@ -168,6 +230,13 @@ impl<'ast> Analyzer {
                 } else {
                     None
                 },
+
+                is_lazy: self.is_loop
+                    && self
+                        .context
+                        .layout_stack
+                        .last()
+                        .is_some_and(|node| node.name == "lazy_list"),
             };
 
             if name == "layout"
@@ -175,8 +244,12 @@ impl<'ast> Analyzer {
             {
                 let statement = self.get_statement_from_hook(hook.clone());
 
-                if let FireworkAction::LayoutBlock(_, _, desc) = &mut statement.action {
-                    *desc = Some(descriptor);
+                if let FireworkAction::LayoutBlock(_, _, desc, _, _) = &mut statement.action {
+                    *desc = Some(descriptor.clone());
+                }
+
+                if let Some(node) = self.context.layout_stack.last_mut() {
+                    node.descriptor = Some(descriptor);
                 }
 
                 visit::visit_macro(self, i);
@@ -192,6 +265,25 @@ impl<'ast> Analyzer {
                 skin_field.to_string(),
             );
 
+            // Виджет со скином внутри лайаута становится его ребёнком. Компоненты
+            // (функциональные виджеты) не реализуют Widget и в компоновке не участвуют
+            if !descriptor.is_functional
+                && let Some(node) = self.context.layout_stack.last_mut()
+            {
+                let mut sparks: Vec<usize> = self.context.spark_stack.iter().map(|s| s.1).collect();
+
+                for (_, field) in &descriptor.fields {
+                    sparks.extend(field.sparks.iter().map(|s| s.1));
+                }
+
+                node.children.push(LayoutChild::Widget {
+                    id: descriptor.id,
+                    in_loop: descriptor.has_microruntime,
+                    is_maybe: descriptor.is_maybe,
+                    sparks,
+                });
+            }
+
             let widget_block = FireworkAction::WidgetBlock(descriptor);
             self.context.statement.action = widget_block;
 
@@ -267,13 +359,51 @@ impl<'ast> Analyzer {
             // Изначально дескриптор None, но ниже сохраняется хук на эту запись в IR чтобы
             // потом при нахождении дескриптора layour! {} записать внутренности в этот
             // лайаут блок
-            self.context.statement.action =
-                FireworkAction::LayoutBlock(name.clone(), has_microruntime, None);
+            // Состояние прокрутки живёт в поле экрана как скин виджета, поэтому оно
+            // получает айди виджета и сбрасывается вместе со скинами при Navigate
+            let scroll = if name == "scroll" {
+                let id = self.context.widget_counter;
+                self.context.widget_counter += 1;
+
+                self.add_field_to_struct(
+                    format!("widget_object_{}", id),
+                    "firework_ui::ScrollView".to_string(),
+                );
+
+                Some(id)
+            } else {
+                None
+            };
+
+            // lazy_list прямо внутри scroll берёт окно из состояния этого scroll
+            let parent_scroll = if name == "lazy_list" {
+                self.context
+                    .layout_stack
+                    .last()
+                    .and_then(|node| node.scroll)
+            } else {
+                scroll
+            };
+
+            self.context.statement.action = FireworkAction::LayoutBlock(
+                name.clone(),
+                has_microruntime,
+                None,
+                None,
+                parent_scroll,
+            );
 
             self.context.statement.screen_index = self.lifetime_manager.scope.screen_index;
             self.context.statement.depth = self.lifetime_manager.scope.depth;
             self.context.ir.push(self.context.statement.clone());
             self.context.layout_hook = self.get_hook();
+
+            // Хук сохраняется локально так как вложенные лайауты перезапишут layout_hook
+            let hook = self.context.layout_hook.clone();
+            self.context.layout_stack.push(LayoutNode {
+                scroll,
+                ..LayoutNode::new(name.clone())
+            });
             self.lifetime_manager.scope.depth += 1;
             self.context.statement.depth += 1;
 
@@ -287,6 +417,21 @@ impl<'ast> Analyzer {
 
             self.context.layouts_count -= 1;
 
+            // Закрытый лайаут становится ребёнком родителя, а если родителя нет то это
+            // корень и всё дерево записывается в его стейтемент
+            let node = self.context.layout_stack.pop().unwrap_or_default();
+            if let Some(parent) = self.context.layout_stack.last_mut() {
+                parent.children.push(LayoutChild::Layout(node));
+            } else if let Some(hook) = &hook {
+                let statement = self.get_statement_from_hook(hook.clone());
+
+                if let FireworkAction::LayoutBlock(_, _, _, tree, _) = &mut statement.action {
+                    *tree = Some(node);
+                }
+            }
+
+            self.context.layout_hook = hook;
+
             self.lifetime_manager.scope.depth -= 1;
             self.context.statement.depth -= 1;
             self.context.statement.action = FireworkAction::DefaultCode;
@@ -73,6 +73,10 @@ impl<'ast> Analyzer {
 
         function_head.push_str(format!("{}", signature).as_str());
 
+        if matches!(self.context.flags.compile_type, CompileType::Screen) {
+            self.validate_screen_args(sig);
+        }
+
         // Добавление всех аргументов в область видимости как переменных
         for input in &node.sig().inputs {
             self.visit_fn_arg(input);
@@ -163,6 +167,46 @@ impl<'ast> Analyzer {
         self.context.maybe_widgets_counter = 0;
     }
 
+    /// Параметры экрана это функциональные пропсы которые передаются через
+    /// navigate::push_with, поэтому допускается только один аргумент Prop<T>
+    fn validate_screen_args(&mut self, sig: &Signature) {
+        // FE032
+        if sig.inputs.len() > 1 {
+            self.context.errors.push(compile_error_spanned(
+                &sig.inputs,
+                SCREEN_MULTIPLE_ARGS_ERROR,
+            ));
+        }
+
+        // FE031
+        for arg in &sig.inputs {
+            let FnArg::Typed(pat_type) = arg else {
+                self.context.errors.push(compile_error_spanned(
+                    arg,
+                    &SCREEN_INVALID_ARG_ERROR.replace("{}", "self"),
+                ));
+                continue;
+            };
+
+            let raw_type_name = pat_type.ty.to_token_stream().to_string();
+            let clean_type = raw_type_name.replace(" ", "");
+
+            let is_prop =
+                clean_type.starts_with("Prop<") || clean_type.starts_with("firework_ui::Prop<");
+
+            // Параметр сохраняется до Navigate и восстанавливается по имени, поэтому
+            // паттерн должен быть простым идентификатором
+            let is_ident = matches!(&*pat_type.pat, Pat::Ident(_));
+
+            if !is_prop || !is_ident {
+                let msg = SCREEN_INVALID_ARG_ERROR.replace("{}", &raw_type_name);
+                self.context
+                    .errors
+                    .push(compile_error_spanned(pat_type, &msg));
+            }
+        }
+    }
+
     pub(crate) fn analyze_fn_arg(&mut self, i: &'ast FnArg) {
         if let FnArg::Typed(pat_type) = i {
             self.current_type = pat_type.ty.to_token_stream().to_string();
@@ -92,6 +92,8 @@ impl<'ast> Analyzer {
     }
 
     pub(crate) fn analyze_expr_method_call(&mut self, i: &'ast syn::ExprMethodCall) {
+        self.restart_marker(i);
+
         if let Some(root_name) = get_root_variable_name(&i.receiver)
             && let Some(variable) = self.lifetime_manager.scope.variables.get(&root_name)
             && variable.is_spark
@@ -66,7 +66,7 @@ impl CodeBuilder {
             let mut temp_tokens = TokenStream::new();
 
             let tokens = &mut temp_tokens;
-            if self.node_initial_spark(span, struct_name.clone(), tokens, statement)
+            if self.node_initial_spark(span, struct_name.clone(), tokens, statement, visitor)
                 || self.node_spark_ref(span, struct_name.clone(), tokens, statement, visitor)
                 || self.node_widget_block(span, struct_name.clone(), tokens, statement, visitor)
                 || self.node_layout(span, tokens, statement, visitor, &processed_body)
@@ -103,6 +103,7 @@ impl CodeBuilder {
                     struct_name,
                     statement,
                     &processed_body,
+                    visitor,
                 ) {
                     processed_body = temp_tokens;
                     is_body_handled = true;
@@ -126,7 +127,7 @@ impl CodeBuilder {
             if let FireworkAction::DropSpark { .. } = statement.action {
                 let struct_name = format!("ApplicationUiBlockStruct{}", statement.screen_index);
 
-                self.node_drop_spark(span, struct_name, &mut drop_tokens, statement);
+                self.node_drop_spark(span, struct_name, &mut drop_tokens, statement, visitor.ir);
             }
         }
 
@@ -22,6 +22,7 @@ impl CodeBuilder {
         struct_name: String,
         final_tokens: &mut TokenStream,
         statement: &FireworkStatement,
+        ir: &FireworkIR,
     ) -> bool {
         // Генерация возврата владения в BSS
         // TODO: Могут возникнуть ошибки компиляции на уровне rustc если
@@ -32,17 +33,60 @@ impl CodeBuilder {
             let field_name = format!("_fwc_spark_{}", id);
 
             let field_ident = format_ident!("{}", field_name);
-            let spark_ident = format_ident!("{}", name);
+
+            // Анимированный спарк возвращается в статику вместе с состоянием анимации
+            let value = match ir.animated_sparks.contains(id) {
+                true => format!("_fwc_anim_{}.with_value({})", id, name),
+                false => name.clone(),
+            };
+            let value_tokens = Self::convert_string_to_syn(&value);
+
+            // Запрос restart! передаётся задаче асинхронного спарка. При Build задача
+            // только что создана с текущими аргументами, поэтому запрос (например из
+            // эффекта) не нужен
+            if ir.async_sparks.contains(id) {
+                let restart_ident = format_ident!("_fwc_restart_{}", name);
+                // Компонент опрашивает задачу только на запрошенных кадрах, поэтому
+                // перезапуск запрашивает кадр на котором задача будет создана заново
+                let (async_field, request_frame) = match self.flags.compile_type {
+                    CompileType::Component => {
+                        let async_field_ident = format_ident!("_fwc_async_{}", id);
+                        (
+                            quote!(self.#async_field_ident.as_mut()),
+                            quote!(firework_ui::request_tick();),
+                        )
+                    }
+
+                    _ => (
+                        static_gen::field_mut_tokens(
+                            &struct_name.to_uppercase(),
+                            &format!("_fwc_async_{}", id),
+                        ),
+                        TokenStream::new(),
+                    ),
+                };
+
+                final_tokens.extend(quote!(
+                    if #restart_ident.get()
+                        && !firework_ui::tiny_matches!(_fwc_event, firework_ui::LifeCycle::Build)
+                    {
+                        if let Some(_fwc_async) = #async_field {
+                            _fwc_async.request_restart();
+                            #request_frame
+                        }
+                    }
+                ));
+            }
 
             match self.flags.compile_type {
                 CompileType::Component => {
                     final_tokens.extend(quote!(
-                        self.#field_ident = Some(#spark_ident);
+                        self.#field_ident = Some(#value_tokens);
                     ));
                 }
 
                 _ => {
-                    let set_field_str = static_gen::set_field(&struct_name, &field_name, name);
+                    let set_field_str = static_gen::set_field(&struct_name, &field_name, &value);
                     let set_field_expr = Self::convert_string_to_syn(&set_field_str);
 
                     final_tokens.extend(quote!(
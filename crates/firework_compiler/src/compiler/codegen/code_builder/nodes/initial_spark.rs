@@ -4,6 +4,7 @@
 use super::super::*;
 
 use crate::CompileType;
+use crate::compiler::codegen::ir::actions::SparkAsyncFn;
 
 impl CodeBuilder {
     /// Инициализация реактивной переменной. Сюда вписывается первое значение которое
@@ -32,7 +33,270 @@ impl CodeBuilder {
         struct_name: String,
         final_tokens: &mut TokenStream,
         statement: &FireworkStatement,
+        visitor: &mut CodegenVisitor,
     ) -> bool {
+        if let FireworkAction::InitialSpark {
+            id,
+            expr_body,
+            expr_body_tokens,
+            name,
+            is_mut,
+            animation: Some(animation),
+            ..
+        } = &statement.action
+        {
+            let field_name = format!("_fwc_spark_{}", id);
+            let anim_ident = format_ident!("_fwc_anim_{}", id);
+            let ident = format_ident!("{}", name);
+
+            let duration_ms = animation.duration_ms;
+            let easing = &animation.easing;
+
+            let mut modifier = TokenStream::new();
+            if *is_mut {
+                modifier = quote!(mut);
+            }
+
+            let (init_field, take_field) = match self.flags.compile_type {
+                CompileType::Component => {
+                    let field_name_ident = format_ident!("{}", field_name);
+
+                    (
+                        quote!(self.#field_name_ident = Some(firework_ui::Animated::new(
+                            #expr_body_tokens, #duration_ms, #easing
+                        ));),
+                        quote!(self.#field_name_ident.take().expect("State not init")),
+                    )
+                }
+
+                _ => {
+                    let value = format!(
+                        "firework_ui::Animated::new({}, {}, {})",
+                        expr_body, duration_ms, easing
+                    );
+
+                    let set_field_str = static_gen::set_field(&struct_name, &field_name, &value);
+                    let take_field_str =
+                        static_gen::take_field(&struct_name.to_uppercase(), &field_name);
+
+                    (
+                        Self::convert_string_to_syn(&set_field_str),
+                        Self::convert_string_to_syn(&take_field_str),
+                    )
+                }
+            };
+
+            // Из статики берётся состояние анимации, пользователь получает текущее значение.
+            // Неизменяемый спарк никогда не сменит цель, поэтому кадры ему не нужны
+            final_tokens.extend(quote_spanned!(span=>
+                if firework_ui::tiny_matches!(
+                    _fwc_event,
+                    firework_ui::LifeCycle::Build
+                ) {
+                    #init_field
+                }
+
+                let #modifier #anim_ident = #take_field;
+                let #modifier #ident = #anim_ident.value();
+            ));
+
+            if *is_mut {
+                // Пока анимация идёт каждый кадр обновляет значение и помечает бит спарка
+                // так же как обычное обновление и запрашивает следующий кадр, после
+                // окончания экран без tick! перестаёт получать Tick
+                let mask_ident = format_ident!("_fwc_bitmask{}", get_spark_mask(*id));
+                let bit_id = normalize_bit_index(*id);
+                let update_widgets_statement =
+                    self.generate_widget_spark_update(statement, id, visitor.ir);
+
+                final_tokens.extend(quote_spanned!(span=>
+                    firework_ui::on_frame(_fwc_event, |_fwc_dt: u32| {
+                        if let Some(_fwc_value) = #anim_ident.advance(_fwc_dt) {
+                            #ident = _fwc_value;
+                            #mask_ident.set(#mask_ident.get() | (1 << #bit_id));
+                            #update_widgets_statement
+                        }
+                    });
+
+                    if #anim_ident.is_animating() {
+                        firework_ui::request_tick();
+                    }
+                ));
+            }
+
+            return true;
+        }
+
+        if let FireworkAction::InitialSpark {
+            id,
+            expr_body,
+            expr_body_tokens,
+            name,
+            spark_type,
+            async_fn: Some(async_fn),
+            ..
+        } = &statement.action
+        {
+            let field_name = format!("_fwc_spark_{}", id);
+            let async_field_name = format!("_fwc_async_{}", id);
+            let ident = format_ident!("{}", name);
+
+            // Дополнительные аргументы замыкания берутся из переменных с тем же именем,
+            // задача получает их копию через Clone
+            let arg_bindings = async_fn.args.iter().map(|(arg_name, arg_type)| {
+                let arg_ident = format_ident!("{}", arg_name);
+                let arg_type = Self::convert_string_to_syn(arg_type);
+
+                quote!(let #arg_ident: #arg_type = ::core::clone::Clone::clone(&#arg_ident);)
+            });
+
+            // Спарк AsyncValue<T> получает все статусы задачи, обычный спарк только значения
+            let (task_type, is_async_value) = SparkAsyncFn::task_type(spark_type);
+            let task_type = Self::convert_string_to_syn(&task_type);
+            let take_method = match is_async_value {
+                true => format_ident!("take_value"),
+                false => format_ident!("take_ready"),
+            };
+            let bridge = &async_fn.bridge;
+            let body = &async_fn.body;
+            let create_task = quote! {
+                #(#arg_bindings)*
+                let mut _fwc_async =
+                    firework_ui::runtime::async_spark::AsyncSpark::<#task_type>::new();
+                _fwc_async.create(move |#bridge| async move { #body });
+            };
+
+            // Флаг который взводит restart!(name), при возврате спарка в статику он
+            // передаётся задаче и на следующем кадре она создаётся заново
+            let restart_ident = format_ident!("_fwc_restart_{}", name);
+
+            // Бит спарка ставится когда готовое значение записывается в переменную
+            let mask_ident = format_ident!("_fwc_bitmask{}", get_spark_mask(*id));
+            let bit_id = normalize_bit_index(*id);
+            let update_widgets_statement =
+                self.generate_widget_spark_update(statement, id, visitor.ir);
+
+            let assign_ready = quote! {
+                if let Some(_fwc_value) = _fwc_ready {
+                    #ident = _fwc_value;
+                    #mask_ident.set(#mask_ident.get() | (1 << #bit_id));
+                    #update_widgets_statement
+                }
+            };
+
+            match self.flags.compile_type {
+                // У компонента нет тик функции, поэтому задача опрашивается в его флэше
+                // на событии кадра. Кадры компонент запрашивает сам пока задача идёт или
+                // её статусы не записаны в спарк, без подписки экрана на Tick
+                CompileType::Component => {
+                    let field_ident = format_ident!("{}", field_name);
+                    let async_field_ident = format_ident!("{}", async_field_name);
+
+                    final_tokens.extend(quote_spanned!(span=>
+                        if firework_ui::tiny_matches!(
+                            _fwc_event,
+                            firework_ui::LifeCycle::Build
+                        ) {
+                            self.#field_ident = Some(#expr_body_tokens);
+
+                            #create_task
+                            self.#async_field_ident = Some(_fwc_async);
+                        }
+
+                        let mut #ident = self.#field_ident.take()
+                            .expect("State not init");
+                        let #restart_ident = &::core::cell::Cell::new(false);
+
+                        firework_ui::on_frame(_fwc_event, |_fwc_dt: u32| {
+                            if let Some(_fwc_async) = self.#async_field_ident.as_mut() {
+                                _fwc_async.poll_ready();
+                            }
+                        });
+
+                        if firework_ui::tiny_matches!(
+                            _fwc_event,
+                            firework_ui::LifeCycle::Event
+                        ) {
+                            if self.#async_field_ident
+                                .as_mut()
+                                .is_some_and(|_fwc_async| _fwc_async.take_restart())
+                            {
+                                #create_task
+                                self.#async_field_ident = Some(_fwc_async);
+                            }
+
+                            let _fwc_ready = self.#async_field_ident
+                                .as_mut()
+                                .and_then(|_fwc_async| _fwc_async.#take_method());
+
+                            #assign_ready
+                        }
+
+                        if self.#async_field_ident
+                            .as_ref()
+                            .is_some_and(|_fwc_async| _fwc_async.is_pending())
+                        {
+                            firework_ui::request_tick();
+                        }
+                    ));
+                }
+
+                // Задачу экрана опрашивает его тик функция, при готовом значении она
+                // отправляет событие AsyncReady и значение записывается здесь
+                _ => {
+                    let struct_name_upper = struct_name.to_uppercase();
+
+                    let set_field_str =
+                        static_gen::set_field(&struct_name, &field_name, &expr_body.to_string());
+                    let set_field_expr = Self::convert_string_to_syn(&set_field_str);
+
+                    let set_async_str =
+                        static_gen::set_field(&struct_name, &async_field_name, "_fwc_async");
+                    let set_async_expr = Self::convert_string_to_syn(&set_async_str);
+
+                    let take_field_str = static_gen::take_field(&struct_name_upper, &field_name);
+                    let take_field_expr = Self::convert_string_to_syn(&take_field_str);
+
+                    let async_field =
+                        static_gen::field_mut_tokens(&struct_name_upper, &async_field_name);
+
+                    final_tokens.extend(quote_spanned!(span=>
+                        if firework_ui::tiny_matches!(
+                            _fwc_event,
+                            firework_ui::LifeCycle::Build
+                        ) {
+                            #set_field_expr
+
+                            #create_task
+                            #set_async_expr
+                        }
+
+                        let mut #ident = #take_field_expr;
+                        let #restart_ident = &::core::cell::Cell::new(false);
+
+                        if firework_ui::tiny_matches!(
+                            _fwc_event,
+                            firework_ui::LifeCycle::Event
+                        ) {
+                            if #async_field
+                                .is_some_and(|_fwc_async| _fwc_async.take_restart())
+                            {
+                                #create_task
+                                #set_async_expr
+                            }
+
+                            let _fwc_ready = #async_field
+                                .and_then(|_fwc_async| _fwc_async.#take_method());
+
+                            #assign_ready
+                        }
+                    ));
+                }
+            }
+
+            return true;
+        }
+
         if let FireworkAction::InitialSpark {
             id,
             expr_body,
@@ -1,22 +1,158 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+use proc_macro2::Ident;
+
 use super::super::*;
 
+use crate::compiler::codegen::ir::{LayoutChild, LayoutNode, WidgetDescription};
+
 impl CodeBuilder {
+    /// Лайаут блок. Тело лайаута (виджеты) выполняется как есть, а после него у корневого
+    /// лайаута генерируется проход компоновки по всему дереву: каждый ребёнок измеряется
+    /// через Widget::layout и ставится на место через Widget::position. Компоновка
+    /// запускается при Build, Navigate, изменении размера окна, если изменился спарк от
+    /// которого зависит пропс одного из детей (например размер), если в списке цикла
+    /// появились, исчезли или переставились элементы или если сдвинулась прокрутка scroll
     #[cfg_attr(feature = "trace", tracing::instrument(skip_all, fields(span = ?span)))]
     pub fn node_layout(
         &mut self,
         span: Span,
         final_tokens: &mut TokenStream,
         statement: &FireworkStatement,
-        _visitor: &mut CodegenVisitor,
+        visitor: &mut CodegenVisitor,
         processed_body: &TokenStream,
     ) -> bool {
-        if let FireworkAction::LayoutBlock(_name, _microruntime, _descriptor) = &statement.action {
+        if let FireworkAction::LayoutBlock(name, _microruntime, descriptor, tree, scroll) =
+            &statement.action
+        {
+            let struct_name = format!("ApplicationUiBlockStruct{}", statement.screen_index);
+            let instance_ident = format_ident!("{}_INSTANCE", struct_name.to_uppercase());
+
+            // Касания и кадры двигают прокрутку до тела, чтобы lazy_list внутри уже видел
+            // новое окно
+            if let Some(id) = scroll.filter(|_| name == "scroll") {
+                let update = self.scroll_update(statement, descriptor.as_ref(), id, visitor);
+
+                final_tokens.extend(quote_spanned!(span=>
+                    #update
+                ));
+            }
+
+            #[cfg(feature = "safety-multithread")]
+            let instance_access = quote! {
+                let mut _fwc_inst = #instance_ident.get()
+                    .expect("Instance not initialized").lock().unwrap();
+            };
+
+            #[cfg(not(feature = "safety-multithread"))]
+            let instance_access = quote! {
+                let _fwc_inst = unsafe { &mut *::core::ptr::addr_of_mut!(#instance_ident) };
+            };
+
+            // Окно lazy_list считается до тела, виджеты цикла внутри проверяют по нему
+            // свою строку. Внутри scroll окно это прокрутка и размер родителя
+            if name == "lazy_list" {
+                let params = layout_params(descriptor.as_ref());
+                let window = match scroll {
+                    Some(id) => {
+                        let field_ident = format_ident!("_fwc_widget_object_{}", id);
+
+                        quote! {
+                            {
+                                #instance_access
+                                ::firework_ui::layout::LazyWindow::in_scroll(
+                                    #params,
+                                    _fwc_inst.#field_ident.as_ref(),
+                                )
+                            }
+                        }
+                    }
+
+                    None => quote!(::firework_ui::layout::LazyWindow::new(#params)),
+                };
+
+                final_tokens.extend(quote_spanned!(span=>
+                    {
+                        let _fwc_lazy_window = #window;
+                        #processed_body
+                    }
+                ));
+            } else {
+                final_tokens.extend(quote_spanned!(span=>
+                    #processed_body
+                ));
+            }
+
+            // Вложенные лайауты компонуются вместе с корнем
+            let Some(tree) = tree else {
+                return true;
+            };
+
+            let mut sparks = Vec::new();
+            tree.collect_sparks(&mut sparks);
+
+            // Компоновка при монтировании и при изменении размера окна
+            let mut condition: Vec<TokenStream> = vec![
+                quote_spanned!(span=>
+                    (::firework_ui::tiny_matches!(_fwc_event,
+                        ::firework_ui::LifeCycle::Navigate |
+                        ::firework_ui::LifeCycle::Build
+                    ))
+                ),
+                quote_spanned!(span=> ::firework_ui::layout::needs_relayout()),
+            ];
+
+            for id in sparks {
+                condition.push(check_flag_tokens(
+                    &get_mask_name(id),
+                    normalize_bit_index(id),
+                ));
+            }
+
+            // Списки циклов и прокрутки которые изменились в этом проходе. Флаг сбрасывается
+            // при проверке, поэтому проверяются все поля без короткого замыкания и до
+            // остальных условий
+            let mut lists = Vec::new();
+            tree.collect_lists(&mut lists);
+
+            if !lists.is_empty() {
+                let instance_name = struct_name.to_uppercase();
+                let changed = lists.iter().map(|id| {
+                    let field = static_gen::field_mut_tokens(
+                        &instance_name,
+                        &format!("_fwc_widget_object_{}", id),
+                    );
+
+                    quote! {
+                        (#field).is_some_and(|_fwc_state| _fwc_state.take_changed())
+                    }
+                });
+
+                condition.insert(0, quote_spanned!(span=> ( #( #changed )|* )));
+            }
+
+            let cursor = format_ident!("_fwc_layout_cursor0");
+            let (kind, params) = layout_kind_and_params(tree);
+            let children = self.layout_children(tree, &cursor, 0);
+            let (attach, finish) = scroll_hooks(tree, &cursor);
+
             final_tokens.extend(quote_spanned!(span=>
-                println!("Layout placeholder");
-                #processed_body
+                if #( #condition )||* {
+                    #instance_access
+
+                    let mut #cursor = ::firework_ui::layout::LayoutCursor::new(
+                        #kind,
+                        #params,
+                        ::firework_ui::layout::root_constraints(),
+                        (0, 0),
+                    );
+
+                    #attach
+                    #children
+
+                    let _ = #finish;
+                }
             ));
 
             return true;
@@ -24,4 +160,211 @@ impl CodeBuilder {
 
         false
     }
+
+    /// Генерирует размещение всех детей узла через курсор с именем cursor
+    fn layout_children(&mut self, node: &LayoutNode, cursor: &Ident, depth: usize) -> TokenStream {
+        let mut tokens = TokenStream::new();
+
+        for child in &node.children {
+            match child {
+                LayoutChild::Widget {
+                    id,
+                    in_loop,
+                    is_maybe,
+                    ..
+                } => {
+                    let field_ident = format_ident!("_fwc_widget_object_{}", id);
+
+                    let mut place = if *in_loop && node.name == "lazy_list" {
+                        quote! {
+                            if let Some(_fwc_list) = _fwc_inst.#field_ident.as_mut() {
+                                _fwc_list.set_top(#cursor.content_top());
+
+                                for (_fwc_w, _fwc_placed, _fwc_row) in _fwc_list.iter_rows_mut() {
+                                    #cursor.place_row(_fwc_w, _fwc_placed, _fwc_row);
+                                }
+
+                                #cursor.lazy_rows(_fwc_list.rows());
+                            }
+                        }
+                    } else if *in_loop {
+                        quote! {
+                            if let Some(_fwc_list) = _fwc_inst.#field_ident.as_mut() {
+                                for (_fwc_w, _fwc_placed) in _fwc_list.iter_placed_mut() {
+                                    #cursor.place_cached(_fwc_w, _fwc_placed);
+                                }
+                            }
+                        }
+                    } else {
+                        quote! {
+                            if let Some(_fwc_w) = _fwc_inst.#field_ident.as_mut() {
+                                #cursor.place(_fwc_w);
+                            }
+                        }
+                    };
+
+                    // Скрытый условный виджет не занимает место
+                    if let Some(local_id) = is_maybe {
+                        let mask_name = self.cache.cache_widget_bitmask(get_spark_mask(*local_id));
+                        let bit = normalize_bit_index(*local_id);
+
+                        place = quote! {
+                            if (#mask_name.get() & (1 << #bit)) != 0 {
+                                #place
+                            }
+                        };
+                    }
+
+                    tokens.extend(place);
+                }
+
+                LayoutChild::Layout(inner) => {
+                    let inner_cursor = format_ident!("_fwc_layout_cursor{}", depth + 1);
+                    let (kind, params) = layout_kind_and_params(inner);
+                    let children = self.layout_children(inner, &inner_cursor, depth + 1);
+                    let (attach, finish) = scroll_hooks(inner, &inner_cursor);
+
+                    tokens.extend(quote! {
+                        {
+                            let mut #inner_cursor = #cursor.nested(#kind, #params);
+                            #attach
+                            #children
+                            #cursor.advance(#finish);
+                        }
+                    });
+                }
+            }
+        }
+
+        tokens
+    }
+
+    /// Обновление состояния scroll в начале его тела. Если offset в layout! {} это
+    /// спарк, то прокрутка привязана к нему: сдвиг пальцем или инерцией записывается в
+    /// спарк как обычное обновление, а присваивание спарку прокручивает контейнер
+    fn scroll_update(
+        &self,
+        statement: &FireworkStatement,
+        descriptor: Option<&WidgetDescription>,
+        id: usize,
+        visitor: &mut CodegenVisitor,
+    ) -> TokenStream {
+        let struct_name = format!("ApplicationUiBlockStruct{}", statement.screen_index);
+        let instance_ident = format_ident!("{}_INSTANCE", struct_name.to_uppercase());
+        let field_ident = format_ident!("_fwc_widget_object_{}", id);
+        let params = layout_params(descriptor);
+
+        let binding = descriptor
+            .and_then(|descriptor| descriptor.fields.iter().find(|(name, _)| name == "offset"))
+            .and_then(|(_, field)| match field.sparks.as_slice() {
+                [(name, spark_id)] if field.string.trim() == name => Some((name, *spark_id)),
+                _ => None,
+            });
+
+        let bound = binding.is_some();
+        let assign = match binding {
+            Some((name, spark_id)) => {
+                let spark_ident = format_ident!("{}", name);
+                let mask_ident = format_ident!("_fwc_bitmask{}", get_spark_mask(spark_id));
+                let bit_id = normalize_bit_index(spark_id);
+                let update_widgets_statement =
+                    self.generate_widget_spark_update(statement, &spark_id, visitor.ir);
+
+                quote! {
+                    if let Some(_fwc_offset) = _fwc_offset {
+                        #spark_ident = _fwc_offset;
+                        #mask_ident.set(#mask_ident.get() | (1 << #bit_id));
+                        #update_widgets_statement
+                    }
+                }
+            }
+
+            None => quote! {
+                let _ = _fwc_offset;
+            },
+        };
+
+        #[cfg(feature = "safety-multithread")]
+        let state = quote! {
+            #instance_ident.get()
+                .expect("Instance not initialized").lock().unwrap()
+                .#field_ident.get_or_insert_with(::firework_ui::ScrollView::new)
+        };
+
+        #[cfg(not(feature = "safety-multithread"))]
+        let state = quote! {
+            unsafe {
+                (*::core::ptr::addr_of_mut!(#instance_ident))
+                    .#field_ident.get_or_insert_with(::firework_ui::ScrollView::new)
+            }
+        };
+
+        quote! {
+            {
+                let _fwc_offset = #state.update(_fwc_event, #params, #bound);
+                #assign
+            }
+        }
+    }
+}
+
+/// Подключение курсора scroll к его состоянию до детей и завершение компоновки после
+/// них. Для остальных лайаутов только обычный finish
+fn scroll_hooks(node: &LayoutNode, cursor: &Ident) -> (TokenStream, TokenStream) {
+    let Some(id) = node.scroll else {
+        return (TokenStream::new(), quote!(#cursor.finish()));
+    };
+
+    let field_ident = format_ident!("_fwc_widget_object_{}", id);
+
+    (
+        quote! {
+            if let Some(_fwc_scroll) = _fwc_inst.#field_ident.as_ref() {
+                #cursor.scroll_with(_fwc_scroll);
+            }
+        },
+        quote! {
+            match _fwc_inst.#field_ident.as_mut() {
+                Some(_fwc_scroll) => #cursor.finish_scroll(_fwc_scroll),
+                None => #cursor.finish(),
+            }
+        },
+    )
+}
+
+/// Вид лайаута по имени макроса и заполнение LayoutParams из дескриптора layout! {}.
+/// Каждое поле дескриптора становится вызовом метода у LayoutParams, так же как у скинов
+fn layout_kind_and_params(node: &LayoutNode) -> (TokenStream, TokenStream) {
+    let kind = match node.name.as_str() {
+        "horizontal" => quote!(::firework_ui::layout::LayoutKind::Horizontal),
+        "stack" => quote!(::firework_ui::layout::LayoutKind::Stack),
+        "absoulute" => quote!(::firework_ui::layout::LayoutKind::Absolute),
+        "lazy_list" => quote!(::firework_ui::layout::LayoutKind::Lazy),
+        "scroll" => quote!(::firework_ui::layout::LayoutKind::Scroll),
+        _ => quote!(::firework_ui::layout::LayoutKind::Vertical),
+    };
+
+    (kind, layout_params(node.descriptor.as_ref()))
+}
+
+/// LayoutParams из дескриптора layout! {}
+fn layout_params(descriptor: Option<&WidgetDescription>) -> TokenStream {
+    let mut params = quote!(::firework_ui::layout::LayoutParams::default());
+
+    if let Some(descriptor) = descriptor {
+        for (name, field) in &descriptor.fields {
+            if field.is_fn {
+                continue;
+            }
+
+            let method_ident = format_ident!("{}", name);
+            let value = &field.token_stream;
+
+            params.extend(quote! {
+                .#method_ident(#value)
+            });
+        }
+    }
+
+    params
 }
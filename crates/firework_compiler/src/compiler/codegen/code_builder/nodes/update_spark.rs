@@ -17,7 +17,7 @@ impl CodeBuilder {
         processed_body: &TokenStream,
         visitor: &mut CodegenVisitor,
     ) -> bool {
-        if let FireworkAction::UpdateSpark(_, id, _, is_closure) = &statement.action {
+        if let FireworkAction::UpdateSpark(name, id, _, is_closure) = &statement.action {
             // Реактивная переменная (спарк) обновилась то нужно изменить бит
             // который соотвествует этому спарку. Для каждого диапазона спарков
             // (от 0 до 63) своя битовая маска, поэтому эта строка позволяет
@@ -82,6 +82,55 @@ impl CodeBuilder {
             // для множества битовых масок
             let bit_id = normalize_bit_index(*id);
             let statement = quote! { #mask_ident.set(#mask_ident.get() | (1 << #bit_id)); };
+            let mut mark = quote! {
+                #statement
+                #update_widgets_statement
+            };
+
+            // У анимированного спарка пользователь меняет цель анимации: перед обновлением
+            // в переменную подставляется цель (чтобы spark += 1 считался от неё), а после
+            // обновления новое значение становится целью и переменная возвращается к
+            // текущему значению анимации. Значение не изменилось, бит дальше ставят кадры
+            // которые запрашиваются пока анимация идёт, здесь только если анимация
+            // закончилась сразу (нулевая длительность). Та же цель ничего не меняет
+            let processed_body = &match visitor.ir.animated_sparks.contains(id) {
+                true => {
+                    let anim_ident = format_ident!("_fwc_anim_{}", id);
+                    let spark_ident = format_ident!("{}", name);
+
+                    // Обновление в замыкании без блока приходит выражением без ;
+                    let ends_with_semi = matches!(
+                        processed_body.clone().into_iter().last(),
+                        Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == ';'
+                    );
+                    let semi = (!ends_with_semi).then(|| quote!(;));
+
+                    let body = quote! {
+                        #[allow(unused_assignments)]
+                        {
+                            #spark_ident = #anim_ident.target();
+                        }
+                        #processed_body #semi
+
+                        if #spark_ident == #anim_ident.target() {
+                            #spark_ident = #anim_ident.value();
+                        } else {
+                            #spark_ident = #anim_ident.retarget(#spark_ident);
+
+                            if #anim_ident.is_animating() {
+                                firework_ui::request_tick();
+                            } else {
+                                #mark
+                            }
+                        }
+                    };
+
+                    mark = TokenStream::new();
+                    body
+                }
+
+                false => processed_body.clone(),
+            };
 
             if need_condition {
                 final_tokens.extend(quote_spanned!(span=>
@@ -90,15 +139,13 @@ impl CodeBuilder {
                         firework_ui::tiny_matches!(_fwc_event,
                             firework_ui::LifeCycle::Build) {
 
-                        #statement
-                        #update_widgets_statement
+                        #mark
                         #processed_body
                     }
                 ));
             } else {
                 final_tokens.extend(quote_spanned!(span=>
-                    #statement
-                    #update_widgets_statement
+                    #mark
                     #processed_body
                 ));
             }
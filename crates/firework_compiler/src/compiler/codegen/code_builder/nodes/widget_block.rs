@@ -1,6 +1,7 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+use proc_macro2::Ident;
 use syn::Expr;
 use syn::visit_mut::VisitMut;
 
@@ -26,12 +27,16 @@ impl CodeBuilder {
 
             let skin_path = self.cache.cache_skin_path(&description.skin);
 
-            // При навигации нужно сгенерировать конструкцию виджета на основе скина
-            let mut widget_init = quote_spanned! { span=>
-                #skin_path::new(1).expect("Failed to create new widget instance")
-            };
+            // Пропсы которые выставляются скину сразу после создания
+            let mut widget_setters = TokenStream::new();
 
-            let mut widget_reactive = quote! {};
+            // Скин первым получает событие чтобы обновить визуальное состояние (нажатие,
+            // наведение) до вызова замыканий обработчиков
+            let mut widget_reactive = quote! {
+                if firework_ui::tiny_matches!(_fwc_event, firework_ui::LifeCycle::Event) {
+                    firework_ui::std_widgets::widget::Widget::on_event(_fwc_wb_1);
+                }
+            };
 
             // Выражение ключа, ключ нужен в динамических списках для оптимизиации
             // обхода в микрорантайме
@@ -67,10 +72,53 @@ impl CodeBuilder {
                         // Трнасформация замыкания
                         visitor.visit_expr_mut(&mut closure_expr);
 
-                        widget_reactive.extend(quote_spanned! (span => {
-                            let _fwc_cl = #closure_expr;
-                            // _fwc_cl();
-                        }));
+                        // Замыкание вызывается только в фазе Event если рантайм отнёс
+                        // текущее событие к этому виджету. Спарки внутри замыкания
+                        // выставляют свои биты и реактивный цикл сделает ещё одну итерацию
+                        // уже в фазе Reactive, поэтому повторного вызова не будет
+                        let handler = match name.as_str() {
+                            // Клавиатура не привязана к хэндлу, событие получают все
+                            // виджеты с обработчиком
+                            "on_key" => quote_spanned! (span=>
+                                if let Some(_fwc_key) = firework_ui::current_key() {
+                                    let mut _fwc_cl = firework_ui::key_handler(#closure_expr);
+                                    _fwc_cl(_fwc_key);
+                                }
+                            ),
+
+                            "on_char" => quote_spanned! (span=>
+                                if let Some(_fwc_char) = firework_ui::current_char() {
+                                    let mut _fwc_cl = firework_ui::char_handler(#closure_expr);
+                                    _fwc_cl(_fwc_char);
+                                }
+                            ),
+
+                            // Касания сравниваются с хэндлом скина, нажатие и отпускание
+                            // парные (см. firework_ui::is_gesture)
+                            _ => {
+                                let gesture = touch_gesture(name);
+
+                                quote_spanned! (span=>
+                                    if firework_ui::is_gesture(
+                                        firework_ui::TouchGesture::#gesture,
+                                        _fwc_wb_1.__id(),
+                                    ) {
+                                        let mut _fwc_cl = #closure_expr;
+                                        _fwc_cl();
+                                    }
+                                )
+                            }
+                        };
+
+                        // Выключенный виджет (например disabled кнопка) не вызывает
+                        // обработчики
+                        widget_reactive.extend(quote_spanned! (span=>
+                            if firework_ui::tiny_matches!(_fwc_event, firework_ui::LifeCycle::Event)
+                                && firework_ui::std_widgets::widget::Widget::is_enabled(_fwc_wb_1)
+                            {
+                                #handler
+                            }
+                        ));
 
                         continue;
                     }
@@ -79,9 +127,9 @@ impl CodeBuilder {
                 // Название метода берётся из названия поля
                 let method_ident = format_ident!("{}", name);
 
-                // Генерируется установка значения по билдер паттерну. Через точку
-                // вызывается метод, имя метода должено соотвестовать названию
-                // поля. Внутрь метода пробрасывается само значение
+                // Генерируется установка значения через метод скина, имя метода
+                // должено соотвестовать названию поля. Внутрь метода пробрасывается
+                // само значение, а результат отбрасывается
                 //
                 // rect! {
                 //  position: (10, 10),
@@ -89,12 +137,15 @@ impl CodeBuilder {
                 //
                 // Превращается в
                 // // Структура скина и айди лайаута в аргументах
-                // [SKIN]::new(1).unwrap()
-                //  .position((10, 10)) // Имя поля становится вызовом метода
-                //                       // а вторая часть выражения становится
-                //                       // аргументов этого метода
-                widget_init.extend(quote! {
-                    .#method_ident(#field_value)
+                // let mut _fwc_init = [SKIN]::new(1).unwrap();
+                // _fwc_init.position((10, 10)); // Имя поля становится вызовом метода
+                //                                // а вторая часть выражения становится
+                //                                // аргументов этого метода
+                //
+                // Вызовы отдельными выражениями, а не цепочкой, поэтому скину не нужен
+                // Copy: методы могут принимать &mut self как и при обновлении пропса
+                widget_setters.extend(quote! {
+                    _fwc_init.#method_ident(#field_value);
                 });
 
                 if !field.sparks.is_empty() {
@@ -117,6 +168,15 @@ impl CodeBuilder {
                 }
             }
 
+            // При навигации нужно сгенерировать конструкцию виджета на основе скина
+            let widget_init = quote_spanned! { span=>
+                {
+                    let mut _fwc_init = #skin_path::new(1).expect("Failed to create new widget instance");
+                    #widget_setters
+                    _fwc_init
+                }
+            };
+
             // Токен стрим для хранения обновления нужного бита в бит маске (активации
             // бита) чтобы показать что виджет жив
             let mut widget_update_bitmask = TokenStream::new();
@@ -151,10 +211,11 @@ impl CodeBuilder {
                     .unwrap().#field_ident
             };
 
+            // Ссылка мутабельная так как реактивные пропсы вроде width меняют состояние скина
             #[cfg(not(feature = "safety-multithread"))]
             let match_value = quote! {
                 unsafe {
-                    (*::core::ptr::addr_of!(#instance_ident_upper)).#field_ident.as_ref()
+                    (*::core::ptr::addr_of_mut!(#instance_ident_upper)).#field_ident.as_mut()
                 }
             };
 
@@ -163,6 +224,46 @@ impl CodeBuilder {
                 // У виджетов в циклах обязан быть ключ, это проверяется анализатором
                 let key_token = key_expr.expect("Key field not found");
 
+                // Строка lazy_list сначала берёт спрятанный скин ушедшей строки и заново
+                // выставляет ему все пропсы, новый скин создаётся только при пустом пуле
+                let vacant_arm = if description.is_lazy {
+                    quote_spanned!(span=>
+                        match vacant.recycle() {
+                            Ok(_fwc_init) => {
+                                #widget_setters
+                                _fwc_init
+                            }
+
+                            Err(vacant) => vacant.insert(#widget_init),
+                        }
+                    )
+                } else {
+                    quote_spanned!(span=> vacant.insert(#widget_init))
+                };
+
+                let mut list_body = quote_spanned!(span=>
+                    let mut _fwc_wb_1 = match _fwc_list_ref.entry(#key_token) {
+                        firework_ui::ListEntry::Occupied(existing) => existing,
+                        firework_ui::ListEntry::Vacant(vacant) => #vacant_arm,
+                    };
+
+                    #widget_reactive
+                    #widget_update_bitmask
+                );
+
+                // Строка lazy_list считается для каждой итерации, но виджет создаётся и
+                // обновляется только если строка попала в окно. Строки вне окна не
+                // попадают в проход и список прячет их скины в пул в end_pass
+                if description.is_lazy {
+                    list_body = quote_spanned!(span=>
+                        let _fwc_row = _fwc_list_ref.next_row();
+
+                        if _fwc_lazy_window.contains(_fwc_row, _fwc_list_ref.top()) {
+                            #list_body
+                        }
+                    );
+                }
+
                 #[cfg(feature = "safety-multithread")]
                 final_tokens.extend(quote_spanned!(span=>
                     {
@@ -172,13 +273,7 @@ impl CodeBuilder {
                         // SAFETY: List initialized before
                         let mut _fwc_list_ref = _fwc_inst.#field_ident.as_mut().unwrap();
 
-                        let mut _fwc_wb_1 = match _fwc_list_ref.entry(#key_token) {
-                            firework_ui::ListEntry::Occupied(existing) => existing,
-                            firework_ui::ListEntry::Vacant(vacant) => vacant.insert(#widget_init),
-                        };
-
-                        #widget_reactive
-                        #widget_update_bitmask
+                        #list_body
                     }
                 ));
 
@@ -189,20 +284,14 @@ impl CodeBuilder {
                             (*::core::ptr::addr_of_mut!(#instance_ident_upper)).#field_ident.as_mut().unwrap()
                         };
 
-                        let mut _fwc_wb_1 = match _fwc_list_ref.entry(#key_token) {
-                            firework_ui::ListEntry::Occupied(existing) => existing,
-                            firework_ui::ListEntry::Vacant(vacant) => vacant.insert(#widget_init),
-                        };
-
-                        #widget_reactive
-                        #widget_update_bitmask
+                        #list_body
                     }
                 ));
             } else {
                 #[cfg(feature = "safety-multithread")]
                 final_tokens.extend(quote_spanned!(span=>
                     match #match_value {
-                        Some(ref _fwc_wb_1) => {
+                        Some(ref mut _fwc_wb_1) => {
                             #widget_reactive
                             #widget_update_bitmask
                         },
@@ -222,7 +311,7 @@ impl CodeBuilder {
                 #[cfg(not(feature = "safety-multithread"))]
                 final_tokens.extend(quote_spanned!(span=>
                     match #match_value {
-                        Some(ref _fwc_wb_1) => {
+                        Some(_fwc_wb_1) => {
                             #widget_reactive
                             #widget_update_bitmask
                         },
@@ -267,6 +356,31 @@ fn need_skip_props(props: &str) -> bool {
     props == "key" // Для динамических списков
 }
 
+/// Пропсы обработчиков событий, их замыкания вызываются в фазе Event
 fn is_event(props: &str) -> bool {
-    props == "on_click"
+    matches!(
+        props,
+        "on_click"
+            | "on_press"
+            | "on_release"
+            | "on_move"
+            | "on_cancel"
+            | "on_long_press"
+            | "on_key"
+            | "on_char"
+    )
+}
+
+/// Жест рантайма для пропса обработчика касаний
+fn touch_gesture(props: &str) -> Ident {
+    let gesture = match props {
+        "on_press" => "Press",
+        "on_release" => "Release",
+        "on_move" => "Move",
+        "on_cancel" => "Cancel",
+        "on_long_press" => "LongPress",
+        _ => "Click",
+    };
+
+    format_ident!("{}", gesture)
 }
@@ -9,3 +9,7 @@ pub const SCREEN_HEADER: &str = "
 
 pub const CHECK_EVENT_INCODE: &str =
     " (firework_ui::tiny_matches!(_fwc_event, firework_ui::LifeCycle::Event)) ";
+
+// Префикс флага перезапуска асинхронного спарка, маркер restart!(name) разворачивается во
+// взвод флага с именем префикс + name
+pub const RESTART_FLAG_PREFIX: &str = "_fwc_restart_";
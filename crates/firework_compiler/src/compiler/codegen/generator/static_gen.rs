@@ -158,6 +158,126 @@ pub(crate) fn init_instance_tokens(
     }
 }
 
+/// Хелпер для сброса полей экземпляра в None. Используется при Navigate: примитивы
+/// виджетов с прошлого посещения экрана уже удалены RemoveAll, поэтому скины сбрасываются
+/// и создаются заново, а спарки остаются нетронутыми
+#[cfg(not(feature = "safety-multithread"))]
+pub(crate) fn reset_fields_tokens(instance_name: &str, fields: &[&str]) -> TokenStream {
+    let instance_ident = format_ident!("{}_INSTANCE", instance_name);
+    let field_idents = fields.iter().map(|name| format_ident!("{}", name));
+
+    quote! {
+        unsafe {
+            let _fwc_inst = &mut *::core::ptr::addr_of_mut!(#instance_ident);
+            #(_fwc_inst.#field_idents = None;)*
+        }
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub(crate) fn reset_fields_tokens(instance_name: &str, fields: &[&str]) -> TokenStream {
+    let instance_ident = format_ident!("{}_INSTANCE", instance_name);
+    let field_idents = fields.iter().map(|name| format_ident!("{}", name));
+
+    quote! {
+        {
+            let mut _fwc_inst = #instance_ident.get().unwrap().lock().unwrap();
+            #(_fwc_inst.#field_idents = None;)*
+        }
+    }
+}
+
+/// Хелпер который перед сбросом полей освобождает ресурсы скинов которые не удаляет
+/// RemoveAll (Widget::release), например текстуры image! {}. Состояние прокрутки своих
+/// ресурсов не держит и пропускается
+#[cfg(not(feature = "safety-multithread"))]
+pub(crate) fn release_widgets_tokens(
+    instance_name: &str,
+    fields: &[(String, String)],
+) -> TokenStream {
+    let instance_ident = format_ident!("{}_INSTANCE", instance_name);
+    let releases: Vec<TokenStream> = fields.iter().filter_map(release_field_tokens).collect();
+
+    if releases.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        unsafe {
+            let _fwc_inst = &mut *::core::ptr::addr_of_mut!(#instance_ident);
+            #(#releases)*
+        }
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub(crate) fn release_widgets_tokens(
+    instance_name: &str,
+    fields: &[(String, String)],
+) -> TokenStream {
+    let instance_ident = format_ident!("{}_INSTANCE", instance_name);
+    let releases: Vec<TokenStream> = fields.iter().filter_map(release_field_tokens).collect();
+
+    if releases.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        {
+            let mut _fwc_inst = #instance_ident.get().unwrap().lock().unwrap();
+            #(#releases)*
+        }
+    }
+}
+
+// Освобождение одного поля виджета, None если поле не скин
+fn release_field_tokens((name, ty): &(String, String)) -> Option<TokenStream> {
+    if !name.starts_with("_fwc_widget_object_") || ty.is_empty() || ty == "firework_ui::ScrollView"
+    {
+        return None;
+    }
+
+    let field_ident = format_ident!("{}", name);
+
+    // DynList освобождает все свои элементы
+    if ty.starts_with("firework_ui::DynList<") {
+        return Some(quote! {
+            if let Some(_fwc_list) = _fwc_inst.#field_ident.as_mut() {
+                _fwc_list.release();
+            }
+        });
+    }
+
+    Some(quote! {
+        if let Some(_fwc_skin) = _fwc_inst.#field_ident.as_mut() {
+            ::firework_ui::std_widgets::widget::Widget::release(_fwc_skin);
+        }
+    })
+}
+
+/// Выражение Option<&mut T> для поля экземпляра. В многопоточном варианте ссылка живёт
+/// вместе с блокировкой только до конца стейтемента, поэтому результат нужно сразу
+/// использовать в том же выражении
+#[cfg(not(feature = "safety-multithread"))]
+pub(crate) fn field_mut_tokens(instance_name: &str, field_name: &str) -> TokenStream {
+    let instance_ident = format_ident!("{}_INSTANCE", instance_name);
+    let field_ident = format_ident!("{}", field_name);
+
+    quote! {
+        unsafe { (*::core::ptr::addr_of_mut!(#instance_ident)).#field_ident.as_mut() }
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub(crate) fn field_mut_tokens(instance_name: &str, field_name: &str) -> TokenStream {
+    let instance_ident = format_ident!("{}_INSTANCE", instance_name);
+    let field_ident = format_ident!("{}", field_name);
+
+    quote! {
+        #instance_ident.get().unwrap().lock().unwrap().#field_ident.as_mut()
+    }
+}
+
 /// Хелпер который позволяет установить значение поля экземпляра экрана (слайда). Важно, метод
 /// считает что все поля в экземпляре это Option поэтому автоматически задае́т
 /// им значение как Some( ... ) где "..." это ввод
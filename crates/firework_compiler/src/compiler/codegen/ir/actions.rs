@@ -2,7 +2,9 @@
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
 use proc_macro2::TokenStream;
+use quote::ToTokens;
 
+use super::layout::LayoutNode;
 use super::reactive_block::FireworkReactiveBlock;
 use super::widget::WidgetDescription;
 
@@ -22,6 +24,7 @@ pub enum FireworkAction {
         expr_body_tokens: TokenStream,
         is_mut: bool,
         async_fn: Option<SparkAsyncFn>,
+        animation: Option<SparkAnimation>,
     },
 
     // Когда спарк выходит из области видимости необходимо вернуть владение обратно в
@@ -86,11 +89,15 @@ pub enum FireworkAction {
     ),
 
     // Лайаут блок, первое значение это название лайаута, второе значение это нужен
-    // ли микрорантайм
+    // ли микрорантайм. Дерево детей заполняется только у корневого лайаута, вложенные
+    // лайауты компонуются вместе с ним
     LayoutBlock(
         /* Название лайаута */ String,
         /* Нужен ли цикл */ bool,
         /* Дескриптор */ Option<WidgetDescription>,
+        /* Дерево (только у корня) */ Option<LayoutNode>,
+        /* Поле состояния прокрутки: своё у scroll, родителя у lazy_list */
+        Option<usize>,
     ),
 
     // Виджет, декларативное описание для скина. Виджет это compile-time концепция,
@@ -110,6 +117,32 @@ pub enum FireworkAction {
 
 #[derive(Debug, Clone)]
 pub struct SparkAsyncFn {
+    pub bridge: TokenStream,
     pub args: Vec<(String, String)>,
     pub body: TokenStream,
 }
+
+impl SparkAsyncFn {
+    /// Тип значения которое отправляет задача. Спарк с типом AsyncValue<T> получает
+    /// вместе со значением загрузку и ошибки, задача такого спарка отправляет T
+    pub fn task_type(spark_type: &str) -> (String, bool) {
+        if let Ok(syn::Type::Path(type_path)) = syn::parse_str::<syn::Type>(spark_type)
+            && let Some(segment) = type_path.path.segments.last()
+            && segment.ident == "AsyncValue"
+            && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+            && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+        {
+            return (inner.to_token_stream().to_string(), true);
+        }
+
+        (spark_type.to_string(), false)
+    }
+}
+
+// Анимированный спарк spark!(v, animate(300ms, ease_out)), easing это путь к варианту
+// firework_ui::Easing
+#[derive(Debug, Clone)]
+pub struct SparkAnimation {
+    pub duration_ms: u32,
+    pub easing: TokenStream,
+}
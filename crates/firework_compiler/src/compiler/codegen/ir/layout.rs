@@ -0,0 +1,108 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use super::widget::WidgetDescription;
+
+/// Узел дерева лайаутов. Анализатор собирает дерево пока обходит лайаут блоки, а
+/// кодогенератор по нему генерирует проход компоновки для корневого лайаута
+#[derive(Debug, Clone, Default)]
+pub struct LayoutNode {
+    /// Название лайаута (vertical, horizontal, stack, absoulute, lazy_list, scroll)
+    pub name: String,
+
+    /// Дескриптор из layout! {}, если он был
+    pub descriptor: Option<WidgetDescription>,
+
+    /// Айди поля с состоянием прокрутки (_fwc_widget_object_{id}), только у scroll
+    pub scroll: Option<usize>,
+
+    /// Дети в порядке декларации
+    pub children: Vec<LayoutChild>,
+}
+
+#[derive(Debug, Clone)]
+pub enum LayoutChild {
+    /// Виджет со скином
+    Widget {
+        /// Айди виджета (поле _fwc_widget_object_{id})
+        id: usize,
+
+        /// Находится ли виджет в динамическом списке
+        in_loop: bool,
+
+        /// Индекс условного виджета в битовой маске
+        is_maybe: Option<usize>,
+
+        /// Спарки от которых зависят пропсы виджета и его условие, при их изменении
+        /// компоновка запускается заново
+        sparks: Vec<usize>,
+    },
+
+    /// Вложенный лайаут
+    Layout(LayoutNode),
+}
+
+impl LayoutNode {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            descriptor: None,
+            scroll: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Все спарки от которых зависит компоновка этого поддерева
+    pub fn collect_sparks(&self, sparks: &mut Vec<usize>) {
+        if let Some(descriptor) = &self.descriptor {
+            for (_, field) in &descriptor.fields {
+                for (_, id) in &field.sparks {
+                    if !sparks.contains(id) {
+                        sparks.push(*id);
+                    }
+                }
+            }
+        }
+
+        for child in &self.children {
+            match child {
+                LayoutChild::Widget {
+                    sparks: widget_sparks,
+                    ..
+                } => {
+                    for id in widget_sparks {
+                        if !sparks.contains(id) {
+                            sparks.push(*id);
+                        }
+                    }
+                }
+
+                LayoutChild::Layout(node) => node.collect_sparks(sparks),
+            }
+        }
+    }
+
+    /// Айди полей у которых компоновка проверяет take_changed: динамические списки
+    /// (изменился состав или порядок) и состояния scroll (сдвинулась прокрутка)
+    pub fn collect_lists(&self, lists: &mut Vec<usize>) {
+        if let Some(id) = self.scroll {
+            lists.push(id);
+        }
+
+        for child in &self.children {
+            match child {
+                LayoutChild::Widget {
+                    id, in_loop: true, ..
+                } => {
+                    if !lists.contains(id) {
+                        lists.push(*id);
+                    }
+                }
+
+                LayoutChild::Widget { .. } => {}
+
+                LayoutChild::Layout(node) => node.collect_lists(lists),
+            }
+        }
+    }
+}
@@ -4,15 +4,17 @@
 #![allow(dead_code)]
 
 pub mod actions;
+pub mod layout;
 pub mod reactive_block;
 pub mod shared;
 pub mod snapshot;
 pub mod widget;
 
 use proc_macro2::Span;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub use actions::FireworkAction;
+pub use layout::{LayoutChild, LayoutNode};
 pub use reactive_block::FireworkReactiveBlock;
 pub use shared::FireworkSharedState;
 pub use shared::SharedData;
@@ -80,6 +82,21 @@ pub struct FireworkIR {
 
     pub shared: SharedData,
 
+    // Айди анимированных спарков spark!(v, animate(...)), их обновление и возврат в
+    // статику проходит через состояние анимации
+    pub animated_sparks: HashSet<usize>,
+
+    // Айди асинхронных спарков, только их можно перезапустить маркером restart!
+    pub async_sparks: HashSet<usize>,
+
+    // Айди виджетов циклов в stack!: их элементы перекрываются, поэтому после прохода
+    // список выставляет Z переставленным
+    pub stacked_lists: HashSet<usize>,
+
+    // Айди виджетов циклов lazy_list!, их DynList прячет скины ушедших строк в пул и
+    // отдаёт их новым строкам
+    pub lazy_lists: HashSet<usize>,
+
     pub component_props: HashMap<
         String,
         Vec<(
@@ -140,6 +157,10 @@ impl FireworkIR {
             screens: Vec::new(),
             screen_data: HashMap::new(),
             shared: SharedData::new(),
+            animated_sparks: HashSet::new(),
+            async_sparks: HashSet::new(),
+            stacked_lists: HashSet::new(),
+            lazy_lists: HashSet::new(),
             component_props: HashMap::new(),
             span: Span::call_site(),
         }
@@ -55,4 +55,8 @@ pub struct WidgetDescription {
     /// Рендерится ли виджет условно (Some) и если да то какой у него индекс в битовыъ масок
     /// на основе maybe_widgets_counter
     pub is_maybe: Option<usize>,
+
+    /// Виджет из цикла внутри lazy_list!, такие виджеты создаются только для строк
+    /// попавших в видимое окно
+    pub is_lazy: bool,
 }
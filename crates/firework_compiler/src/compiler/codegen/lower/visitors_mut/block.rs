@@ -29,19 +29,17 @@ impl LowerVisitor<'_> {
                 continue;
             }
 
-            if let Stmt::Macro(m) = &mut statement {
-                if let Some(segment) = m.mac.path.segments.last() {
-                    let name = segment.ident.to_string();
-                    if is_layout(&name) {
-                        let tokens = &m.mac.tokens;
-                        let tokens_with_braces = quote::quote!({ #tokens });
-                        if let Ok(mut inner_block) = syn::parse2::<Block>(tokens_with_braces) {
-                            self.lower_block_mut(&mut inner_block);
-                            let cleaned = &inner_block.stmts;
+            if let Stmt::Macro(m) = &mut statement
+                && let Some(segment) = m.mac.path.segments.last()
+                && is_layout(&segment.ident.to_string())
+            {
+                let tokens = &m.mac.tokens;
+                let tokens_with_braces = quote::quote!({ #tokens });
+                if let Ok(mut inner_block) = syn::parse2::<Block>(tokens_with_braces) {
+                    self.lower_block_mut(&mut inner_block);
+                    let cleaned = &inner_block.stmts;
 
-                            m.mac.tokens = quote::quote!(#(#cleaned)*);
-                        }
-                    }
+                    m.mac.tokens = quote::quote!(#(#cleaned)*);
                 }
             }
 
@@ -62,6 +60,7 @@ impl LowerVisitor<'_> {
                         struct_name.clone(),
                         &mut drop_tokens,
                         &stmt,
+                        self.ir,
                     );
                 }
             }
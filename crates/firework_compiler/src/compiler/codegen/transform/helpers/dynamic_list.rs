@@ -4,13 +4,17 @@
 #[cfg(feature = "trace")]
 use tracing::instrument;
 
+use std::collections::HashSet;
+
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 
 #[cfg_attr(feature = "trace", instrument(skip_all, fields(struct_name_raw = ?struct_name_raw)))]
 pub fn generate_lifecycle(
     struct_name_raw: &str,
     dynamic_widgets: &[usize],
+    stacked_lists: &HashSet<usize>,
+    lazy_lists: &HashSet<usize>,
     span: Span,
 ) -> (TokenStream, TokenStream) {
     let mut begin_tokens = TokenStream::new();
@@ -21,6 +25,18 @@ pub fn generate_lifecycle(
     for widget_id in dynamic_widgets {
         let field_ident = format_ident!("_fwc_widget_object_{}", widget_id);
 
+        // Элементы которые перекрываются получают Z по порядку списка
+        let order_z = stacked_lists
+            .contains(widget_id)
+            .then(|| quote!(list.order_z();));
+
+        // Строки lazy_list переиспользуют скины ушедших из окна строк
+        let new_list = if lazy_lists.contains(widget_id) {
+            quote!(firework_ui::DynList::new().with_recycling())
+        } else {
+            quote!(firework_ui::DynList::new())
+        };
+
         #[cfg(feature = "safety-multithread")]
         {
             begin_tokens.extend(quote_spanned!(span=>
@@ -29,7 +45,7 @@ pub fn generate_lifecycle(
                         .expect("Firework: Instance not initialized").lock().unwrap();
 
                     if _fwc_inst.#field_ident.is_none() {
-                        _fwc_inst.#field_ident = Some(firework_ui::DynList::new());
+                        _fwc_inst.#field_ident = Some(#new_list);
                     }
 
                     _fwc_inst.#field_ident.as_mut().unwrap().begin_pass();
@@ -43,6 +59,7 @@ pub fn generate_lifecycle(
 
                     if let Some(list) = _fwc_inst.#field_ident.as_mut() {
                         list.end_pass();
+                        #order_z
                     }
                 }
             ));
@@ -55,7 +72,7 @@ pub fn generate_lifecycle(
                     let inst = &mut *::core::ptr::addr_of_mut!(#instance_ident_upper);
 
                     if inst.#field_ident.is_none() {
-                        inst.#field_ident = Some(firework_ui::DynList::new());
+                        inst.#field_ident = Some(#new_list);
                     }
 
                     inst.#field_ident.as_mut().unwrap().begin_pass();
@@ -69,6 +86,7 @@ pub fn generate_lifecycle(
                         .#field_ident.as_mut()
                     {
                         list.end_pass();
+                        #order_z
                     }
                 }
             ));
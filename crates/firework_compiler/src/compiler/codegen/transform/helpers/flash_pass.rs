@@ -6,6 +6,8 @@ use tracing::instrument;
 
 use quote::quote;
 
+use crate::RetentionPolicy;
+
 use super::super::*;
 
 impl CodegenVisitor<'_> {
@@ -22,6 +24,61 @@ impl CodegenVisitor<'_> {
             .unwrap_or(&[]);
 
         let instance_init = init_instance_tokens(&instance_name, &struct_name_raw, fields);
+
+        // Скины виджетов (в том числе DynList циклов) которые нужно создать заново при
+        // возвращении на экран. Перед сбросом скины освобождают текстуры, иначе каждое
+        // возвращение на экран с image! {} оставляло бы их в адаптере
+        let widgets_release = release_widgets_tokens(&instance_name, fields);
+        let widget_fields: Vec<&str> = fields
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| name.starts_with("_fwc_widget_object_"))
+            .collect();
+        let widgets_reset = if widget_fields.is_empty() {
+            quote! {}
+        } else {
+            let reset = reset_fields_tokens(&instance_name, &widget_fields);
+            quote! { #widgets_release #reset }
+        };
+
+        // Всё состояние экрана кроме _fwc_screen_id, сбрасывается при каждом входе на
+        // экран с #[ui(reset_on_enter)]
+        let state_fields: Vec<&str> = fields
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| *name != "_fwc__fwc_screen_id")
+            .collect();
+
+        let navigate_branch = match self.flags.retention {
+            RetentionPolicy::ResetOnEnter => {
+                let state_reset = if state_fields.is_empty() {
+                    quote! {}
+                } else {
+                    let reset = reset_fields_tokens(&instance_name, &state_fields);
+                    quote! { #widgets_release #reset }
+                };
+
+                quote! {
+                    #state_reset
+                    _fwc_event = ::firework_ui::LifeCycle::Build;
+                }
+            }
+
+            _ => quote! {
+                #widgets_reset
+                _fwc_event = ::firework_ui::LifeCycle::Navigate;
+            },
+        };
+
+        // Экран с #[ui(drop_on_leave)] регистрирует функцию которая освободит его
+        // статику когда фокус получит другой экран (см. transform_ui_function)
+        let leave_registration = if matches!(self.flags.retention, RetentionPolicy::DropOnLeave) {
+            let leave_ident = format_ident!("_fwc_leave_{}", id);
+            quote! { ::firework_ui::set_leave_fn(#leave_ident); }
+        } else {
+            quote! {}
+        };
+
         let fn_path: Path = syn::parse_str(function_name)
             .unwrap_or_else(|_| panic!("Invalid function path: {}", function_name));
 
@@ -36,31 +93,42 @@ impl CodegenVisitor<'_> {
         // то это Navigate, если совпадает то это Event, если была итерация
         // реактивного цикла то Reactive, изначально Zero. Все контексты:
         //  - Build: Первый старт экрана или компонента, инициализируется
-        //    состояние. Выполняется только один раз
-        //  - Navigate: Переход с одного экрана на другой. Виджеты удаляются
-        //    (Как и при navigate) и всё создаётся с нуля
+        //    состояние. Выполняется только один раз. Перед Build и Navigate
+        //    enter_screen удаляет примитивы прошлого экрана (RemoveAll) или оставляет
+        //    их до конца анимации перехода (см. firework_ui::Transition)
+        //  - Navigate: Переход на уже построенный экран (см. firework_ui::navigate).
+        //    Примитивы удаляются, скины виджетов сбрасываются и создаются заново, а
+        //    спарки сохраняют значения с прошлого посещения. С #[ui(reset_on_enter)]
+        //    сбрасывается всё состояние и вместо Navigate выполняется Build
         //  - Event: Какой либо ивент
         //  - Reactive: Пустышка чтобы обновление спарков не запустилось снова без
         //    явной причины. (Детальнее в ../code_builder/nodes/update_spark.rs)
-        //  Функция сама устанавливает себя как фокус (SET_FOCUS константа)
+        //  Функция сама устанавливает себя как фокус (SET_FOCUS константа). Перед
+        //  инициализацией экран который получает фокус даёт прошлому экрану освободить
+        //  состояние (#[ui(drop_on_leave)])
         let block_tokens = quote! {
             {
                 let _fwc_id: u128 = #id;
 
+                if _fwc_id != ::firework_ui::get_focus_id() {
+                    ::firework_ui::leave_focus();
+                }
+
                 #instance_init
 
                 if _fwc_id == ::firework_ui::get_focus_id() && !_fwc_build {
                     _fwc_event = ::firework_ui::LifeCycle::Event;
                 } else {
                     if _fwc_build {
-                        ::firework_ui::adapter_command(::firework_ui::AdapterCommand::RemoveAll);
+                        ::firework_ui::enter_screen();
                         _fwc_event = ::firework_ui::LifeCycle::Build;
                     } else {
-                        ::firework_ui::adapter_command(::firework_ui::AdapterCommand::RemoveAll);
-                        _fwc_event = ::firework_ui::LifeCycle::Navigate;
+                        ::firework_ui::enter_screen();
+                        #navigate_branch
                     }
 
                     ::firework_ui::set_tick_fn(#tick_ident);
+                    #leave_registration
                 }
 
                 ::firework_ui::set_focus_id(_fwc_id);
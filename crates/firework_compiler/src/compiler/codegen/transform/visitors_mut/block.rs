@@ -21,19 +21,17 @@ impl CodegenVisitor<'_> {
             let span = statement.span();
             let mut layout_body = None;
 
-            if let Stmt::Macro(m) = &mut statement {
-                if let Some(segment) = m.mac.path.segments.last() {
-                    let name = segment.ident.to_string();
-                    if is_layout(&name) {
-                        let tokens = &m.mac.tokens;
-                        let tokens_with_braces = quote::quote!({ #tokens });
+            if let Stmt::Macro(m) = &mut statement
+                && let Some(segment) = m.mac.path.segments.last()
+                && is_layout(&segment.ident.to_string())
+            {
+                let tokens = &m.mac.tokens;
+                let tokens_with_braces = quote::quote!({ #tokens });
 
-                        if let Ok(mut inner_block) = syn::parse2::<Block>(tokens_with_braces) {
-                            self.analyze_block_mut(&mut inner_block);
-                            let processed_children = &inner_block.stmts;
-                            layout_body = Some(quote::quote!(#(#processed_children)*));
-                        }
-                    }
+                if let Ok(mut inner_block) = syn::parse2::<Block>(tokens_with_braces) {
+                    self.analyze_block_mut(&mut inner_block);
+                    let processed_children = &inner_block.stmts;
+                    layout_body = Some(quote::quote!(#(#processed_children)*));
                 }
             }
 
@@ -8,9 +8,10 @@ use syn::parse_quote;
 
 pub use super::super::*;
 
-use crate::CompileType;
 use crate::compiler::codegen::generator::static_gen;
+use crate::compiler::codegen::ir::FireworkAction;
 use crate::compiler::codegen::transform::visitors_mut::self_visitor::SelfFieldAdder;
+use crate::{CompileType, RetentionPolicy};
 
 impl CodegenVisitor<'_> {
     /// Обрабатывает верхний уровень в вызове компилятора (item), функции, структуры и так
@@ -99,14 +100,54 @@ impl CodegenVisitor<'_> {
             .map(|(_, _, id)| *id);
 
         // Генерация tick функции для экранов. Она нужна для асинхронности и в будущем
-        // анимаций. Вызывается каждый кадр
+        // анимаций. Вызывается каждый кадр пока экран в фокусе
         if matches!(self.flags.compile_type, CompileType::Screen) {
-            let tick_name = format_ident!("_fwc_tick_{}", self.ui_id.unwrap_or(0));
-            new_items.push(parse_quote! {
-                fn #tick_name() {
-                    // println!("Hello world");
-                }
-            });
+            let ui_id = self.ui_id.unwrap_or(0);
+            let tick_name = format_ident!("_fwc_tick_{}", ui_id);
+            let instance_name = format!("ApplicationUiBlockStruct{}", ui_id).to_uppercase();
+
+            // Опрос задач асинхронных спарков экрана, если хотя-бы одна прислала значение
+            // то экран получает событие AsyncReady и записывает значения в спарки
+            let polls: Vec<TokenStream> = self
+                .ir
+                .statements
+                .iter()
+                .filter(|statement| statement.screen_index == ui_id)
+                .filter_map(|statement| match &statement.action {
+                    FireworkAction::InitialSpark {
+                        id,
+                        async_fn: Some(_),
+                        ..
+                    } => Some(static_gen::field_mut_tokens(
+                        &instance_name,
+                        &format!("_fwc_async_{}", id),
+                    )),
+                    _ => None,
+                })
+                .map(|field| {
+                    quote! {
+                        _fwc_ready |= #field
+                            .is_some_and(|_fwc_async| _fwc_async.poll_ready());
+                    }
+                })
+                .collect();
+
+            if polls.is_empty() {
+                new_items.push(parse_quote! {
+                    fn #tick_name() {}
+                });
+            } else {
+                new_items.push(parse_quote! {
+                    fn #tick_name() {
+                        let mut _fwc_ready = false;
+                        #(#polls)*
+
+                        if _fwc_ready {
+                            ::firework_ui::dispatch_event(::firework_ui::CurrentEvent::AsyncReady);
+                        }
+                    }
+                });
+            }
         }
 
         self.visit_block_mut(block);
@@ -131,14 +172,80 @@ impl CodegenVisitor<'_> {
 
             self.generate_build(new_items, instance_item, &fields, id, span);
 
+            // Функция ухода для #[ui(drop_on_leave)], обнуляет все поля статики включая
+            // _fwc_screen_id, поэтому следующий вход на экран будет Build
+            if matches!(self.flags.compile_type, CompileType::Screen)
+                && matches!(self.flags.retention, RetentionPolicy::DropOnLeave)
+            {
+                let leave_ident = format_ident!("_fwc_leave_{}", id);
+                let field_names: Vec<&str> =
+                    fields_data.iter().map(|(name, _)| name.as_str()).collect();
+                let release = static_gen::release_widgets_tokens(&instance_name, &fields_data);
+                let reset = static_gen::reset_fields_tokens(&instance_name, &field_names);
+
+                new_items.push(parse_quote! {
+                    fn #leave_ident() {
+                        #release
+                        #reset
+                    }
+                });
+            }
+
             // Оригинальное тело функции (уже трансформированное), так как block
             // не реализует Default нужно использовать std::mem::replace, идёт
             // парсинг обычного пустого блока чтобы заменить на него оригинал, а
             // оригинальые данные забрать сюда чтобы избежать клонирования
             let mut original_block = std::mem::replace(block, parse_quote!({}));
 
+            // Экран с параметром (см. firework_ui::navigate::push_with). Параметр
+            // хранится в записи истории навигации, а фокусом становится точка входа без
+            // аргументов которая вызывает экран с None, при Build и Navigate параметр
+            // берётся из показываемой записи, в остальных фазах он None как и
+            // функциональные пропсы компонентов
+            let screen_param = self.screen_param(sig);
+            let mut params_prologue = TokenStream::new();
+            let mut params_restore = TokenStream::new();
+            let mut focus_name = function_name.clone();
+
+            if let Some((pat, ty)) = screen_param {
+                let entry_ident = format_ident!("_fwc_entry_{}", id);
+                let fn_ident = &sig.ident;
+                let param_ident = &pat.ident;
+
+                new_items.push(parse_quote! {
+                    fn #entry_ident() {
+                        #fn_ident(None);
+                    }
+                });
+
+                // В режиме подготовки push_with экран только сообщает точку входа, флэш
+                // выполнится когда навигация покажет экран. Аргумент функции не читается,
+                // навигация всегда вызывает экран с None
+                params_prologue = quote! {
+                    let _ = #param_ident;
+
+                    if ::firework_ui::navigate::is_staging() {
+                        ::firework_ui::navigate::stage(#entry_ident);
+                        return;
+                    }
+                };
+
+                params_restore = quote! {
+                    let #pat: #ty = if ::firework_ui::tiny_matches!(_fwc_event,
+                        ::firework_ui::LifeCycle::Navigate |
+                        ::firework_ui::LifeCycle::Build
+                    ) {
+                        ::firework_ui::navigate::args()
+                    } else {
+                        None
+                    };
+                };
+
+                focus_name = entry_ident.to_string();
+            }
+
             let reactive_output = self.generate_reactive(id);
-            let generated_block = self.generate_flash_pass(id, &function_name);
+            let generated_block = self.generate_flash_pass(id, &focus_name);
 
             let bitmask_statements = reactive_output.bitmask_statements;
             let bitmask_clone_statements = reactive_output.bitmask_clone_statements;
@@ -215,6 +322,7 @@ impl CodegenVisitor<'_> {
                 // (с _) то предупреждений не будет, а компилятор раста просто вырежет
                 // этот код в релизной сборке как мёртвый
                 final_stmts.extend(parse_batch(quote! {
+                    #params_prologue
                     let mut _fwc_event = firework_ui::LifeCycle::Navigate;
                     #init_code
                     let mut _fwc_guard: u8 = 0;
@@ -225,8 +333,11 @@ impl CodegenVisitor<'_> {
                 if !has_return {
                     let mut loop_stmts = Vec::new();
 
+                    // Параметр восстанавливается в каждой итерации чтобы в Reactive
+                    // он уже был None и обновления спарков от него не зациклились
                     loop_stmts.extend(parse_batch(quote! {
                         #(#bitmask_clone_statements)*
+                        #params_restore
                     }));
 
                     loop_stmts.append(&mut original_block.stmts);
@@ -262,6 +373,7 @@ impl CodegenVisitor<'_> {
                     final_stmts.extend(parse_batch(quote! {
                         #(#bitmask_statements)*
                         #(#bitmask_clone_statements)*
+                        #params_restore
                     }));
 
                     final_stmts.append(&mut original_block.stmts);
@@ -345,6 +457,22 @@ impl CodegenVisitor<'_> {
         }
     }
 
+    /// Параметр экрана если он есть, анализатор уже проверил что он один и имеет вид
+    /// `name: Prop<T>` (FE031, FE032)
+    fn screen_param(&self, sig: &Signature) -> Option<(PatIdent, Type)> {
+        if !matches!(self.flags.compile_type, CompileType::Screen) || sig.inputs.len() != 1 {
+            return None;
+        }
+
+        match sig.inputs.first() {
+            Some(FnArg::Typed(pat_type)) => match &*pat_type.pat {
+                Pat::Ident(pat) => Some((pat.clone(), (*pat_type.ty).clone())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// проверяет возвращаемоет значение у функции
     fn check_function_has_return(&self, sig: &Signature) -> bool {
         match &sig.output {
@@ -26,10 +26,13 @@ impl CodegenVisitor<'_> {
         {
             for (field_name, field_type) in fields {
                 let field_name_ident = format_ident!("{}", field_name);
-                let type_ident = format_ident!("{}", field_type);
+                // Тип может быть путём с дженериками (например firework_ui::Animated<f32>),
+                // поэтому он парсится как тип, а не как идентификатор
+                let field_type: syn::Type =
+                    syn::parse_str(field_type).expect("IE: Failed to parse field type");
 
                 let new_field: syn::Field = parse_quote! {
-                    pub #field_name_ident: core::option::Option<#type_ident>
+                    pub #field_name_ident: core::option::Option<#field_type>
                 };
 
                 fields_named.named.push(new_field);
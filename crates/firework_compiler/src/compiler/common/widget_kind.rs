@@ -9,6 +9,7 @@ pub fn is_widget(name: &str) -> bool {
     name == "text"      ||
     name == "button"    ||
     name == "app_bar"   ||
+    name == "image"     ||
 
     // Функциональные виджеты, они не имеют набора рендер примитивов (скина) и
     // нужны для выполнения логики с синтаксисом DSL виджет
@@ -25,7 +26,12 @@ pub fn is_widget(name: &str) -> bool {
 ///    // Код
 /// };
 pub fn is_layout(name: &str) -> bool {
-    name == "vertical" || name == "horizontal" || name == "stack" || name == "absoulute"
+    name == "vertical"
+        || name == "horizontal"
+        || name == "stack"
+        || name == "absoulute"
+        || name == "lazy_list"
+        || name == "scroll"
 }
 
 /// Является ли это функциональным виджетом
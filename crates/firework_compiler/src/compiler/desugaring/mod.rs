@@ -1,8 +1,18 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::visit_mut::VisitMut;
-use syn::{Expr, ExprBlock, ExprClosure, File, parse_quote_spanned, spanned::Spanned};
+use syn::{
+    Block, Expr, ExprBlock, ExprClosure, File, Ident, Macro, Stmt, Token, parse_quote_spanned,
+    spanned::Spanned,
+};
+
+use super::codegen::consts::RESTART_FLAG_PREFIX;
+use super::common::widget_kind::is_layout;
+use super::error::{RESTART_SYNTAX_ERROR, TICK_SYNTAX_ERROR, compile_error_spanned};
 
 pub struct DesugarVisitor;
 
@@ -13,8 +23,6 @@ impl VisitMut for DesugarVisitor {
     /// обвязку (в данном случае, изменение битовой маски) для этого выражения, так как
     /// оно теперь в блоке (let mut cl = || a += 1;)
     fn visit_expr_closure_mut(&mut self, i: &mut ExprClosure) {
-        syn::visit_mut::visit_expr_closure_mut(self, i);
-
         if !matches!(*i.body, Expr::Block(_)) {
             let body_expr = &*i.body;
             let span = body_expr.span();
@@ -25,6 +33,108 @@ impl VisitMut for DesugarVisitor {
 
             *i.body = Expr::Block(new_block);
         }
+
+        // Обход после сворачивания чтобы маркер в коротком замыкании (|| restart!(a))
+        // развернулся как стейтемент блока
+        syn::visit_mut::visit_expr_closure_mut(self, i);
+    }
+
+    /// Маркер tick!(dt, { ... }) разворачивается в вызов firework_ui::on_tick с
+    /// замыканием, поэтому обновления спарков внутри него анализируются как обновления
+    /// в замыкании и взводят биты в битовой маске. _fwc_event объявляет flash pass
+    ///
+    /// Маркер restart!(spark) разворачивается во взвод флага перезапуска который
+    /// объявляет кодогенерация асинхронного спарка, флаг проверяет анализатор
+    fn visit_stmt_mut(&mut self, i: &mut Stmt) {
+        let mac = match i {
+            Stmt::Macro(stmt_macro) => Some(&stmt_macro.mac),
+            Stmt::Expr(Expr::Macro(expr_macro), _) => Some(&expr_macro.mac),
+            _ => None,
+        };
+
+        if let Some(mac) = mac {
+            if mac.path.is_ident("tick") {
+                *i = expand_tick_macro(mac);
+            } else if mac.path.is_ident("restart") {
+                *i = expand_restart_macro(mac);
+            }
+        }
+
+        syn::visit_mut::visit_stmt_mut(self, i);
+    }
+
+    /// Тело лайаута и аргументы эффекта это обычный раст код внутри токенов макроса,
+    /// syn их не обходит, поэтому маркеры внутри разворачиваются здесь
+    fn visit_macro_mut(&mut self, i: &mut Macro) {
+        let Some(name) = i.path.get_ident().map(|ident| ident.to_string()) else {
+            return;
+        };
+
+        if is_layout(&name) {
+            let tokens = &i.tokens;
+
+            if let Ok(mut block) = syn::parse2::<Block>(quote!({ #tokens })) {
+                self.visit_block_mut(&mut block);
+
+                let stmts = &block.stmts;
+                i.tokens = quote!(#(#stmts)*);
+            }
+        } else if name == "effect" {
+            let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+
+            if let Ok(mut arguments) = parser.parse2(i.tokens.clone()) {
+                for argument in arguments.iter_mut() {
+                    self.visit_expr_mut(argument);
+                }
+
+                i.tokens = quote!(#arguments);
+            }
+        }
+    }
+}
+
+fn expand_tick_macro(mac: &Macro) -> Stmt {
+    let span = mac.span();
+    let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+    let arguments: Vec<Expr> = parser
+        .parse2(mac.tokens.clone())
+        .map(|punctuated| punctuated.into_iter().collect())
+        .unwrap_or_default();
+
+    // FE034: Первый аргумент имя переменной для dt, второй блок
+    match arguments.as_slice() {
+        [Expr::Path(dt), Expr::Block(body)] if dt.path.get_ident().is_some() => {
+            let dt = dt.path.get_ident();
+
+            parse_quote_spanned!(span=>
+                ::firework_ui::on_tick(_fwc_event, |#dt: u32| #body);
+            )
+        }
+
+        _ => {
+            let error = compile_error_spanned(mac, TICK_SYNTAX_ERROR).to_compile_error();
+            parse_quote_spanned!(span=> #error;)
+        }
+    }
+}
+
+fn expand_restart_macro(mac: &Macro) -> Stmt {
+    let span = mac.span();
+
+    // FE036: Единственный аргумент имя спарка
+    match syn::parse2::<Ident>(mac.tokens.clone()) {
+        Ok(name) => {
+            let flag = format_ident!("{}{}", RESTART_FLAG_PREFIX, name, span = name.span());
+
+            parse_quote_spanned!(span=>
+                #flag.set(true);
+            )
+        }
+
+        Err(_) => {
+            let error = compile_error_spanned(mac, RESTART_SYNTAX_ERROR).to_compile_error();
+            parse_quote_spanned!(span=> #error;)
+        }
     }
 }
 
@@ -287,6 +287,65 @@ error[FE030]: closure must be marked as `move`
    = note: for more information, see: [WORK IN PROGRESS]
 ";
 
+/// Параметр экрана должен быть функциональным пропсом
+pub const SCREEN_INVALID_ARG_ERROR: &str = "\
+error[FE031]: invalid argument type `{}` in screen function
+   = note: screen parameters act as 'functional props' and must be `Prop<T>` (or `firework_ui::Prop<T>`)
+   = note: parameters are guaranteed to be `Some` only during `Build` or `Navigate`
+   = help: wrap the type in a Prop: `Prop<{}>` and open the screen with `navigate::push_with`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
+/// Экран может принимать только один параметр
+pub const SCREEN_MULTIPLE_ARGS_ERROR: &str = "\
+error[FE032]: screen function accepts at most one parameter
+   = note: `navigate::push_with` passes a single value to the screen
+   = help: group the values into a struct or a tuple: `fn screen(args: Prop<(u32, bool)>)`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
+/// Неизвестный аргумент #[ui(...)]
+pub const UI_INVALID_ATTRIBUTE_ERROR: &str = "\
+error[FE033]: invalid `#[ui]` argument
+   = note: `#[ui]` accepts a single state retention policy
+   = help: use one of `#[ui(keep_state)]` (default), `#[ui(reset_on_enter)]` or `#[ui(drop_on_leave)]`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
+/// Неверный синтаксис маркера tick!
+pub const TICK_SYNTAX_ERROR: &str = "\
+error[FE034]: invalid syntax for `tick!` marker
+   = note: expected `tick!(dt, { ... })` where `dt` is the name of the frame delta in milliseconds
+   = help: example: `tick!(dt, { angle += dt / 10; });`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
+/// Неверный синтаксис анимированного спарка
+pub const SPARK_ANIMATE_SYNTAX_ERROR: &str = "\
+error[FE035]: invalid syntax for animated spark
+   = note: expected `spark!(value, animate(duration, easing))` where duration is a literal in `ms` or `s`
+   = note: easing is one of `linear`, `ease_in`, `ease_out` (default) or `ease_in_out`
+   = help: example: `let mut width: f32 = spark!(100.0, animate(300ms, ease_out));`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
+/// Маркер restart! принимает только асинхронный спарк
+pub const RESTART_SYNTAX_ERROR: &str = "\
+error[FE036]: invalid syntax for `restart!` marker
+   = note: expected `restart!(spark)` where `spark` is an async spark declared with `spark!(value, async move |bridge| { ... })`
+   = help: example: `on_click: || restart!(user),`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
+/// Ёмкость списка цикла задаётся константой
+pub const LIST_CAPACITY_SYNTAX_ERROR: &str = "\
+error[FE037]: invalid syntax for `#[capacity]` attribute
+   = note: expected `#[capacity(N)]` where `N` is an integer literal or a `usize` constant
+   = note: capacity limits the loop list only in `no-alloc` mode, overflow is handled by `firework_ui::ListOverflow`
+   = help: example: `rect! { #[key_type(u32)] #[capacity(128)] key: index, }`
+   = note: for more information, see: [WORK IN PROGRESS]
+";
+
 pub fn compile_error_spanned<T: quote::ToTokens>(tokens: T, msg: &str) -> Error {
     Error::new_spanned(tokens, msg)
 }
@@ -1,6 +1,13 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+use proc_macro2::TokenStream;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Ident, Token};
+
+use super::error::{UI_INVALID_ATTRIBUTE_ERROR, compile_error_spanned};
+
 #[derive(Debug, Clone, Copy)]
 pub enum CompileType {
     Screen,
@@ -11,12 +18,50 @@ pub enum CompileType {
 #[derive(Debug, Clone, Copy)]
 pub struct CompileFlags {
     pub compile_type: CompileType,
+    pub retention: RetentionPolicy,
 }
 
 impl CompileFlags {
     pub fn new() -> Self {
         Self {
             compile_type: CompileType::Screen,
+            retention: RetentionPolicy::KeepState,
+        }
+    }
+}
+
+/// Что происходит с состоянием экрана (статикой ApplicationUiBlockStruct) при навигации,
+/// задаётся аргументом #[ui(...)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RetentionPolicy {
+    /// Спарки сохраняют значения между посещениями, виджеты создаются заново
+    #[default]
+    KeepState,
+
+    /// Каждый вход на экран это Build, состояние с прошлого посещения сбрасывается
+    ResetOnEnter,
+
+    /// При уходе с экрана все поля статики (спарки, скины, DynList) освобождаются,
+    /// следующий вход это Build
+    DropOnLeave,
+}
+
+impl RetentionPolicy {
+    /// Парсинг аргументов #[ui(...)], пустые аргументы это KeepState
+    pub fn parse(args: TokenStream) -> syn::Result<Self> {
+        let idents = Punctuated::<Ident, Token![,]>::parse_terminated.parse2(args.clone())?;
+
+        // FE033: Политика может быть только одна
+        if idents.len() > 1 {
+            return Err(compile_error_spanned(args, UI_INVALID_ATTRIBUTE_ERROR));
+        }
+
+        match idents.first() {
+            None => Ok(Self::KeepState),
+            Some(ident) if ident == "keep_state" => Ok(Self::KeepState),
+            Some(ident) if ident == "reset_on_enter" => Ok(Self::ResetOnEnter),
+            Some(ident) if ident == "drop_on_leave" => Ok(Self::DropOnLeave),
+            Some(ident) => Err(compile_error_spanned(ident, UI_INVALID_ATTRIBUTE_ERROR)),
         }
     }
 }
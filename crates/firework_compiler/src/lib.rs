@@ -3,7 +3,7 @@
 
 mod compiler;
 
-pub use compiler::flags::CompileType;
+pub use compiler::flags::{CompileType, RetentionPolicy};
 
 use compiler::flags::CompileFlags;
 use compiler::*;
@@ -18,12 +18,23 @@ struct FireworkAst {
 
 pub fn process_compile(
     input: TokenStream2,
+    args: TokenStream2,
     compile_type: CompileType,
     use_counter: bool,
 ) -> TokenStream2 {
     let ast = FireworkAst { tokens: input };
 
-    let flags = CompileFlags { compile_type };
+    // Ошибка в аргументах не останавливает компиляцию, экран собирается с политикой по
+    // умолчанию чтобы остальные ошибки тоже были видны
+    let (retention, args_error) = match RetentionPolicy::parse(args) {
+        Ok(retention) => (retention, None),
+        Err(error) => (RetentionPolicy::default(), Some(error.to_compile_error())),
+    };
+
+    let flags = CompileFlags {
+        compile_type,
+        retention,
+    };
 
     let id = if use_counter {
         BLOCK_COUNTER.fetch_add(1, Ordering::Relaxed)
@@ -34,6 +45,7 @@ pub fn process_compile(
     let (token_stream, error_tokens) = run_firework_compiler(ast, flags, id);
 
     let mut output: TokenStream2 = token_stream;
+    output.extend(args_error);
 
     // Если есть ошибки компиляции - добавляем их к выходному потоку
     // Каждая ошибка уже содержит правильный спан через compile_error! макрос
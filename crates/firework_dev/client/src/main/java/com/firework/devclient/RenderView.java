@@ -4,6 +4,8 @@
 package com.firework.devclient;
 
 import android.content.Context;
+import android.graphics.Bitmap;
+import android.graphics.BitmapFactory;
 import android.graphics.Canvas;
 import android.graphics.Color;
 import android.graphics.Paint;
@@ -15,7 +17,9 @@ import android.os.Looper;
 import android.text.Layout;
 import android.text.StaticLayout;
 import android.text.TextPaint;
+import android.util.Base64;
 import android.view.Choreographer;
+import android.view.KeyEvent;
 import android.view.MotionEvent;
 import android.view.View;
 import android.widget.Toast;
@@ -24,6 +28,9 @@ import org.json.JSONArray;
 import org.json.JSONObject;
 
 import java.io.BufferedReader;
+import java.io.File;
+import java.io.FileOutputStream;
+import java.io.InputStream;
 import java.io.InputStreamReader;
 import java.io.OutputStream;
 import java.net.ServerSocket;
@@ -45,9 +52,20 @@ public class RenderView extends View implements Choreographer.FrameCallback {
     private final ExecutorService networkExecutor = Executors.newSingleThreadExecutor();
     
     private boolean isRunLoopActive = false;
+    private int backgroundColor = Color.WHITE;
 
     private final ConcurrentHashMap<Integer, FWPrimitive> primitives = new ConcurrentHashMap<>();
 
+    // Уходящая сцена перехода между экранами (DetachAll), рисуется под текущей и не
+    // участвует в ResolveHit. Индекс 0 текущая сцена, 1 уходящая
+    private final ConcurrentHashMap<Integer, FWPrimitive> detached = new ConcurrentHashMap<>();
+    private final float[][] sceneOffsets = new float[2][2];
+    private final int[] sceneOpacity = {255, 255};
+
+    // Текстуры не удаляются при RemoveAll, только по FreeTexture
+    private final ConcurrentHashMap<Integer, Bitmap> textures = new ConcurrentHashMap<>();
+    private final ConcurrentHashMap<Integer, Typeface> fonts = new ConcurrentHashMap<>();
+
     private static class FWPrimitive {
         int id;
         boolean isText;
@@ -67,6 +85,12 @@ public class RenderView extends View implements Choreographer.FrameCallback {
         StringBuilder text = new StringBuilder();
         int textAlign = 0; 
         int wrapWidth = 0;
+        Typeface font = Typeface.DEFAULT;
+        int textStyle = Typeface.NORMAL;
+
+        boolean isImage = false;
+        int textureId = -1;
+        int imageFit = 0;
         
         FWPrimitive(int id, boolean isText) {
             this.id = id;
@@ -79,6 +103,9 @@ public class RenderView extends View implements Choreographer.FrameCallback {
     public RenderView(Context context) {
         super(context);
         setLayerType(LAYER_TYPE_SOFTWARE, null);
+        // Для получения событий аппаратной клавиатуры
+        setFocusable(true);
+        setFocusableInTouchMode(true);
         startServer();
     }
 
@@ -115,12 +142,47 @@ public class RenderView extends View implements Choreographer.FrameCallback {
 
             if (cmd.equals("RemoveAll")) {
                 primitives.clear();
+                detached.clear();
+                resetScene(0);
+                resetScene(1);
+                return;
+            }
+
+            if (cmd.equals("DetachAll")) {
+                detached.clear();
+                detached.putAll(primitives);
+                primitives.clear();
+                return;
+            }
+
+            if (cmd.equals("RemoveDetached")) {
+                detached.clear();
+                resetScene(1);
+                return;
+            }
+
+            if (cmd.equals("SetSceneOffset")) {
+                int scene = obj.getInt("scene") == 1 ? 1 : 0;
+                JSONArray offset = obj.getJSONArray("offset");
+                sceneOffsets[scene][0] = (float) offset.getDouble(0);
+                sceneOffsets[scene][1] = (float) offset.getDouble(1);
+                return;
+            }
+
+            if (cmd.equals("SetSceneOpacity")) {
+                int scene = obj.getInt("scene") == 1 ? 1 : 0;
+                sceneOpacity[scene] = obj.getInt("opacity");
                 return;
             }
         	
             if (cmd.equals("RunLoop")) {
+                JSONArray bg = obj.optJSONArray("bg");
+                if (bg != null) {
+                    backgroundColor = Color.argb(bg.getInt(3), bg.getInt(0), bg.getInt(1), bg.getInt(2));
+                }
                 if (!isRunLoopActive) {
                     isRunLoopActive = true;
+                    post(this::sendResize);
                     Choreographer.getInstance().postFrameCallback(this);
                 }
                 return;
@@ -136,6 +198,48 @@ public class RenderView extends View implements Choreographer.FrameCallback {
                 case "NewText":
                     primitives.put(id, new FWPrimitive(id, true));
                     break;
+                case "NewImage":
+                    FWPrimitive image = new FWPrimitive(id, false);
+                    image.isImage = true;
+                    primitives.put(id, image);
+                    break;
+                case "LoadImage":
+                    byte[] bytes = Base64.decode(obj.getString("bytes"), Base64.DEFAULT);
+                    Bitmap decoded = BitmapFactory.decodeByteArray(bytes, 0, bytes.length);
+                    if (decoded != null) textures.put(id, decoded);
+                    break;
+                case "LoadImageFromPath":
+                    // Путь ищется в assets клиента
+                    try (InputStream in = getContext().getAssets().open(obj.getString("path"))) {
+                        Bitmap asset = BitmapFactory.decodeStream(in);
+                        if (asset != null) textures.put(id, asset);
+                    }
+                    break;
+                case "LoadFont":
+                    // Typeface читается только из файла, поэтому байты сохраняются в кэш
+                    File fontFile = new File(getContext().getCacheDir(), "firework_font_" + id);
+                    try (FileOutputStream out = new FileOutputStream(fontFile)) {
+                        out.write(Base64.decode(obj.getString("bytes"), Base64.DEFAULT));
+                    }
+                    fonts.put(id, Typeface.createFromFile(fontFile));
+                    break;
+                case "SetFont":
+                    if (primitives.containsKey(id) && fonts.containsKey(obj.getInt("font"))) {
+                        FWPrimitive p = primitives.get(id);
+                        p.font = fonts.get(obj.getInt("font"));
+                        p.textPaint.setTypeface(Typeface.create(p.font, p.textStyle));
+                    }
+                    break;
+                case "SetImage":
+                    if (primitives.containsKey(id)) primitives.get(id).textureId = obj.getInt("texture");
+                    break;
+                case "SetImageFit":
+                    if (primitives.containsKey(id)) primitives.get(id).imageFit = obj.getInt("fit");
+                    break;
+                case "FreeTexture":
+                    Bitmap freed = textures.remove(id);
+                    if (freed != null) freed.recycle();
+                    break;
                 case "SetPosition":
                     JSONArray pos = obj.getJSONArray("pos");
                     if (primitives.containsKey(id)) {
@@ -199,10 +303,11 @@ public class RenderView extends View implements Choreographer.FrameCallback {
                         FWPrimitive p = primitives.get(id);
                         p.text.append(obj.getString("text"));
                         int mode = obj.getInt("mode");
-                        if (mode == 1) p.textPaint.setTypeface(Typeface.create(Typeface.DEFAULT, Typeface.BOLD));
-                        else if (mode == 2) p.textPaint.setTypeface(Typeface.create(Typeface.DEFAULT, Typeface.ITALIC));
-                        else if (mode == 3) p.textPaint.setTypeface(Typeface.create(Typeface.DEFAULT, Typeface.BOLD_ITALIC));
-                        else p.textPaint.setTypeface(Typeface.create(Typeface.DEFAULT, Typeface.NORMAL));
+                        if (mode == 1) p.textStyle = Typeface.BOLD;
+                        else if (mode == 2) p.textStyle = Typeface.ITALIC;
+                        else if (mode == 3) p.textStyle = Typeface.BOLD_ITALIC;
+                        else p.textStyle = Typeface.NORMAL;
+                        p.textPaint.setTypeface(Typeface.create(p.font, p.textStyle));
                     }
                     break;
                 case "ClearText":
@@ -291,6 +396,78 @@ public class RenderView extends View implements Choreographer.FrameCallback {
         return true;
     }
 
+    @Override
+    protected void onSizeChanged(int w, int h, int oldw, int oldh) {
+        super.onSizeChanged(w, h, oldw, oldh);
+        sendResize();
+    }
+
+    // Размер отправляется в пикселях экрана, так как клиент рисует без масштабирования
+    private void sendResize() {
+        if (!isRunLoopActive || getWidth() == 0) return;
+        sendToRust(String.format("{\"evt\":\"Resize\",\"w\":%d,\"h\":%d,\"scale\":%d}",
+                getWidth(), getHeight(), 100));
+    }
+
+    @Override
+    public boolean onKeyDown(int keyCode, KeyEvent event) {
+        return sendKey(keyCode, event, 0) || super.onKeyDown(keyCode, event);
+    }
+
+    @Override
+    public boolean onKeyUp(int keyCode, KeyEvent event) {
+        return sendKey(keyCode, event, 1) || super.onKeyUp(keyCode, event);
+    }
+
+    private boolean sendKey(int keyCode, KeyEvent event, int phase) {
+        int code = fireworkKeyCode(keyCode);
+        if (code < 0) return false;
+
+        int mods = (event.isShiftPressed() ? 1 : 0) | (event.isCtrlPressed() ? 2 : 0)
+                | (event.isAltPressed() ? 4 : 0) | (event.isMetaPressed() ? 8 : 0);
+
+        sendToRust(String.format("{\"evt\":\"Key\",\"code\":%d,\"phase\":%d,\"mods\":%d}",
+                code, phase, mods));
+
+        // Набранный символ отправляется отдельно с учётом раскладки и регистра
+        int ch = event.getUnicodeChar();
+        if (phase == 0 && ch > 0) {
+            sendToRust(String.format("{\"evt\":\"Char\",\"code\":%d}", ch));
+        }
+        return true;
+    }
+
+    // Коды клавиш Firework (см. firework_adapter::key_code)
+    private static int fireworkKeyCode(int keyCode) {
+        if (keyCode >= KeyEvent.KEYCODE_A && keyCode <= KeyEvent.KEYCODE_Z) {
+            return 'A' + (keyCode - KeyEvent.KEYCODE_A);
+        }
+        if (keyCode >= KeyEvent.KEYCODE_0 && keyCode <= KeyEvent.KEYCODE_9) {
+            return '0' + (keyCode - KeyEvent.KEYCODE_0);
+        }
+        if (keyCode >= KeyEvent.KEYCODE_F1 && keyCode <= KeyEvent.KEYCODE_F12) {
+            return 0x10100 + (keyCode - KeyEvent.KEYCODE_F1);
+        }
+        switch (keyCode) {
+            case KeyEvent.KEYCODE_DEL: return 8;
+            case KeyEvent.KEYCODE_TAB: return 9;
+            case KeyEvent.KEYCODE_ENTER: return 13;
+            case KeyEvent.KEYCODE_ESCAPE: return 27;
+            case KeyEvent.KEYCODE_SPACE: return 32;
+            case KeyEvent.KEYCODE_FORWARD_DEL: return 127;
+            case KeyEvent.KEYCODE_DPAD_LEFT: return 0x10000;
+            case KeyEvent.KEYCODE_DPAD_RIGHT: return 0x10001;
+            case KeyEvent.KEYCODE_DPAD_UP: return 0x10002;
+            case KeyEvent.KEYCODE_DPAD_DOWN: return 0x10003;
+            case KeyEvent.KEYCODE_MOVE_HOME: return 0x10004;
+            case KeyEvent.KEYCODE_MOVE_END: return 0x10005;
+            case KeyEvent.KEYCODE_PAGE_UP: return 0x10006;
+            case KeyEvent.KEYCODE_PAGE_DOWN: return 0x10007;
+            case KeyEvent.KEYCODE_INSERT: return 0x10008;
+            default: return -1;
+        }
+    }
+
     @Override
     public void doFrame(long frameTimeNanos) {
         if (isRunLoopActive) {
@@ -299,26 +476,47 @@ public class RenderView extends View implements Choreographer.FrameCallback {
         }
     }
 
+    private void resetScene(int scene) {
+        sceneOffsets[scene][0] = 0;
+        sceneOffsets[scene][1] = 0;
+        sceneOpacity[scene] = 255;
+    }
+
     @Override
     protected void onDraw(Canvas canvas) {
         super.onDraw(canvas);
-        canvas.drawColor(Color.WHITE);
+        canvas.drawColor(backgroundColor);
+
+        if (!detached.isEmpty()) drawScene(canvas, detached, 1);
+        drawScene(canvas, primitives, 0);
+    }
 
-        List<FWPrimitive> list = new ArrayList<>(primitives.values());
+    private void drawScene(Canvas canvas, ConcurrentHashMap<Integer, FWPrimitive> scene, int index) {
+        if (sceneOpacity[index] == 0) return;
+
+        int sceneSave = sceneOpacity[index] < 255
+                ? canvas.saveLayerAlpha(null, sceneOpacity[index])
+                : canvas.save();
+        canvas.translate(sceneOffsets[index][0], sceneOffsets[index][1]);
+
+        List<FWPrimitive> list = new ArrayList<>(scene.values());
         Collections.sort(list, (a, b) -> Integer.compare(a.zIndex, b.zIndex));
 
         for (FWPrimitive p : list) {
             if (!p.visible) continue;
 
             canvas.save();
-            if (p.clipToId != -1 && primitives.containsKey(p.clipToId)) {
-                FWPrimitive parent = primitives.get(p.clipToId);
+            if (p.clipToId != -1 && scene.containsKey(p.clipToId)) {
+                FWPrimitive parent = scene.get(p.clipToId);
                 Path clipPath = new Path();
                 clipPath.addRoundRect(parent.rect, parent.radii, Path.Direction.CW);
                 canvas.clipPath(clipPath);
             }
 
-            if (!p.isText) {
+            if (p.isImage) {
+                Bitmap bitmap = textures.get(p.textureId);
+                if (bitmap != null) drawImage(canvas, p, bitmap);
+            } else if (!p.isText) {
                 Path path = new Path();
                 path.addRoundRect(p.rect, p.radii, Path.Direction.CW);
                 canvas.drawPath(path, p.paint);
@@ -347,6 +545,25 @@ public class RenderView extends View implements Choreographer.FrameCallback {
             }
             canvas.restore();
         }
+
+        canvas.restoreToCount(sceneSave);
+    }
+
+    // Вписывание по AdapterCommand::SetImageFit: 0 contain, 1 cover, 2 fill
+    private void drawImage(Canvas canvas, FWPrimitive p, Bitmap bitmap) {
+        float bw = bitmap.getWidth(), bh = bitmap.getHeight();
+        float w = p.rect.width(), h = p.rect.height();
+        RectF dest = new RectF(p.rect);
+
+        if (p.imageFit != 2 && bw > 0 && bh > 0) {
+            float scale = p.imageFit == 1 ? Math.max(w / bw, h / bh) : Math.min(w / bw, h / bh);
+            float dw = bw * scale, dh = bh * scale;
+            dest.set(p.rect.centerX() - dw / 2, p.rect.centerY() - dh / 2,
+                    p.rect.centerX() + dw / 2, p.rect.centerY() + dh / 2);
+            canvas.clipRect(p.rect);
+        }
+
+        canvas.drawBitmap(bitmap, null, dest, p.paint);
     }
 
     private void showToast(final String message) {
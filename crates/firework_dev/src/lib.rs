@@ -10,7 +10,10 @@ use std::sync::{
 };
 use std::thread;
 
-use firework_adapter::{AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterResult};
+use firework_adapter::{
+    AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterKeyModifiers, AdapterKeyPhase,
+    AdapterResult,
+};
 
 static NEXT_PROXY_ID: AtomicUsize = AtomicUsize::new(1);
 static WRITE_STREAM: Mutex<Option<TcpStream>> = Mutex::new(None);
@@ -56,30 +59,63 @@ fn spawn_reader_thread(stream: TcpStream, listener: Option<fn(AdapterEvent)>) {
         let reader = BufReader::new(stream);
         let tx = SYNC_RESPONSE_TX.lock().unwrap().as_ref().unwrap().clone();
 
-        for line in reader.lines() {
-            if let Ok(msg) = line {
-                if msg.contains("\"res\":") {
-                    // Это синхронный ответ
-                    let _ = tx.send(msg);
-                } else if let Some(cb) = listener {
-                    // Аинхронное событие
-                    if msg.contains("\"evt\":\"Tick\"") {
-                        cb(AdapterEvent::Tick);
-                    } else if msg.contains("\"evt\":\"Touch\"") {
-                        // Примитивный парсинг чтобы не тянуть зависимости, Dev ориентирован только
-                        // на скорость компиляции
-                        let x = extract_int(&msg, "\"x\":").unwrap_or(0);
-                        let y = extract_int(&msg, "\"y\":").unwrap_or(0);
-                        let p = extract_int(&msg, "\"phase\":").unwrap_or(3);
+        for msg in reader.lines().map_while(Result::ok) {
+            if msg.contains("\"res\":") {
+                // Это синхронный ответ
+                let _ = tx.send(msg);
+            } else if let Some(cb) = listener {
+                // Аинхронное событие
+                if msg.contains("\"evt\":\"Tick\"") {
+                    cb(AdapterEvent::Tick);
+                } else if msg.contains("\"evt\":\"Touch\"") {
+                    // Примитивный парсинг чтобы не тянуть зависимости, Dev ориентирован только
+                    // на скорость компиляции
+                    let x = extract_int(&msg, "\"x\":").unwrap_or(0);
+                    let y = extract_int(&msg, "\"y\":").unwrap_or(0);
+                    let p = extract_int(&msg, "\"phase\":").unwrap_or(3);
+
+                    let phase = match p {
+                        0 => AdapterClickPhase::Began,
+                        1 => AdapterClickPhase::Moved,
+                        2 => AdapterClickPhase::Ended,
+                        _ => AdapterClickPhase::Cancelled,
+                    };
+
+                    cb(AdapterEvent::Touch(x as u32, y as u32, phase));
+                } else if msg.contains("\"evt\":\"Key\"") {
+                    // Модификаторы битами: 1 shift, 2 ctrl, 4 alt, 8 meta
+                    let code = extract_int(&msg, "\"code\":").unwrap_or(0);
+                    let p = extract_int(&msg, "\"phase\":").unwrap_or(0);
+                    let mods = extract_int(&msg, "\"mods\":").unwrap_or(0);
+
+                    let phase = match p {
+                        0 => AdapterKeyPhase::Down,
+                        _ => AdapterKeyPhase::Up,
+                    };
 
-                        let phase = match p {
-                            0 => AdapterClickPhase::Began,
-                            1 => AdapterClickPhase::Moved,
-                            2 => AdapterClickPhase::Ended,
-                            _ => AdapterClickPhase::Cancelled,
-                        };
+                    let modifiers = AdapterKeyModifiers {
+                        shift: mods & 1 != 0,
+                        ctrl: mods & 2 != 0,
+                        alt: mods & 4 != 0,
+                        meta: mods & 8 != 0,
+                    };
 
-                        cb(AdapterEvent::Touch(x as u32, y as u32, phase));
+                    cb(AdapterEvent::Key(code as u32, phase, modifiers));
+                } else if msg.contains("\"evt\":\"Resize\"") {
+                    let width = extract_int(&msg, "\"w\":").unwrap_or(0);
+                    let height = extract_int(&msg, "\"h\":").unwrap_or(0);
+                    let scale = extract_int(&msg, "\"scale\":").unwrap_or(100);
+
+                    cb(AdapterEvent::Resize {
+                        width: width as u32,
+                        height: height as u32,
+                        scale: scale as u16,
+                    });
+                } else if msg.contains("\"evt\":\"Char\"") {
+                    let code = extract_int(&msg, "\"code\":").unwrap_or(0);
+
+                    if let Some(ch) = char::from_u32(code as u32) {
+                        cb(AdapterEvent::Char(ch));
                     }
                 }
             }
@@ -116,16 +152,54 @@ fn escape_json_string(s: &str) -> String {
         .replace('\n', "\\n")
 }
 
+/// Байты изображений и шрифтов передаются клиенту в base64 внутри JSON строки
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+
+    out
+}
+
 pub fn network_adapter<'a>(cmd: AdapterCommand<'a>) -> AdapterResult {
     match cmd {
         AdapterCommand::RemoveAll => {
             send_json(r#"{"cmd":"RemoveAll"}"#.to_string());
             AdapterResult::Void
         }
-        AdapterCommand::RunLoop { listener, .. } => {
+        AdapterCommand::RunLoop {
+            background: (r, g, b, a),
+            listener,
+            ..
+        } => {
             if let Some(stream) = get_write_stream() {
                 spawn_reader_thread(stream.try_clone().unwrap(), Some(listener));
-                send_json(r#"{"cmd":"RunLoop"}"#.to_string());
+                send_json(format!(
+                    r#"{{"cmd":"RunLoop","bg":[{},{},{},{}]}}"#,
+                    r, g, b, a
+                ));
             }
             AdapterResult::Void
         }
@@ -267,5 +341,84 @@ pub fn network_adapter<'a>(cmd: AdapterCommand<'a>) -> AdapterResult {
             ));
             AdapterResult::Void
         }
+        AdapterCommand::LoadImage { bytes } => {
+            let id = NEXT_PROXY_ID.fetch_add(1, Ordering::SeqCst);
+            send_json(format!(
+                r#"{{"cmd":"LoadImage","id":{},"bytes":"{}"}}"#,
+                id,
+                encode_base64(bytes)
+            ));
+            AdapterResult::Handle(id)
+        }
+        AdapterCommand::LoadImageFromPath { path } => {
+            let id = NEXT_PROXY_ID.fetch_add(1, Ordering::SeqCst);
+            send_json(format!(
+                r#"{{"cmd":"LoadImageFromPath","id":{},"path":"{}"}}"#,
+                id,
+                escape_json_string(path)
+            ));
+            AdapterResult::Handle(id)
+        }
+        AdapterCommand::NewImage { .. } => {
+            let id = NEXT_PROXY_ID.fetch_add(1, Ordering::SeqCst);
+            send_json(format!(r#"{{"cmd":"NewImage","id":{}}}"#, id));
+            AdapterResult::Handle(id)
+        }
+        AdapterCommand::SetImage(id, texture) => {
+            send_json(format!(
+                r#"{{"cmd":"SetImage","id":{},"texture":{}}}"#,
+                id, texture
+            ));
+            AdapterResult::Void
+        }
+        AdapterCommand::SetImageFit(id, fit) => {
+            send_json(format!(
+                r#"{{"cmd":"SetImageFit","id":{},"fit":{}}}"#,
+                id, fit
+            ));
+            AdapterResult::Void
+        }
+        AdapterCommand::FreeTexture(id) => {
+            send_json(format!(r#"{{"cmd":"FreeTexture","id":{}}}"#, id));
+            AdapterResult::Void
+        }
+        AdapterCommand::LoadFont { bytes } => {
+            let id = NEXT_PROXY_ID.fetch_add(1, Ordering::SeqCst);
+            send_json(format!(
+                r#"{{"cmd":"LoadFont","id":{},"bytes":"{}"}}"#,
+                id,
+                encode_base64(bytes)
+            ));
+            AdapterResult::Handle(id)
+        }
+        AdapterCommand::SetFont(id, font) => {
+            send_json(format!(
+                r#"{{"cmd":"SetFont","id":{},"font":{}}}"#,
+                id, font
+            ));
+            AdapterResult::Void
+        }
+        AdapterCommand::DetachAll => {
+            send_json(r#"{"cmd":"DetachAll"}"#.to_string());
+            AdapterResult::Void
+        }
+        AdapterCommand::SetSceneOffset(scene, (x, y)) => {
+            send_json(format!(
+                r#"{{"cmd":"SetSceneOffset","scene":{},"offset":[{},{}]}}"#,
+                scene, x, y
+            ));
+            AdapterResult::Void
+        }
+        AdapterCommand::SetSceneOpacity(scene, opacity) => {
+            send_json(format!(
+                r#"{{"cmd":"SetSceneOpacity","scene":{},"opacity":{}}}"#,
+                scene, opacity
+            ));
+            AdapterResult::Void
+        }
+        AdapterCommand::RemoveDetached => {
+            send_json(r#"{"cmd":"RemoveDetached"}"#.to_string());
+            AdapterResult::Void
+        }
     }
 }
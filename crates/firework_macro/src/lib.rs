@@ -10,27 +10,32 @@ use syn::{Fields, Ident, ItemEnum, Type, parse_macro_input};
 
 #[proc_macro]
 pub fn shared(input: TokenStream) -> TokenStream {
-    process_macro(input, CompileType::Shared, false)
+    process_macro(input, TokenStream::new(), CompileType::Shared, false)
 }
 
 #[proc_macro]
 pub fn component(input: TokenStream) -> TokenStream {
-    process_macro(input, CompileType::Component, false)
+    process_macro(input, TokenStream::new(), CompileType::Component, false)
 }
 
 #[proc_macro]
 pub fn ui_block(input: TokenStream) -> TokenStream {
-    process_macro(input, CompileType::Screen, true)
+    process_macro(input, TokenStream::new(), CompileType::Screen, true)
 }
 
 #[proc_macro_attribute]
-pub fn ui(_args: proc_macro::TokenStream, input: TokenStream) -> TokenStream {
-    process_macro(input, CompileType::Screen, true)
+pub fn ui(args: proc_macro::TokenStream, input: TokenStream) -> TokenStream {
+    process_macro(input, args, CompileType::Screen, true)
 }
 
-fn process_macro(input: TokenStream, compile_type: CompileType, use_counter: bool) -> TokenStream {
+fn process_macro(
+    input: TokenStream,
+    args: TokenStream,
+    compile_type: CompileType,
+    use_counter: bool,
+) -> TokenStream {
     let input = parse_macro_input!(input);
-    process_compile(input, compile_type, use_counter).into()
+    process_compile(input, args.into(), compile_type, use_counter).into()
 }
 
 #[proc_macro_attribute]
@@ -155,9 +160,23 @@ fn is_string_ref(ty: &Type) -> bool {
     false
 }
 
+fn is_bytes_ref(ty: &Type) -> bool {
+    if let Type::Reference(type_ref) = ty
+        && let Type::Slice(type_slice) = &*type_ref.elem
+        && let Type::Path(type_path) = &*type_slice.elem
+        && type_path.path.is_ident("u8")
+    {
+        return true;
+    }
+
+    false
+}
+
 fn map_type(ty: &Type) -> proc_macro2::TokenStream {
     if is_string_ref(ty) {
         quote! { String }
+    } else if is_bytes_ref(ty) {
+        quote! { Vec<u8> }
     } else {
         quote! { #ty }
     }
@@ -166,6 +185,8 @@ fn map_type(ty: &Type) -> proc_macro2::TokenStream {
 fn map_expr(name: &Ident, ty: &Type) -> proc_macro2::TokenStream {
     if is_string_ref(ty) {
         quote! { #name.to_string() }
+    } else if is_bytes_ref(ty) {
+        quote! { #name.to_vec() }
     } else {
         quote! { #name }
     }
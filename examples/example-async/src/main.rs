@@ -3,9 +3,9 @@ use firework_ui::ui;
 #[ui]
 fn compose() {
     let mut state: i32 = spark!(0, async move |mut bridge| {
-        bridge.sleep_s(2);
+        bridge.sleep_s(2).await;
         *bridge = 1;
-        bridge.sleep_s(2);
+        bridge.sleep_s(2).await;
     });
 
     println!("Hello world");
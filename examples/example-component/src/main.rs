@@ -6,6 +6,12 @@ component! {
         pub hello: i32,
     }
 
+    impl Default for Button {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     impl Button {
         pub fn new() -> Self {
             Self {
@@ -1,7 +1,10 @@
 // [AI GEN ADAPTER EXAMPLE FOR TEST]
 
 use eframe::egui;
-use firework_ui::{AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterResult};
+use firework_ui::{
+    AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterKeyModifiers, AdapterKeyPhase,
+    AdapterResult, key_code,
+};
 use std::sync::Mutex;
 
 const MAX_OBJECTS: usize = 4096;
@@ -9,6 +12,10 @@ const MAX_OBJECTS: usize = 4096;
 #[derive(Clone)]
 struct RenderObject {
     alive: bool,
+
+    // Объект уходящей сцены перехода (DetachAll), рисуется со смещением и прозрачностью
+    // сцены 1 и не участвует в ResolveHit
+    detached: bool,
     pos: (i32, i32),
     size: (i32, i32),
     color: (u8, u8, u8, u8),
@@ -24,12 +31,17 @@ struct RenderObject {
     border_color: (u8, u8, u8, u8),
     font_size: u16,
     clip_to: Option<usize>,
+    is_image: bool,
+    texture: Option<usize>,
+    image_fit: u8,
+    font: Option<usize>,
 }
 
 impl Default for RenderObject {
     fn default() -> Self {
         Self {
             alive: false,
+            detached: false,
             pos: (0, 0),
             size: (0, 0),
             color: (255, 255, 255, 255),
@@ -45,6 +57,10 @@ impl Default for RenderObject {
             border_color: (0, 0, 0, 0),
             font_size: 14,
             clip_to: None,
+            is_image: false,
+            texture: None,
+            image_fit: 0,
+            font: None,
         }
     }
 }
@@ -53,6 +69,7 @@ impl RenderObject {
     const fn empty() -> Self {
         Self {
             alive: false,
+            detached: false,
             pos: (0, 0),
             size: (0, 0),
             color: (0, 0, 0, 0),
@@ -68,31 +85,73 @@ impl RenderObject {
             border_color: (0, 0, 0, 0),
             font_size: 14,
             clip_to: None,
+            is_image: false,
+            texture: None,
+            image_fit: 0,
+            font: None,
         }
     }
 }
 
+/// Текстура декодируется сразу при LoadImage, но загрузить её в egui можно только когда
+/// есть контекст, а первый проход экрана идёт до RunLoop. Поэтому загрузка откладывается
+/// до первого кадра
+enum TextureSlot {
+    Free,
+    Pending(egui::ColorImage),
+    Loaded(egui::TextureHandle),
+}
+
 struct AdapterState {
     objects: [RenderObject; MAX_OBJECTS],
+    textures: Vec<TextureSlot>,
+
+    // Шрифты из LoadFont. set_fonts применяется только со следующего кадра, поэтому
+    // отдельно хранится сколько шрифтов отдано egui и сколько из них уже активно
+    fonts: Vec<Vec<u8>>,
+    fonts_installed: usize,
+    fonts_active: usize,
     listener: Option<fn(AdapterEvent)>,
     dirty: bool,
     ctx: Option<egui::Context>,
+    fps: u16,
+    background: (u8, u8, u8, u8),
+    viewport: (u32, u32, u16),
+
+    // Смещение и прозрачность сцен, 0 текущая, 1 уходящая
+    scene_offset: [(i32, i32); 2],
+    scene_opacity: [u8; 2],
 }
 
 static ADAPTER_STATE: Mutex<AdapterState> = Mutex::new(AdapterState {
     objects: [const { RenderObject::empty() }; MAX_OBJECTS],
+    textures: Vec::new(),
+    fonts: Vec::new(),
+    fonts_installed: 0,
+    fonts_active: 0,
     listener: None,
     dirty: false,
     ctx: None,
+    fps: 0,
+    background: (255, 255, 255, 255),
+    viewport: (0, 0, 0),
+    scene_offset: [(0, 0); 2],
+    scene_opacity: [255; 2],
 });
 
-fn create_layout_job(obj: &RenderObject) -> egui::text::LayoutJob {
-    let mut job = egui::text::LayoutJob::default();
+fn font_family(index: usize) -> egui::FontFamily {
+    egui::FontFamily::Name(format!("firework_font_{}", index).into())
+}
 
-    job.halign = match obj.text_align {
-        1 => egui::Align::Center,
-        2 => egui::Align::Max,
-        _ => egui::Align::Min,
+/// Шрифт который ещё не стал активным в egui рисуется шрифтом по умолчанию
+fn create_layout_job(obj: &RenderObject, fonts_active: usize) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob {
+        halign: match obj.text_align {
+            1 => egui::Align::Center,
+            2 => egui::Align::Max,
+            _ => egui::Align::Min,
+        },
+        ..Default::default()
     };
 
     if obj.text_wrap_width > 0 {
@@ -109,8 +168,13 @@ fn create_layout_job(obj: &RenderObject) -> egui::text::LayoutJob {
     let font_size = obj.font_size as f32;
 
     for (text, mode) in &obj.text_segments {
+        let family = match obj.font {
+            Some(font) if font < fonts_active => font_family(font),
+            _ => egui::FontFamily::Proportional,
+        };
+
         let mut format = egui::text::TextFormat {
-            font_id: egui::FontId::proportional(font_size),
+            font_id: egui::FontId::new(font_size, family),
             color,
             ..Default::default()
         };
@@ -137,6 +201,66 @@ fn create_layout_job(obj: &RenderObject) -> egui::text::LayoutJob {
     job
 }
 
+fn decode_image(bytes: &[u8]) -> Option<egui::ColorImage> {
+    let image = image::load_from_memory(bytes).ok()?.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_flat_samples().as_slice(),
+    ))
+}
+
+fn store_texture(state: &mut AdapterState, image: egui::ColorImage) -> usize {
+    let slot = TextureSlot::Pending(image);
+
+    if let Some(index) = state
+        .textures
+        .iter()
+        .position(|slot| matches!(slot, TextureSlot::Free))
+    {
+        state.textures[index] = slot;
+        index
+    } else {
+        state.textures.push(slot);
+        state.textures.len() - 1
+    }
+}
+
+/// Прямоугольник куда рисуется текстура и UV координаты для режима вписывания
+fn fit_image(rect: egui::Rect, texture: egui::Vec2, fit: u8) -> (egui::Rect, egui::Rect) {
+    let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+
+    if texture.x <= 0.0 || texture.y <= 0.0 {
+        return (rect, full_uv);
+    }
+
+    match fit {
+        // cover: размер по большей стороне, лишнее обрезается через UV
+        1 => {
+            let scale = (rect.width() / texture.x).max(rect.height() / texture.y);
+            let visible = egui::vec2(
+                rect.width() / (texture.x * scale),
+                rect.height() / (texture.y * scale),
+            );
+            let uv = egui::Rect::from_center_size(egui::pos2(0.5, 0.5), visible);
+
+            (rect, uv)
+        }
+
+        // fill
+        2 => (rect, full_uv),
+
+        // contain
+        _ => {
+            let scale = (rect.width() / texture.x).min(rect.height() / texture.y);
+            let dest = egui::Rect::from_center_size(rect.center(), texture * scale);
+
+            (dest, full_uv)
+        }
+    }
+}
+
 pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
     let mut state = ADAPTER_STATE.lock().unwrap();
 
@@ -145,14 +269,32 @@ pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
             title,
             width,
             height,
+            min_size,
+            max_size,
+            resizable,
+            fps,
+            background,
             listener,
         } => {
             state.listener = Some(listener);
+            state.fps = fps;
+            state.background = background;
             drop(state);
 
+            let mut viewport = egui::ViewportBuilder::default()
+                .with_inner_size([width as f32, height as f32])
+                .with_resizable(resizable);
+
+            if let Some((min_width, min_height)) = min_size {
+                viewport = viewport.with_min_inner_size([min_width as f32, min_height as f32]);
+            }
+
+            if let Some((max_width, max_height)) = max_size {
+                viewport = viewport.with_max_inner_size([max_width as f32, max_height as f32]);
+            }
+
             let options = eframe::NativeOptions {
-                viewport: egui::ViewportBuilder::default()
-                    .with_inner_size([width as f32, height as f32]),
+                viewport,
                 ..Default::default()
             };
 
@@ -168,10 +310,61 @@ pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
         AdapterCommand::RemoveAll => {
             for obj in state.objects.iter_mut() {
                 obj.alive = false;
+                obj.detached = false;
                 obj.text_segments.clear();
                 obj.clip_to = None;
             }
 
+            state.scene_offset = [(0, 0); 2];
+            state.scene_opacity = [255; 2];
+
+            AdapterResult::Void
+        }
+
+        AdapterCommand::DetachAll => {
+            for obj in state.objects.iter_mut().filter(|obj| obj.alive) {
+                if obj.detached {
+                    obj.alive = false;
+                    obj.detached = false;
+                    obj.text_segments.clear();
+                    obj.clip_to = None;
+                } else {
+                    obj.detached = true;
+                }
+            }
+
+            state.dirty = true;
+            AdapterResult::Void
+        }
+
+        AdapterCommand::SetSceneOffset(scene, offset) => {
+            if let Some(slot) = state.scene_offset.get_mut(scene as usize) {
+                *slot = offset;
+            }
+
+            AdapterResult::Void
+        }
+
+        AdapterCommand::SetSceneOpacity(scene, opacity) => {
+            if let Some(slot) = state.scene_opacity.get_mut(scene as usize) {
+                *slot = opacity;
+            }
+
+            AdapterResult::Void
+        }
+
+        AdapterCommand::RemoveDetached => {
+            for obj in state.objects.iter_mut().filter(|obj| obj.detached) {
+                obj.alive = false;
+                obj.detached = false;
+                obj.text_segments.clear();
+                obj.clip_to = None;
+            }
+
+            state.scene_offset[1] = (0, 0);
+            state.scene_opacity[1] = 255;
+            state.dirty = true;
+
             AdapterResult::Void
         }
 
@@ -231,15 +424,16 @@ pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
                 state.ctx = Some(egui::Context::default());
             }
 
-            if let Some(obj) = state.objects.get(id) {
-                if obj.alive && obj.is_text {
-                    let job = create_layout_job(obj);
-                    let galley = state.ctx.as_ref().unwrap().fonts(|f| f.layout_job(job));
-                    return AdapterResult::Size(
-                        galley.rect.width().ceil() as u32,
-                        galley.rect.height().ceil() as u32,
-                    );
-                }
+            if let Some(obj) = state.objects.get(id)
+                && obj.alive
+                && obj.is_text
+            {
+                let job = create_layout_job(obj, state.fonts_active);
+                let galley = state.ctx.as_ref().unwrap().fonts(|f| f.layout_job(job));
+                return AdapterResult::Size(
+                    galley.rect.width().ceil() as u32,
+                    galley.rect.height().ceil() as u32,
+                );
             }
             AdapterResult::Size(0, 0)
         }
@@ -342,6 +536,75 @@ pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
 
         AdapterCommand::SetShadow(..) => AdapterResult::Void,
 
+        AdapterCommand::LoadImage { bytes } => match decode_image(bytes) {
+            Some(image) => AdapterResult::Handle(store_texture(&mut state, image)),
+            None => AdapterResult::Fail,
+        },
+
+        AdapterCommand::LoadImageFromPath { path } => {
+            match std::fs::read(path)
+                .ok()
+                .and_then(|bytes| decode_image(&bytes))
+            {
+                Some(image) => AdapterResult::Handle(store_texture(&mut state, image)),
+                None => AdapterResult::Fail,
+            }
+        }
+
+        AdapterCommand::LoadFont { bytes } => {
+            state.fonts.push(bytes.to_vec());
+            AdapterResult::Handle(state.fonts.len() - 1)
+        }
+
+        AdapterCommand::SetFont(id, font) => {
+            if let Some(obj) = state.objects.get_mut(id) {
+                obj.font = Some(font);
+                state.dirty = true;
+            }
+
+            AdapterResult::Void
+        }
+
+        AdapterCommand::NewImage { layout: _ } => {
+            if let Some(index) = state.objects.iter().position(|obj| !obj.alive) {
+                state.objects[index] = RenderObject {
+                    alive: true,
+                    visible: true,
+                    is_image: true,
+                    ..Default::default()
+                };
+                state.dirty = true;
+
+                AdapterResult::Handle(index)
+            } else {
+                AdapterResult::Fail
+            }
+        }
+
+        AdapterCommand::SetImage(id, texture) => {
+            if let Some(obj) = state.objects.get_mut(id) {
+                obj.texture = Some(texture);
+            }
+
+            AdapterResult::Void
+        }
+
+        AdapterCommand::SetImageFit(id, fit) => {
+            if let Some(obj) = state.objects.get_mut(id) {
+                obj.image_fit = fit;
+            }
+
+            AdapterResult::Void
+        }
+
+        AdapterCommand::FreeTexture(texture) => {
+            if let Some(slot) = state.textures.get_mut(texture) {
+                *slot = TextureSlot::Free;
+            }
+
+            AdapterResult::Void
+        }
+
         AdapterCommand::Remove(id) => {
             if let Some(obj) = state.objects.get_mut(id) {
                 obj.alive = false;
@@ -371,7 +634,7 @@ pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
             let a_bottom = ay + ah;
 
             for (id, obj) in state.objects.iter().enumerate() {
-                if obj.alive && obj.visible && obj.hit_group == group {
+                if obj.alive && !obj.detached && obj.visible && obj.hit_group == group {
                     let b_left = obj.pos.0;
                     let b_right = obj.pos.0 + obj.size.0;
                     let b_top = obj.pos.1;
@@ -401,6 +664,43 @@ pub fn egui_adapter(cmd: AdapterCommand<'_>) -> AdapterResult {
 
 struct FireworkEguiApp;
 
+/// Сопоставление клавиш egui с кодами клавиш Firework
+fn egui_key_code(key: egui::Key) -> Option<u32> {
+    use egui::Key;
+
+    let code = match key {
+        Key::Backspace => key_code::BACKSPACE,
+        Key::Tab => key_code::TAB,
+        Key::Enter => key_code::ENTER,
+        Key::Escape => key_code::ESCAPE,
+        Key::Space => key_code::SPACE,
+        Key::Delete => key_code::DELETE,
+        Key::ArrowLeft => key_code::LEFT,
+        Key::ArrowRight => key_code::RIGHT,
+        Key::ArrowUp => key_code::UP,
+        Key::ArrowDown => key_code::DOWN,
+        Key::Home => key_code::HOME,
+        Key::End => key_code::END,
+        Key::PageUp => key_code::PAGE_UP,
+        Key::PageDown => key_code::PAGE_DOWN,
+        Key::Insert => key_code::INSERT,
+        _ => {
+            // Буквы, цифры и F клавиши egui называет одним символом или F1..F35
+            let name = key.name();
+
+            if name.len() == 1 && name.as_bytes()[0].is_ascii_alphanumeric() {
+                name.as_bytes()[0].to_ascii_uppercase() as u32
+            } else if let Some(n) = name.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+                key_code::F1 + n - 1
+            } else {
+                return None;
+            }
+        }
+    };
+
+    Some(code)
+}
+
 impl eframe::App for FireworkEguiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let listener = {
@@ -410,6 +710,28 @@ impl eframe::App for FireworkEguiApp {
         };
 
         if let Some(listener) = listener {
+            let size = ctx.screen_rect().size();
+            let viewport = (
+                size.x as u32,
+                size.y as u32,
+                (ctx.pixels_per_point() * 100.0).round() as u16,
+            );
+
+            let changed = {
+                let mut state = ADAPTER_STATE.lock().unwrap();
+                let changed = state.viewport != viewport;
+                state.viewport = viewport;
+                changed
+            };
+
+            if changed {
+                listener(AdapterEvent::Resize {
+                    width: viewport.0,
+                    height: viewport.1,
+                    scale: viewport.2,
+                });
+            }
+
             listener(AdapterEvent::Tick);
 
             if ctx.input(|i| i.viewport().close_requested()) {
@@ -421,12 +743,14 @@ impl eframe::App for FireworkEguiApp {
                     let x = pos.x.max(0.0) as u32;
                     let y = pos.y.max(0.0) as u32;
 
+                    // Движение без нажатия тоже отправляется, по нему скины
+                    // показывают наведение
                     if i.pointer.any_pressed() {
                         listener(AdapterEvent::Touch(x, y, AdapterClickPhase::Began));
-                    } else if i.pointer.any_down() && i.pointer.delta() != egui::Vec2::ZERO {
-                        listener(AdapterEvent::Touch(x, y, AdapterClickPhase::Moved));
                     } else if i.pointer.any_released() {
                         listener(AdapterEvent::Touch(x, y, AdapterClickPhase::Ended));
+                    } else if i.pointer.delta() != egui::Vec2::ZERO {
+                        listener(AdapterEvent::Touch(x, y, AdapterClickPhase::Moved));
                     }
                 }
 
@@ -434,22 +758,31 @@ impl eframe::App for FireworkEguiApp {
                     match event {
                         egui::Event::Text(text) => {
                             for c in text.chars() {
-                                listener(AdapterEvent::Key(c as u32));
+                                listener(AdapterEvent::Char(c));
                             }
                         }
                         egui::Event::Key {
-                            key: egui::Key::Backspace,
-                            pressed: true,
+                            key,
+                            pressed,
+                            modifiers,
                             ..
                         } => {
-                            listener(AdapterEvent::Key(8));
-                        }
-                        egui::Event::Key {
-                            key: egui::Key::Enter,
-                            pressed: true,
-                            ..
-                        } => {
-                            listener(AdapterEvent::Key(13));
+                            if let Some(code) = egui_key_code(*key) {
+                                let phase = if *pressed {
+                                    AdapterKeyPhase::Down
+                                } else {
+                                    AdapterKeyPhase::Up
+                                };
+
+                                let modifiers = AdapterKeyModifiers {
+                                    shift: modifiers.shift,
+                                    ctrl: modifiers.ctrl,
+                                    alt: modifiers.alt,
+                                    meta: modifiers.mac_cmd,
+                                };
+
+                                listener(AdapterEvent::Key(code, phase, modifiers));
+                            }
                         }
                         _ => {}
                     }
@@ -459,37 +792,105 @@ impl eframe::App for FireworkEguiApp {
 
         let mut objects_to_draw = Vec::new();
         let mut clip_rects = std::collections::HashMap::new();
+        let mut textures = std::collections::HashMap::new();
+        let (fps, background, fonts_active, scene_offset, scene_opacity) = {
+            let mut state = ADAPTER_STATE.lock().unwrap();
+            state.fonts_active = state.fonts_installed;
+
+            if state.fonts.len() > state.fonts_installed {
+                let mut definitions = egui::FontDefinitions::default();
+
+                for (i, bytes) in state.fonts.iter().enumerate() {
+                    let name = format!("firework_font_{}", i);
+                    definitions
+                        .font_data
+                        .insert(name.clone(), egui::FontData::from_owned(bytes.clone()));
+                    definitions.families.insert(font_family(i), vec![name]);
+                }
+
+                ctx.set_fonts(definitions);
+                state.fonts_installed = state.fonts.len();
+            }
+
+            (
+                state.fps,
+                state.background,
+                state.fonts_active,
+                state.scene_offset,
+                state.scene_opacity,
+            )
+        };
         {
             let mut state = ADAPTER_STATE.lock().unwrap();
 
+            for (i, slot) in state.textures.iter_mut().enumerate() {
+                if let TextureSlot::Pending(image) = slot {
+                    let image = std::mem::take(image);
+                    *slot = TextureSlot::Loaded(ctx.load_texture(
+                        format!("firework_texture_{}", i),
+                        image,
+                        Default::default(),
+                    ));
+                }
+
+                if let TextureSlot::Loaded(handle) = slot {
+                    textures.insert(i, (handle.id(), handle.size_vec2()));
+                }
+            }
+
             for (i, obj) in state.objects.iter().enumerate() {
                 if obj.alive {
                     if obj.visible {
                         objects_to_draw.push(obj.clone());
                     }
 
+                    let offset = scene_offset[obj.detached as usize];
                     clip_rects.insert(
                         i,
                         egui::Rect::from_min_size(
-                            egui::pos2(obj.pos.0 as f32, obj.pos.1 as f32),
+                            egui::pos2(
+                                (obj.pos.0 + offset.0) as f32,
+                                (obj.pos.1 + offset.1) as f32,
+                            ),
                             egui::vec2(obj.size.0 as f32, obj.size.1 as f32),
                         ),
                     );
                 }
             }
 
+            // Уходящая сцена рисуется под текущей
             if state.dirty {
-                objects_to_draw.sort_by_key(|o| o.z_index);
+                objects_to_draw.sort_by_key(|o| (!o.detached, o.z_index));
                 state.dirty = false;
             }
         }
 
+        let fill = egui::Color32::from_rgba_unmultiplied(
+            background.0,
+            background.1,
+            background.2,
+            background.3,
+        );
+
         egui::CentralPanel::default()
-            .frame(egui::Frame::none())
+            .frame(egui::Frame::none().fill(fill))
             .show(ctx, |ui| {
-                for obj in objects_to_draw {
+                for mut obj in objects_to_draw {
+                    let scene = obj.detached as usize;
+                    let offset = scene_offset[scene];
+                    let opacity = scene_opacity[scene] as f32 / 255.0;
+
+                    if scene_opacity[scene] == 0 {
+                        continue;
+                    }
+
+                    // Прозрачность сцены умножается на альфа канал объекта, в том числе
+                    // для цвета текста в create_layout_job
+                    obj.color.3 = (obj.color.3 as f32 * opacity) as u8;
+                    obj.border_color.3 = (obj.border_color.3 as f32 * opacity) as u8;
+
                     let rect = egui::Rect::from_min_size(
-                        egui::pos2(obj.pos.0 as f32, obj.pos.1 as f32),
+                        egui::pos2((obj.pos.0 + offset.0) as f32, (obj.pos.1 + offset.1) as f32),
                         egui::vec2(obj.size.0 as f32, obj.size.1 as f32),
                     );
 
@@ -511,9 +912,17 @@ impl eframe::App for FireworkEguiApp {
                     };
 
                     if obj.is_text {
-                        let job = create_layout_job(&obj);
-                        let pos = egui::pos2(obj.pos.0 as f32, obj.pos.1 as f32);
+                        let job = create_layout_job(&obj, fonts_active);
+                        let pos = rect.min;
                         current_painter.galley(pos, ctx.fonts(|f| f.layout_job(job)), color);
+                    } else if obj.is_image {
+                        if let Some((texture_id, texture_size)) =
+                            obj.texture.and_then(|texture| textures.get(&texture))
+                        {
+                            let (dest, uv) = fit_image(rect, *texture_size, obj.image_fit);
+                            let tint = egui::Color32::WHITE.gamma_multiply(opacity);
+                            current_painter.image(*texture_id, dest, uv, tint);
+                        }
                     } else {
                         let rounding = egui::Rounding {
                             nw: obj.corner_radius.0 as f32,
@@ -535,6 +944,10 @@ impl eframe::App for FireworkEguiApp {
                 }
             });
 
-        ctx.request_repaint();
+        if fps == 0 {
+            ctx.request_repaint();
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1) / fps as u32);
+        }
     }
 }
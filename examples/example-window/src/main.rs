@@ -1,9 +1,12 @@
 use firework_ui::ui;
-use firework_ui::{AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterResult};
+use firework_ui::{
+    AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterKeyModifiers, AdapterKeyPhase,
+    AdapterResult, key_code,
+};
 
 use winit::{
     dpi::LogicalSize,
-    event::{ElementState, Event, MouseButton, WindowEvent},
+    event::{ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     window::WindowBuilder,
 };
@@ -11,8 +14,67 @@ use winit::{
 use std::cell::RefCell;
 
 thread_local! {
-    static LAST_MOUSE_POS: RefCell<(u32, u32)> = RefCell::new((0, 0));
-    static MOUSE_BUTTON_DOWN: RefCell<bool> = RefCell::new(false);
+    static LAST_MOUSE_POS: RefCell<(u32, u32)> = const { RefCell::new((0, 0)) };
+    static MOUSE_BUTTON_DOWN: RefCell<bool> = const { RefCell::new(false) };
+    static MODIFIERS: RefCell<AdapterKeyModifiers> = const {
+        RefCell::new(AdapterKeyModifiers {
+            shift: false,
+            ctrl: false,
+            alt: false,
+            meta: false,
+        })
+    };
+}
+
+fn winit_key_code(key: VirtualKeyCode) -> Option<u32> {
+    use VirtualKeyCode as K;
+
+    let code = match key {
+        K::Back => key_code::BACKSPACE,
+        K::Tab => key_code::TAB,
+        K::Return => key_code::ENTER,
+        K::Escape => key_code::ESCAPE,
+        K::Space => key_code::SPACE,
+        K::Delete => key_code::DELETE,
+        K::Left => key_code::LEFT,
+        K::Right => key_code::RIGHT,
+        K::Up => key_code::UP,
+        K::Down => key_code::DOWN,
+        K::Home => key_code::HOME,
+        K::End => key_code::END,
+        K::PageUp => key_code::PAGE_UP,
+        K::PageDown => key_code::PAGE_DOWN,
+        K::Insert => key_code::INSERT,
+        K::Key0 => key_code::DIGIT_0,
+        K::Key1 => key_code::DIGIT_0 + 1,
+        K::Key2 => key_code::DIGIT_0 + 2,
+        K::Key3 => key_code::DIGIT_0 + 3,
+        K::Key4 => key_code::DIGIT_0 + 4,
+        K::Key5 => key_code::DIGIT_0 + 5,
+        K::Key6 => key_code::DIGIT_0 + 6,
+        K::Key7 => key_code::DIGIT_0 + 7,
+        K::Key8 => key_code::DIGIT_0 + 8,
+        K::Key9 => key_code::DIGIT_0 + 9,
+        K::F1 => key_code::F1,
+        K::F2 => key_code::F1 + 1,
+        K::F3 => key_code::F1 + 2,
+        K::F4 => key_code::F1 + 3,
+        K::F5 => key_code::F1 + 4,
+        K::F6 => key_code::F1 + 5,
+        K::F7 => key_code::F1 + 6,
+        K::F8 => key_code::F1 + 7,
+        K::F9 => key_code::F1 + 8,
+        K::F10 => key_code::F1 + 9,
+        K::F11 => key_code::F1 + 10,
+        K::F12 => key_code::F1 + 11,
+        // Буквы в VirtualKeyCode идут подряд от A до Z
+        _ if (K::A as u32..=K::Z as u32).contains(&(key as u32)) => {
+            key_code::A + (key as u32 - K::A as u32)
+        }
+        _ => return None,
+    };
+
+    Some(code)
 }
 
 #[ui]
@@ -40,14 +102,27 @@ fn my_adapter(command: AdapterCommand) -> AdapterResult {
             title,
             width,
             height,
+            min_size,
+            max_size,
+            resizable,
             listener,
+            ..
         } => {
             let event_loop = EventLoop::new();
-            let window = WindowBuilder::new()
+            let mut builder = WindowBuilder::new()
                 .with_title(title)
                 .with_inner_size(LogicalSize::new(width, height))
-                .build(&event_loop)
-                .unwrap();
+                .with_resizable(resizable);
+
+            if let Some((min_width, min_height)) = min_size {
+                builder = builder.with_min_inner_size(LogicalSize::new(min_width, min_height));
+            }
+
+            if let Some((max_width, max_height)) = max_size {
+                builder = builder.with_max_inner_size(LogicalSize::new(max_width, max_height));
+            }
+
+            let window = builder.build(&event_loop).unwrap();
 
             event_loop.run(move |event, _, control_flow| {
                 *control_flow = ControlFlow::Poll;
@@ -64,11 +139,14 @@ fn my_adapter(command: AdapterCommand) -> AdapterResult {
                             let y = position.y as u32;
 
                             LAST_MOUSE_POS.with(|pos| *pos.borrow_mut() = (x, y));
+                            listener(AdapterEvent::Touch(x, y, AdapterClickPhase::Moved));
                         }
 
-                        WindowEvent::MouseInput { state, button, .. }
-                            if button == MouseButton::Left =>
-                        {
+                        WindowEvent::MouseInput {
+                            state,
+                            button: MouseButton::Left,
+                            ..
+                        } => {
                             let (x, y) = LAST_MOUSE_POS.with(|pos| *pos.borrow());
                             let phase = match state {
                                 ElementState::Pressed => {
@@ -85,8 +163,63 @@ fn my_adapter(command: AdapterCommand) -> AdapterResult {
                             listener(AdapterEvent::Touch(x, y, phase));
                         }
 
+                        WindowEvent::Resized(size) => {
+                            let scale = window.scale_factor();
+                            let logical = size.to_logical::<u32>(scale);
+
+                            listener(AdapterEvent::Resize {
+                                width: logical.width,
+                                height: logical.height,
+                                scale: (scale * 100.0).round() as u16,
+                            });
+                        }
+
+                        WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            new_inner_size,
+                        } => {
+                            let logical = new_inner_size.to_logical::<u32>(scale_factor);
+
+                            listener(AdapterEvent::Resize {
+                                width: logical.width,
+                                height: logical.height,
+                                scale: (scale_factor * 100.0).round() as u16,
+                            });
+                        }
+
                         WindowEvent::ReceivedCharacter(ch) => {
-                            listener(AdapterEvent::Key(ch as u32));
+                            listener(AdapterEvent::Char(ch));
+                        }
+
+                        WindowEvent::ModifiersChanged(state) => {
+                            MODIFIERS.with(|modifiers| {
+                                *modifiers.borrow_mut() = AdapterKeyModifiers {
+                                    shift: state.shift(),
+                                    ctrl: state.ctrl(),
+                                    alt: state.alt(),
+                                    meta: state.logo(),
+                                }
+                            });
+                        }
+
+                        WindowEvent::KeyboardInput {
+                            input:
+                                KeyboardInput {
+                                    state,
+                                    virtual_keycode: Some(key),
+                                    ..
+                                },
+                            ..
+                        } => {
+                            if let Some(code) = winit_key_code(key) {
+                                let phase = match state {
+                                    ElementState::Pressed => AdapterKeyPhase::Down,
+                                    ElementState::Released => AdapterKeyPhase::Up,
+                                };
+                                let modifiers = MODIFIERS.with(|modifiers| *modifiers.borrow());
+
+                                listener(AdapterEvent::Key(code, phase, modifiers));
+                            }
                         }
 
                         _ => (),
@@ -0,0 +1,134 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use crate::layout::{Viewport, set_viewport, viewport};
+use crate::runtime::dyn_list::{ListOverflow, set_list_overflow};
+use crate::runtime::transition;
+use crate::{
+    AdapterCommand, AdapterResult, Transition, after_first_flash, default_adapter, set_adapter,
+};
+
+/// Конфигурация приложения и его окна. Все параметры передаются адаптеру в
+/// AdapterCommand::RunLoop, адаптер сам решает какие из них поддерживает платформа
+///
+/// ```ignore
+/// firework_ui::App::new("Kiosk")
+///     .size(1080, 1920)
+///     .resizable(false)
+///     .fps(30)
+///     .run_with_adapter(my_adapter, root_screen);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct App {
+    pub title: &'static str,
+
+    /// Логический размер окна (ширина, высота)
+    pub size: (u32, u32),
+
+    pub min_size: Option<(u32, u32)>,
+    pub max_size: Option<(u32, u32)>,
+    pub resizable: bool,
+
+    /// Целевая частота кадров, 0 если частоту выбирает платформа
+    pub fps: u16,
+
+    /// R, G, B, A цвет фона от 0 до 255
+    pub background: (u8, u8, u8, u8),
+
+    /// Анимация перехода между экранами при навигации
+    pub transition: Transition,
+
+    /// Поведение списков циклов при переполнении в режиме no-alloc
+    pub list_overflow: ListOverflow,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new("Firework")
+    }
+}
+
+impl App {
+    pub const fn new(title: &'static str) -> Self {
+        Self {
+            title,
+            size: (720, 1280),
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            fps: 60,
+            background: (255, 255, 255, 255),
+            transition: Transition::None,
+            list_overflow: ListOverflow::Panic,
+        }
+    }
+
+    pub const fn title(mut self, title: &'static str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Устанавливает логический размер окна
+    pub const fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub const fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    pub const fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub const fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub const fn fps(mut self, fps: u16) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Устанавливает цвет фона окна (R, G, B, A)
+    pub const fn background(mut self, background: (u8, u8, u8, u8)) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Устанавливает анимацию перехода между экранами, её можно поменять позже через
+    /// navigate::set_transition
+    pub const fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Устанавливает политику переполнения списков циклов, её можно поменять позже через
+    /// set_list_overflow
+    pub const fn list_overflow(mut self, policy: ListOverflow) -> Self {
+        self.list_overflow = policy;
+        self
+    }
+
+    /// Запуск приложения без адаптера рендеринга
+    pub fn run(self, root_slide: fn()) {
+        self.run_with_adapter(default_adapter, root_slide);
+    }
+
+    /// Запуск приложения: первый проход корневого экрана, после чего адаптер получает
+    /// RunLoop с параметрами окна. Пока адаптер не прислал Resize корневые ограничения
+    /// равны запрошенному размеру окна
+    pub fn run_with_adapter(self, adapter: fn(AdapterCommand) -> AdapterResult, root_slide: fn()) {
+        set_adapter(adapter);
+        set_viewport(Viewport::new(self.size.0, self.size.1, viewport().scale));
+        transition::reset(self.transition);
+        set_list_overflow(self.list_overflow);
+        crate::navigate::start(root_slide);
+
+        after_first_flash(&self);
+    }
+}
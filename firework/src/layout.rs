@@ -1,6 +1,13 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+#[cfg(feature = "safety-multithread")]
+use std::sync::{Mutex, OnceLock};
+
+use crate::runtime::scroll::ScrollView;
+use crate::std_widgets::widget::Widget;
+use crate::{CurrentEvent, get_current_event};
+
 /// Структура ограничений которые родитель отдаёт ребёнку. В Firework есть чёткое разделение
 /// на лайаут и виджет поэтому лайаут берёт свои ограничения (которые он получил от своего
 /// родителя или корня) и передаёт их детям чтобы они знали в какое пространство им нужно
@@ -13,8 +20,89 @@ pub struct Constraints {
     pub max_height: i32,
 }
 
+impl Constraints {
+    /// Свободные ограничения, ребёнок может занять от нуля до переданного размера
+    pub const fn loose(width: i32, height: i32) -> Self {
+        Self {
+            min_width: 0,
+            max_width: width,
+            min_height: 0,
+            max_height: height,
+        }
+    }
+}
+
+/// Размер окна в логических пикселях и масштаб экрана. Задаётся из App при запуске и
+/// обновляется адаптером через AdapterEvent::Resize
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+
+    /// Масштаб в сотых долях (100 = 1.0, 200 = 2.0)
+    pub scale: u16,
+}
+
+impl Viewport {
+    pub const fn new(width: u32, height: u32, scale: u16) -> Self {
+        Self {
+            width,
+            height,
+            scale,
+        }
+    }
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut VIEWPORT: Viewport = Viewport::new(720, 1280, 100);
+
+#[cfg(feature = "safety-multithread")]
+static VIEWPORT: OnceLock<Mutex<Viewport>> = OnceLock::new();
+
+/// Текущий размер окна
+#[cfg(not(feature = "safety-multithread"))]
+pub fn viewport() -> Viewport {
+    unsafe { *::core::ptr::addr_of!(VIEWPORT) }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn viewport() -> Viewport {
+    *VIEWPORT
+        .get_or_init(|| Mutex::new(Viewport::new(720, 1280, 100)))
+        .lock()
+        .unwrap()
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+pub fn set_viewport(viewport: Viewport) {
+    unsafe {
+        *::core::ptr::addr_of_mut!(VIEWPORT) = viewport;
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn set_viewport(viewport: Viewport) {
+    *VIEWPORT
+        .get_or_init(|| Mutex::new(viewport))
+        .lock()
+        .unwrap() = viewport;
+}
+
+/// Ограничения корня дерева лайаутов, совпадают с текущим размером окна
+pub fn root_constraints() -> Constraints {
+    let viewport = viewport();
+
+    Constraints::loose(viewport.width as i32, viewport.height as i32)
+}
+
+/// Нужно ли заново скомпоновать экран вне Build и Navigate. Сейчас это происходит когда
+/// изменился размер окна
+pub fn needs_relayout() -> bool {
+    matches!(get_current_event(), CurrentEvent::Resize { .. })
+}
+
 /// Итоговый размер виджета после вызова layout
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Size {
     pub width: i32,
     pub height: i32,
@@ -39,7 +127,7 @@ impl Size {
 
 /// Параметры макета (лайаута) для компоновки, компилятор извлекает их из layout! {} виджета
 /// и генерирует заполнение этой структуры
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct LayoutParams {
     // Внутренний отступ контейнера
     pub padding: (
@@ -48,9 +136,70 @@ pub struct LayoutParams {
         /* bottom */ i32,
         /* left */ i32,
     ),
+
+    // Расстояние между соседними детьми по основной оси (для vertical и horizontal)
+    pub spacing: i32,
+
+    // Шаг строки lazy_list вместе с промежутком между строками
+    pub extent: i32,
+
+    // Размер окна lazy_list и scroll по оси прокрутки, 0 если окно занимает всё
+    // доступное место
+    pub viewport: i32,
+
+    // Прокрутка lazy_list и scroll, на сколько содержимое сдвинуто вверх (или влево)
+    pub offset: i32,
+
+    // Сколько строк lazy_list создаётся за пределами окна с каждой стороны
+    pub overscan: usize,
+
+    // Прокручивается ли scroll по горизонтали, по умолчанию по вертикали
+    pub horizontal: bool,
 }
 
 impl LayoutParams {
+    /// Устанавливает внутренний отступ (top, right, bottom, left)
+    pub fn padding(mut self, padding: (i32, i32, i32, i32)) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Устанавливает расстояние между детьми
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Устанавливает шаг строки виртуализированного списка
+    pub fn extent(mut self, extent: i32) -> Self {
+        self.extent = extent;
+        self
+    }
+
+    /// Устанавливает высоту окна виртуализированного списка
+    pub fn viewport(mut self, viewport: i32) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    /// Устанавливает прокрутку виртуализированного списка
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Устанавливает запас строк за пределами окна
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Устанавливает горизонтальную прокрутку для scroll
+    pub fn horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+
     /// Применяет LayoutParams к ограничениям (Constraints) и возвращает изменённые
     /// ограничения для детей. Min_width и min_height не меняются, работа идёт только
     /// с максимумом
@@ -88,6 +237,446 @@ impl LayoutParams {
     }
 }
 
+/// Вид лайаута, компилятор выбирает его по имени макроса (vertical!, horizontal!, stack!,
+/// absoulute!)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutKind {
+    // Дети идут сверху вниз
+    Vertical,
+
+    // Дети идут слева направо
+    Horizontal,
+
+    // Все дети в левом верхнем углу контейнера друг над другом
+    Stack,
+
+    // Дети сами задают свою позицию, лайаут их только измеряет
+    Absolute,
+
+    // Виртуализированный список: строки цикла стоят на row * extent - offset, а сам
+    // лайаут занимает только окно (viewport)
+    Lazy,
+
+    // Прокрутка: дети идут друг за другом как в vertical (или horizontal) без
+    // ограничения по оси прокрутки и сдвинуты на offset, а сам лайаут занимает окно
+    Scroll,
+}
+
+/// Ограничение по оси прокрутки для детей scroll, содержимое может быть сколько угодно
+/// длинным. Запас до i32::MAX оставлен чтобы сложение позиций не переполнялось
+pub const SCROLL_UNBOUNDED: i32 = i32::MAX / 4;
+
+/// Окно строк виртуализированного списка lazy_list!. Сгенерированный код считает его в
+/// начале тела списка, и виджет цикла создаёт скин только для строк внутри окна. Скины
+/// строк ушедших из окна DynList прячет в пул и отдаёт новым строкам, поэтому список из
+/// тысяч строк держит столько скинов сколько помещается на экране плюс overscan с
+/// каждой стороны
+///
+/// Внутри scroll окно берётся из прокрутки и размера scroll, а offset и viewport у
+/// самого lazy_list не нужны
+///
+/// ```ignore
+/// lazy_list! {
+///     layout! {
+///         extent: 40,
+///         viewport: 600,
+///         offset: scroll,
+///         overscan: 2,
+///     }
+///
+///     for (i, item) in items.iter().enumerate() {
+///         text! {
+///             content: item,
+///
+///             #[key_type(usize)]
+///             key: i,
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LazyWindow {
+    // Видимый отрезок в пикселях от начала содержимого
+    start: i32,
+    end: i32,
+
+    extent: i32,
+    overscan: usize,
+}
+
+impl LazyWindow {
+    pub fn new(params: LayoutParams) -> Self {
+        let start = params.offset.max(0);
+        let height = if params.viewport > 0 {
+            params.viewport
+        } else {
+            viewport().height as i32
+        };
+
+        Self {
+            start,
+            end: start.saturating_add(height),
+            extent: params.extent,
+            overscan: params.overscan,
+        }
+    }
+
+    /// Окно lazy_list внутри вертикального scroll: видимый отрезок это прокрутка и окно
+    /// scroll. Без состояния scroll (до первого кадра) или в горизонтальном scroll окно
+    /// считается как у обычного lazy_list
+    pub fn in_scroll(params: LayoutParams, view: Option<&ScrollView>) -> Self {
+        match view {
+            Some(view) if !view.is_horizontal() => Self {
+                start: view.offset(),
+                end: view.offset().saturating_add(view.window()),
+                extent: params.extent,
+                overscan: params.overscan,
+            },
+
+            _ => Self::new(params),
+        }
+    }
+
+    /// Попадает ли строка с этим индексом в окно. top это начало списка в содержимом
+    /// scroll (DynList::top), у списка вне scroll он нулевой
+    pub fn contains(&self, row: usize, top: i32) -> bool {
+        let extent = self.extent.max(1) as i64;
+        let start = (self.start as i64 - top as i64).max(0);
+        let end = (self.end as i64 - top as i64).max(0);
+
+        let first = (start / extent) as usize;
+        let last = ((end + extent - 1) / extent) as usize;
+
+        row >= first.saturating_sub(self.overscan) && row < last.saturating_add(self.overscan)
+    }
+}
+
+/// Курсор компоновки. Кодогенератор создаёт его для корневого лайаута и передаёт в него
+/// детей по порядку декларации, курсор измеряет ребёнка (Widget::layout) и сразу ставит
+/// его на место (Widget::position). Такой однопроходный подход не требует хранить детей
+/// в векторе и работает в no-alloc. Вложенный лайаут получает свой курсор через nested
+/// и после завершения сообщает родителю свой размер через advance
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutCursor {
+    kind: LayoutKind,
+    params: LayoutParams,
+
+    // Ограничения которые получил сам контейнер
+    constraints: Constraints,
+
+    // Ограничения для детей (уже без padding)
+    inner: Constraints,
+
+    // Левый верхний угол контейнера
+    origin: (i32, i32),
+
+    // Сколько места занято по основной оси
+    offset: i32,
+
+    // Размер содержимого без padding
+    content: Size,
+
+    // Количество размещённых детей, нужно для spacing
+    count: usize,
+
+    // Хэндл обрезки ближайшего scroll, дети получают его через Widget::clip_to
+    clip: Option<usize>,
+
+    // Курсор прямой ребёнок scroll: прокрутку уже учёл родитель, поэтому lazy_list
+    // не сдвигает строки сам и занимает высоту всех строк
+    scrolled: bool,
+
+    // Сколько строк прошёл lazy_list, нужно для его высоты внутри scroll
+    rows: usize,
+
+    // Начало курсора в содержимом вертикального scroll, по нему lazy_list находит
+    // свои строки в окне прокрутки
+    top: i32,
+}
+
+impl LayoutCursor {
+    pub fn new(
+        kind: LayoutKind,
+        params: LayoutParams,
+        constraints: Constraints,
+        origin: (i32, i32),
+    ) -> Self {
+        let mut inner = params.apply_to(&constraints);
+
+        if kind == LayoutKind::Scroll {
+            if params.horizontal {
+                inner.max_width = SCROLL_UNBOUNDED;
+            } else {
+                inner.max_height = SCROLL_UNBOUNDED;
+            }
+        }
+
+        Self {
+            kind,
+            params,
+            constraints,
+            inner,
+            origin,
+            offset: 0,
+            content: Size {
+                width: 0,
+                height: 0,
+            },
+            count: 0,
+            clip: None,
+            scrolled: false,
+            rows: 0,
+            top: 0,
+        }
+    }
+
+    /// Подключает курсор scroll к его состоянию: дети сдвигаются на текущую прокрутку
+    /// и обрезаются по окну
+    pub fn scroll_with(&mut self, view: &ScrollView) {
+        self.params.offset = view.offset();
+        self.clip = view.clip().or(self.clip);
+    }
+
+    /// Начало первой строки lazy_list в содержимом scroll, вне scroll всегда ноль
+    pub fn content_top(&self) -> i32 {
+        if self.scrolled {
+            self.top + self.params.padding.0
+        } else {
+            0
+        }
+    }
+
+    /// Сколько строк прошёл цикл lazy_list, вызывается после размещения строк
+    pub fn lazy_rows(&mut self, rows: usize) {
+        self.rows = self.rows.max(rows);
+    }
+
+    // Горизонтальная ли основная ось у курсора
+    fn is_horizontal(&self) -> bool {
+        match self.kind {
+            LayoutKind::Horizontal => true,
+            LayoutKind::Scroll => self.params.horizontal,
+            _ => false,
+        }
+    }
+
+    fn clip_child<W: Widget + ?Sized>(&self, widget: &mut W) {
+        if let Some(clip) = self.clip {
+            widget.clip_to(clip);
+        }
+    }
+
+    /// Позиция и ограничения для следующего ребёнка
+    fn next_slot(&self) -> ((i32, i32), Constraints) {
+        let (top, _, _, left) = self.params.padding;
+        let spacing = if self.count > 0 {
+            self.params.spacing
+        } else {
+            0
+        };
+
+        let x = self.origin.0 + left;
+        let y = self.origin.1 + top;
+
+        // Ребёнок не обязан заполнять контейнер поэтому минимум всегда нулевой
+        let mut constraints = Constraints::loose(self.inner.max_width, self.inner.max_height);
+
+        // Прокрутка сдвигает всё содержимое scroll против оси
+        let scroll = if self.kind == LayoutKind::Scroll {
+            self.params.offset
+        } else {
+            0
+        };
+
+        match self.kind {
+            LayoutKind::Stack | LayoutKind::Absolute => ((x, y), constraints),
+
+            _ if self.is_horizontal() => {
+                constraints.max_width = (constraints.max_width - self.offset - spacing).max(0);
+                ((x + self.offset + spacing - scroll, y), constraints)
+            }
+
+            _ => {
+                constraints.max_height = (constraints.max_height - self.offset - spacing).max(0);
+                ((x, y + self.offset + spacing - scroll), constraints)
+            }
+        }
+    }
+
+    /// Сдвигает курсор на размер ребёнка
+    pub fn advance(&mut self, size: Size) {
+        let spacing = if self.count > 0 {
+            self.params.spacing
+        } else {
+            0
+        };
+
+        match self.kind {
+            LayoutKind::Stack | LayoutKind::Absolute => {
+                self.content.width = self.content.width.max(size.width);
+                self.content.height = self.content.height.max(size.height);
+            }
+
+            _ if self.is_horizontal() => {
+                self.offset += spacing + size.width;
+                self.content.width = self.offset;
+                self.content.height = self.content.height.max(size.height);
+            }
+
+            _ => {
+                self.offset += spacing + size.height;
+                self.content.height = self.offset;
+                self.content.width = self.content.width.max(size.width);
+            }
+        }
+
+        self.count += 1;
+    }
+
+    /// Измеряет ребёнка и ставит его на следующее место
+    pub fn place<W: Widget + ?Sized>(&mut self, widget: &mut W) {
+        let (position, constraints) = self.next_slot();
+        let size = widget.layout(constraints);
+
+        // В absoulute лайауте ребёнок сам отвечает за свою позицию
+        if self.kind != LayoutKind::Absolute {
+            widget.position(position);
+        }
+
+        self.clip_child(widget);
+        self.advance(size);
+    }
+
+    /// То же что place, но позиция отправляется только если она отличается от placed
+    /// (последней выставленной). Используется для элементов DynList чтобы перестановка
+    /// в большом списке не двигала строки которые остались на своём месте
+    pub fn place_cached<W: Widget + ?Sized>(
+        &mut self,
+        widget: &mut W,
+        placed: &mut Option<(i32, i32)>,
+    ) {
+        let (position, constraints) = self.next_slot();
+        let size = widget.layout(constraints);
+
+        if self.kind != LayoutKind::Absolute && *placed != Some(position) {
+            widget.position(position);
+            *placed = Some(position);
+        }
+
+        self.clip_child(widget);
+        self.advance(size);
+    }
+
+    /// Размещает строку lazy_list по её индексу: строка стоит на row * extent со сдвигом
+    /// на прокрутку и получает высоту не больше extent. Позиция отправляется только при
+    /// изменении, как в place_cached. Внутри scroll сдвиг уже учтён родителем
+    pub fn place_row<W: Widget + ?Sized>(
+        &mut self,
+        widget: &mut W,
+        placed: &mut Option<(i32, i32)>,
+        row: usize,
+    ) {
+        let (top, _, _, left) = self.params.padding;
+        let extent = self.params.extent.max(0);
+        let scroll = if self.scrolled { 0 } else { self.params.offset };
+
+        let size = widget.layout(Constraints::loose(self.inner.max_width, extent));
+        let position = (
+            self.origin.0 + left,
+            self.origin.1 + top + row as i32 * extent - scroll,
+        );
+
+        if *placed != Some(position) {
+            widget.position(position);
+            *placed = Some(position);
+        }
+
+        self.clip_child(widget);
+        self.content.width = self.content.width.max(size.width);
+        self.count += 1;
+    }
+
+    /// Создаёт курсор для вложенного лайаута который займёт следующее место в этом
+    pub fn nested(&self, kind: LayoutKind, params: LayoutParams) -> LayoutCursor {
+        let (position, constraints) = self.next_slot();
+
+        // Вложенный absoulute лайаут считает координаты от корня экрана
+        let origin = if self.kind == LayoutKind::Absolute {
+            self.origin
+        } else {
+            position
+        };
+
+        let mut cursor = LayoutCursor::new(kind, params, constraints, origin);
+        cursor.clip = self.clip;
+        cursor.scrolled = self.kind == LayoutKind::Scroll;
+
+        // Позиция уже сдвинута на прокрутку, возвращаем её обратно
+        if cursor.scrolled && !self.params.horizontal {
+            cursor.top = position.1 - self.origin.1 + self.params.offset;
+        }
+
+        cursor
+    }
+
+    /// Завершает компоновку и возвращает размер контейнера вместе с padding
+    pub fn finish(mut self) -> Size {
+        let (top, right, bottom, left) = self.params.padding;
+
+        // Виртуализированный список занимает окно, а не высоту всех строк. Внутри scroll
+        // наоборот, окном управляет scroll и ему нужна высота всего списка
+        if self.kind == LayoutKind::Lazy {
+            self.content.height = if self.scrolled {
+                self.rows as i32 * self.params.extent.max(0)
+            } else if self.params.viewport > 0 {
+                self.params.viewport
+            } else {
+                self.inner.max_height
+            };
+        }
+
+        // Scroll занимает окно по оси прокрутки, содержимое за окном обрезается
+        if self.kind == LayoutKind::Scroll {
+            let viewport = self.params.viewport;
+            let window = |max: i32| if viewport > 0 { viewport } else { max };
+
+            if self.params.horizontal {
+                self.content.width = window(self.constraints.max_width) - left - right;
+            } else {
+                self.content.height = window(self.constraints.max_height) - top - bottom;
+            }
+        }
+
+        Size {
+            width: (self.content.width + left + right).clamp(
+                self.constraints.min_width,
+                self.constraints.max_width.max(0),
+            ),
+            height: (self.content.height + top + bottom).clamp(
+                self.constraints.min_height,
+                self.constraints.max_height.max(0),
+            ),
+        }
+    }
+
+    /// Завершает компоновку scroll и передаёт его состоянию окно и длину содержимого,
+    /// по ним ограничивается прокрутка и ставится прямоугольник обрезки
+    pub fn finish_scroll(self, view: &mut ScrollView) -> Size {
+        let (top, right, bottom, left) = self.params.padding;
+        let content = if self.params.horizontal {
+            self.content.width + left + right
+        } else {
+            self.content.height + top + bottom
+        };
+
+        let origin = self.origin;
+        let size = self.finish();
+        view.place(origin, size, content);
+
+        size
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +709,122 @@ mod tests {
         // Это невалидно
         assert!(!size.is_valid(constraints));
     }
+
+    /// Тестовый виджет который запоминает свою позицию
+    struct Probe {
+        size: (i32, i32),
+        position: core::cell::Cell<(i32, i32)>,
+    }
+
+    impl Probe {
+        fn new(width: i32, height: i32) -> Self {
+            Self {
+                size: (width, height),
+                position: core::cell::Cell::new((-1, -1)),
+            }
+        }
+    }
+
+    impl Widget for Probe {
+        fn position(&mut self, position: (i32, i32)) {
+            self.position.set(position);
+        }
+
+        fn visible(&self, _state: bool) {}
+
+        fn unmount(self) {}
+
+        fn layout(&mut self, constraints: Constraints) -> Size {
+            Size {
+                width: self
+                    .size
+                    .0
+                    .clamp(constraints.min_width, constraints.max_width),
+                height: self
+                    .size
+                    .1
+                    .clamp(constraints.min_height, constraints.max_height),
+            }
+        }
+    }
+
+    #[test]
+    fn test_layout_vertical_cursor() {
+        let params = LayoutParams::default().padding((10, 0, 10, 5)).spacing(4);
+        let mut cursor = LayoutCursor::new(
+            LayoutKind::Vertical,
+            params,
+            Constraints::loose(100, 100),
+            (0, 0),
+        );
+
+        let mut first = Probe::new(50, 20);
+        let mut second = Probe::new(200, 30);
+
+        cursor.place(&mut first);
+        cursor.place(&mut second);
+
+        assert_eq!(first.position.get(), (5, 10));
+        assert_eq!(second.position.get(), (5, 34));
+
+        // Второй ребёнок шире контейнера и был обрезан до 95 (100 - padding слева)
+        assert_eq!(
+            cursor.finish(),
+            Size {
+                width: 100,
+                height: 74
+            }
+        );
+    }
+
+    #[test]
+    fn test_layout_nested_horizontal_in_vertical() {
+        let mut root = LayoutCursor::new(
+            LayoutKind::Vertical,
+            LayoutParams::default(),
+            Constraints::loose(100, 100),
+            (0, 0),
+        );
+
+        let mut header = Probe::new(100, 10);
+        root.place(&mut header);
+
+        let mut row = root.nested(LayoutKind::Horizontal, LayoutParams::default());
+        let mut left = Probe::new(30, 20);
+        let mut right = Probe::new(30, 25);
+        row.place(&mut left);
+        row.place(&mut right);
+        root.advance(row.finish());
+
+        let mut footer = Probe::new(10, 10);
+        root.place(&mut footer);
+
+        assert_eq!(left.position.get(), (0, 10));
+        assert_eq!(right.position.get(), (30, 10));
+        assert_eq!(footer.position.get(), (0, 35));
+    }
+
+    #[test]
+    fn test_layout_lazy_window() {
+        let params = LayoutParams::default().extent(10).viewport(30);
+
+        let window = LazyWindow::new(params.offset(25));
+        assert!(!window.contains(1, 0));
+        assert!(window.contains(2, 0));
+        assert!(window.contains(5, 0));
+        assert!(!window.contains(6, 0));
+
+        // overscan добавляет строки с обеих сторон, но не уходит в отрицательные
+        let window = LazyWindow::new(params.offset(5).overscan(2));
+        assert!(window.contains(0, 0));
+        assert!(window.contains(5, 0));
+        assert!(!window.contains(6, 0));
+
+        // Список ниже начала содержимого scroll считает строки от своего top
+        let window = LazyWindow::new(params.offset(25));
+        assert!(window.contains(0, 20));
+        assert!(window.contains(3, 20));
+        assert!(!window.contains(4, 20));
+        assert!(!window.contains(0, 60));
+    }
 }
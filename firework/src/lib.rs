@@ -1,7 +1,9 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+pub mod app;
 pub mod layout;
+pub mod navigate;
 pub mod null_adapter;
 pub mod runtime;
 pub mod std_widgets;
@@ -11,13 +13,33 @@ mod runtime_errors;
 #[cfg(feature = "safety-multithread")]
 use std::sync::{Mutex, OnceLock};
 
-pub use firework_adapter::{AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterResult};
+pub use firework_adapter::{
+    AdapterClickPhase, AdapterCommand, AdapterEvent, AdapterKeyModifiers, AdapterKeyPhase,
+    AdapterResult, key_code,
+};
 pub use firework_macro::{component, effect, shared, ui};
 
+pub use app::App;
 pub use null_adapter::null_adapter;
-pub use runtime::dyn_list::{DynList, ListEntry};
+pub use runtime::animated::{Animatable, Animated, Easing};
+#[cfg(feature = "async")]
+pub use runtime::async_spark::{AsyncError, AsyncValue};
+pub use runtime::dyn_list::{
+    DEFAULT_LIST_CAPACITY, DynList, ListEntry, ListKey, ListOverflow, set_list_overflow,
+};
+#[cfg(feature = "tokio")]
+pub use runtime::executor::TokioSpawner;
+#[cfg(feature = "async")]
+pub use runtime::executor::{LocalExecutor, Spawner, set_spawner};
+pub use runtime::resources::{Font, Texture, free_texture, load_font, load_image, load_image_path};
+pub use runtime::scroll::ScrollView;
+pub use runtime::touch::{LONG_PRESS_MS, TOUCH_SLOP, TouchGesture, is_gesture, pressed_handle};
+pub use runtime::transition::{Transition, enter_screen};
 pub use runtime_errors::RENDER_ADAPTER_MISSING_ERROR;
-pub use std_widgets::DefaultRectSkin;
+pub use std_widgets::{
+    DefaultAppBarSkin, DefaultButtonSkin, DefaultImageSkin, DefaultRectSkin, DefaultTextSkin,
+    ImageFit, SurfaceState, TextAlign, TextStyle,
+};
 
 pub const TOUCH_HIT_GROUP: u16 = u16::MAX;
 
@@ -66,6 +88,81 @@ pub enum CurrentEvent {
         hit_object_id: Option<usize>,
         phase: AdapterClickPhase,
     },
+
+    /// Долгое нажатие, синтезируется рантаймом по часам тика когда палец удерживается
+    /// на одном хэндле дольше LONG_PRESS_MS
+    LongPress {
+        x: u32,
+        y: u32,
+        hit_object_id: usize,
+    },
+
+    /// Нажатие или отпускание клавиши, доставляется всем виджетам с on_key
+    Key(KeyEvent),
+
+    /// Набранный символ, доставляется всем виджетам с on_char
+    Char(char),
+
+    /// Изменился размер окна или масштаб, корневые ограничения уже обновлены и экран
+    /// компонуется заново
+    Resize {
+        width: u32,
+        height: u32,
+        scale: u16,
+    },
+
+    /// Экран выше по истории закрылся через navigate::pop_with, значение можно получить
+    /// через navigate::result или navigate::on_result
+    NavigationResult,
+
+    /// Кадр длиной dt миллисекунд, приходит только экранам с маркером tick!
+    Tick {
+        dt: u32,
+    },
+
+    /// Асинхронный спарк экрана получил значение, событие отправляет тик функция экрана
+    AsyncReady,
+}
+
+/// Событие клавиатуры которое получает замыкание on_key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyEvent {
+    /// Код клавиши из key_code
+    pub code: u32,
+    pub phase: AdapterKeyPhase,
+    pub modifiers: AdapterKeyModifiers,
+}
+
+impl KeyEvent {
+    pub fn is_down(&self) -> bool {
+        self.phase == AdapterKeyPhase::Down
+    }
+}
+
+/// Событие клавиатуры если оно текущее, используется сгенерированным кодом on_key
+pub fn current_key() -> Option<KeyEvent> {
+    match get_current_event() {
+        CurrentEvent::Key(key) => Some(key),
+        _ => None,
+    }
+}
+
+/// Набранный символ если он текущее событие, используется сгенерированным кодом on_char
+pub fn current_char() -> Option<char> {
+    match get_current_event() {
+        CurrentEvent::Char(ch) => Some(ch),
+        _ => None,
+    }
+}
+
+/// Задаёт тип аргумента замыкания on_key чтобы пользователю не нужно было его указывать
+pub fn key_handler<F: FnMut(KeyEvent)>(f: F) -> F {
+    f
+}
+
+/// Задаёт тип аргумента замыкания on_char
+pub fn char_handler<F: FnMut(char)>(f: F) -> F {
+    f
 }
 
 #[cfg(not(feature = "safety-multithread"))]
@@ -90,6 +187,21 @@ pub fn set_current_event(event: CurrentEvent) {
         .unwrap() = event;
 }
 
+/// Получить текущее событие без очистки, используется сгенерированным кодом виджетов
+/// чтобы сравнить событие с хэндлом скина
+#[cfg(not(feature = "safety-multithread"))]
+pub fn get_current_event() -> CurrentEvent {
+    unsafe { CURRENT_EVENT }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn get_current_event() -> CurrentEvent {
+    *CURRENT_EVENT
+        .get_or_init(|| Mutex::new(CurrentEvent::None))
+        .lock()
+        .unwrap()
+}
+
 /// Получить и ОЧИСТИТЬ текущее событие (заменить на None)
 #[cfg(not(feature = "safety-multithread"))]
 pub fn take_current_event() -> CurrentEvent {
@@ -157,6 +269,203 @@ pub fn set_tick_fn(f: fn()) {
         .unwrap() = Some(f);
 }
 
+// Подписан ли экран в фокусе на кадры (маркер tick!), подписка сбрасывается при входе
+// на новый экран и возобновляется его флэшем Build или Navigate
+#[cfg(not(feature = "safety-multithread"))]
+static mut TICK_SUBSCRIBED: bool = false;
+
+#[cfg(feature = "safety-multithread")]
+static TICK_SUBSCRIBED: OnceLock<Mutex<bool>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+fn set_tick_subscribed(subscribed: bool) {
+    unsafe {
+        *::core::ptr::addr_of_mut!(TICK_SUBSCRIBED) = subscribed;
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn set_tick_subscribed(subscribed: bool) {
+    *TICK_SUBSCRIBED
+        .get_or_init(|| Mutex::new(false))
+        .lock()
+        .unwrap() = subscribed;
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+fn is_tick_subscribed() -> bool {
+    unsafe { *::core::ptr::addr_of!(TICK_SUBSCRIBED) }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn is_tick_subscribed() -> bool {
+    *TICK_SUBSCRIBED
+        .get_or_init(|| Mutex::new(false))
+        .lock()
+        .unwrap()
+}
+
+// Запрошен ли кадр без подписки, его запрашивают анимированные спарки пока анимация идёт.
+// Запрос действует на один кадр, поэтому экран без tick! перестаёт получать Tick сразу
+// после окончания анимации
+#[cfg(not(feature = "safety-multithread"))]
+static mut TICK_REQUESTED: bool = false;
+
+#[cfg(feature = "safety-multithread")]
+static TICK_REQUESTED: OnceLock<Mutex<bool>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+fn set_tick_requested(requested: bool) {
+    unsafe {
+        *::core::ptr::addr_of_mut!(TICK_REQUESTED) = requested;
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn set_tick_requested(requested: bool) {
+    *TICK_REQUESTED
+        .get_or_init(|| Mutex::new(false))
+        .lock()
+        .unwrap() = requested;
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+fn take_tick_requested() -> bool {
+    unsafe { core::mem::take(&mut *::core::ptr::addr_of_mut!(TICK_REQUESTED)) }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn take_tick_requested() -> bool {
+    core::mem::take(
+        &mut *TICK_REQUESTED
+            .get_or_init(|| Mutex::new(false))
+            .lock()
+            .unwrap(),
+    )
+}
+
+/// Запрашивает событие Tick на следующем кадре для экрана без маркера tick!. Вызывается
+/// сгенерированным кодом анимированных спарков пока анимация идёт
+pub fn request_tick() {
+    set_tick_requested(true);
+}
+
+/// Во что разворачивается маркер tick!(dt, { ... }). При Build и Navigate экран
+/// подписывается на кадры, после чего каждый кадр получает событие Tick и замыкание
+/// вызывается с dt в миллисекундах. Спарки изменённые в замыкании обновляют экран как
+/// и в обработчиках виджетов
+///
+/// ```ignore
+/// let mut angle = spark!(0u32);
+///
+/// tick!(dt, {
+///     angle = (angle + dt / 10) % 360;
+/// });
+/// ```
+pub fn on_tick<F: FnOnce(u32)>(event: LifeCycle, f: F) {
+    if matches!(event, LifeCycle::Build | LifeCycle::Navigate) {
+        set_tick_subscribed(true);
+    }
+
+    on_frame(event, f);
+}
+
+/// То же что и on_tick, но без подписки: замыкание вызывается только на кадрах которые
+/// кто-то запросил через request_tick или на которые экран подписан маркером tick!
+pub fn on_frame<F: FnOnce(u32)>(event: LifeCycle, f: F) {
+    // Только первая итерация реактивного цикла, дальше событие уже Reactive
+    if let (LifeCycle::Event, CurrentEvent::Tick { dt }) = (event, get_current_event()) {
+        f(dt);
+    }
+}
+
+/// Сбрасывает подписку на кадры, вызывается при входе на новый экран
+pub(crate) fn reset_tick_subscription() {
+    set_tick_subscribed(false);
+    set_tick_requested(false);
+}
+
+// Функция которая освобождает состояние экрана в фокусе при уходе с него, её
+// устанавливают экраны с #[ui(drop_on_leave)]
+#[cfg(not(feature = "safety-multithread"))]
+static mut CURRENT_LEAVE: Option<fn()> = None;
+
+#[cfg(feature = "safety-multithread")]
+static CURRENT_LEAVE: OnceLock<Mutex<Option<fn()>>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+pub fn set_leave_fn(f: fn()) {
+    unsafe {
+        CURRENT_LEAVE = Some(f);
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn set_leave_fn(f: fn()) {
+    *CURRENT_LEAVE
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap() = Some(f);
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+fn take_leave_fn() -> Option<fn()> {
+    unsafe { (*::core::ptr::addr_of_mut!(CURRENT_LEAVE)).take() }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn take_leave_fn() -> Option<fn()> {
+    CURRENT_LEAVE
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap()
+        .take()
+}
+
+/// Вызывается экраном который получает фокус, до его инициализации. Если у прошлого
+/// экрана была функция ухода то она вызывается один раз
+pub fn leave_focus() {
+    if let Some(leave) = take_leave_fn() {
+        leave();
+    }
+}
+
+// Часы тика в миллисекундах, продвигаются на dt каждого кадра
+#[cfg(not(feature = "safety-multithread"))]
+static mut CURRENT_TIME_MS: u64 = 0;
+
+#[cfg(feature = "safety-multithread")]
+static CURRENT_TIME_MS: OnceLock<Mutex<u64>> = OnceLock::new();
+
+/// Текущее время по часам тика в миллисекундах
+#[cfg(not(feature = "safety-multithread"))]
+pub fn get_time_ms() -> u64 {
+    unsafe { CURRENT_TIME_MS }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn get_time_ms() -> u64 {
+    *CURRENT_TIME_MS
+        .get_or_init(|| Mutex::new(0))
+        .lock()
+        .unwrap()
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+fn advance_time_ms(dt: u32) {
+    unsafe {
+        CURRENT_TIME_MS += dt as u64;
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn advance_time_ms(dt: u32) {
+    *CURRENT_TIME_MS
+        .get_or_init(|| Mutex::new(0))
+        .lock()
+        .unwrap() += dt as u64;
+}
+
 #[cfg(not(feature = "safety-multithread"))]
 // Хранилище текущего фокуса (активного слайда) для ивентов
 static mut CURRENT_FOCUS: Option<fn()> = None;
@@ -279,33 +588,54 @@ fn default_adapter(_command: AdapterCommand) -> AdapterResult {
     AdapterResult::Void
 }
 
+/// Запуск приложения с параметрами окна по умолчанию, см. App
 pub fn run(root_slide: fn()) {
-    set_adapter(default_adapter);
-    root_slide();
-
-    after_first_flash();
+    App::default().run(root_slide);
 }
 
+/// Запуск приложения с адаптером и параметрами окна по умолчанию, см. App
 pub fn run_with_adapter(adapter: fn(AdapterCommand) -> AdapterResult, root_slide: fn()) {
-    set_adapter(adapter);
-    root_slide();
-
-    after_first_flash();
+    App::default().run_with_adapter(adapter, root_slide);
 }
 
-pub fn after_first_flash() {
+pub fn after_first_flash(app: &App) {
     adapter_command(AdapterCommand::RunLoop {
-        title: "Test",
-        width: 720,
-        height: 1280,
+        title: app.title,
+        width: app.size.0,
+        height: app.size.1,
+        min_size: app.min_size,
+        max_size: app.max_size,
+        resizable: app.resizable,
+        fps: app.fps,
+        background: app.background,
         listener: |event| match event {
             AdapterEvent::Touch(x, y, phase) => {
                 handle_touch_event(x, y, phase, TOUCH_HIT_GROUP);
             }
 
+            AdapterEvent::Key(code, phase, modifiers) => {
+                dispatch_event(CurrentEvent::Key(KeyEvent {
+                    code,
+                    phase,
+                    modifiers,
+                }));
+            }
+
+            AdapterEvent::Char(ch) => {
+                dispatch_event(CurrentEvent::Char(ch));
+            }
+
+            AdapterEvent::Resize {
+                width,
+                height,
+                scale,
+            } => {
+                handle_resize(width, height, scale);
+            }
+
             AdapterEvent::Tick => {
                 adapter_command(AdapterCommand::Render);
-                get_tick_fn()();
+                handle_tick(frame_dt_ms());
             }
 
             _ => {}
@@ -313,6 +643,23 @@ pub fn after_first_flash() {
     });
 }
 
+/// Обновляет корневые ограничения и отправляет фокусу событие Resize чтобы он
+/// скомпоновал лайауты заново. Повторный размер без изменений игнорируется
+pub fn handle_resize(width: u32, height: u32, scale: u16) {
+    let viewport = layout::Viewport::new(width, height, scale);
+
+    if layout::viewport() == viewport {
+        return;
+    }
+
+    layout::set_viewport(viewport);
+    dispatch_event(CurrentEvent::Resize {
+        width,
+        height,
+        scale,
+    });
+}
+
 pub fn handle_touch_event(x: u32, y: u32, phase: AdapterClickPhase, hit_group: u16) {
     let hit_result = adapter_command(AdapterCommand::ResolveHit(
         hit_group,
@@ -333,7 +680,67 @@ pub fn handle_touch_event(x: u32, y: u32, phase: AdapterClickPhase, hit_group: u
 }
 
 pub fn dispatch_event(event: CurrentEvent) {
+    runtime::touch::before_dispatch(&event, get_time_ms());
+
     set_current_event(event);
     get_focus()();
     set_current_event(CurrentEvent::None);
+
+    runtime::touch::after_dispatch(&event);
+    navigate::apply_pending();
+}
+
+/// Обработка кадра: часы тика продвигаются на dt миллисекунд, продвигается переход между
+/// экранами, после чего отправляются синтезированные события (долгое нажатие) и
+/// вызывается тик функция фокуса
+pub fn handle_tick(dt: u32) {
+    advance_time_ms(dt);
+    runtime::transition::tick(dt);
+
+    // Задачи встроенного исполнителя опрашиваются до тик функции экрана, поэтому
+    // отправленное ими значение попадает в спарк в этом же кадре
+    #[cfg(feature = "async")]
+    runtime::executor::tick();
+
+    if let Some(event) = runtime::touch::poll_long_press(get_time_ms()) {
+        dispatch_event(event);
+    }
+
+    get_tick_fn()();
+
+    // Запрос забирается до отправки, анимация которая ещё идёт запросит следующий кадр
+    // во время этого же события
+    if take_tick_requested() | is_tick_subscribed() {
+        dispatch_event(CurrentEvent::Tick { dt });
+    }
+
+    navigate::apply_pending();
+}
+
+// Момент прошлого кадра для подсчёта dt
+#[cfg(not(feature = "safety-multithread"))]
+static mut LAST_FRAME: Option<std::time::Instant> = None;
+
+#[cfg(feature = "safety-multithread")]
+static LAST_FRAME: OnceLock<Mutex<Option<std::time::Instant>>> = OnceLock::new();
+
+/// Сколько миллисекунд прошло с прошлого кадра, для первого кадра 0
+#[cfg(not(feature = "safety-multithread"))]
+fn frame_dt_ms() -> u32 {
+    let now = std::time::Instant::now();
+    let last = unsafe { (*::core::ptr::addr_of_mut!(LAST_FRAME)).replace(now) };
+
+    last.map_or(0, |last| now.duration_since(last).as_millis() as u32)
+}
+
+#[cfg(feature = "safety-multithread")]
+fn frame_dt_ms() -> u32 {
+    let now = std::time::Instant::now();
+    let last = LAST_FRAME
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap()
+        .replace(now);
+
+    last.map_or(0, |last| now.duration_since(last).as_millis() as u32)
 }
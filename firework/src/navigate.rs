@@ -0,0 +1,518 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+// Навигация между экранами #[ui] со стэком истории
+//
+// ```ignore
+// button! {
+//     label: "Открыть",
+//     on_click: || firework_ui::navigate::push(details_screen),
+// }
+// ```
+//
+// Переход не выполняется сразу: экран который вызвал push находится посреди своего
+// флэша (спарки взяты из его структуры), поэтому переход откладывается до конца
+// события или тика. Целевой экран получает флэш Build при первом посещении и Navigate
+// при возвращении, во втором случае виджеты создаются заново, а спарки сохраняют
+// значения которые были при уходе с экрана. Это поведение #[ui(keep_state)] (по
+// умолчанию), #[ui(reset_on_enter)] сбрасывает состояние при каждом входе, а
+// #[ui(drop_on_leave)] освобождает статику экрана сразу при уходе с него
+//
+// По умолчанию старый экран удаляется сразу, set_transition (или App::transition)
+// включает анимацию перехода: Slide или Fade, продвигаемые тиком
+//
+// Экран может принимать параметры как функциональные пропсы, они доступны только
+// во флэше Build или Navigate, в остальных фазах они None. Параметр хранится в записи
+// истории, поэтому при возвращении назад каждая запись получает свой параметр:
+//
+// ```ignore
+// #[ui]
+// fn details_screen(item_id: Prop<u32>) {
+//     let mut item = spark!(0u32);
+//
+//     if let Some(id) = item_id {
+//         item = id;
+//     }
+// }
+//
+// navigate::push_with(details_screen, 42);
+// ```
+//
+// Результат возвращается через pop_with и приходит предыдущему экрану событием
+// CurrentEvent::NavigationResult:
+//
+// ```ignore
+// navigate::on_result(|id: u32| selected = id);
+// ```
+
+#[cfg(feature = "safety-multithread")]
+use std::sync::{Mutex, OnceLock};
+
+use core::any::Any;
+#[cfg(feature = "no-alloc")]
+use core::any::TypeId;
+use core::mem;
+#[cfg(feature = "no-alloc")]
+use core::mem::{MaybeUninit, size_of};
+
+use crate::runtime::transition::{self, Direction};
+use crate::{CurrentEvent, Prop, dispatch_event, get_current_event, get_focus_id, set_focus_id};
+
+pub use crate::runtime::transition::{Transition, is_transitioning, set_transition};
+
+/// Максимальная глубина истории, при переполнении забывается самый старый экран
+pub const NAVIGATION_DEPTH: usize = 16;
+
+/// Максимальный размер параметра push_with и значения pop_with в байтах в режиме
+/// no-alloc, с кучей размер не ограничен
+pub const RESULT_CAPACITY: usize = 64;
+
+// Сколько переходов подряд может запросить сам переход (например экран который при
+// Build сразу перенаправляет на другой), дальше запросы отбрасываются
+const MAX_REDIRECTS: u8 = 8;
+
+/// Запись истории: функция экрана и его id из флэша
+#[derive(Debug, Clone, Copy)]
+pub struct Route {
+    pub screen: fn(),
+    pub id: u128,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Action {
+    Push(fn()),
+    Pop,
+    Replace(fn()),
+    Reset(fn()),
+}
+
+/// Тип параметра push_with и значения pop_with. С кучей подходит любой 'static тип (Send
+/// в safety-multithread), в режиме no-alloc значение хранится байтами без Drop, поэтому
+/// там принимаются только Copy типы не больше RESULT_CAPACITY байт
+#[cfg(all(not(feature = "no-alloc"), not(feature = "safety-multithread")))]
+pub trait NavigationValue: Any {}
+
+#[cfg(all(not(feature = "no-alloc"), not(feature = "safety-multithread")))]
+impl<T: Any> NavigationValue for T {}
+
+#[cfg(all(not(feature = "no-alloc"), feature = "safety-multithread"))]
+pub trait NavigationValue: Any + Send {}
+
+#[cfg(all(not(feature = "no-alloc"), feature = "safety-multithread"))]
+impl<T: Any + Send> NavigationValue for T {}
+
+#[cfg(feature = "no-alloc")]
+pub trait NavigationValue: Any + Copy {}
+
+#[cfg(feature = "no-alloc")]
+impl<T: Any + Copy> NavigationValue for T {}
+
+#[cfg(all(not(feature = "no-alloc"), not(feature = "safety-multithread")))]
+type AnyValue = dyn Any;
+
+#[cfg(all(not(feature = "no-alloc"), feature = "safety-multithread"))]
+type AnyValue = dyn Any + Send;
+
+/// Значение параметра экрана или pop_with со стёртым типом
+#[cfg(not(feature = "no-alloc"))]
+struct ValueSlot(Option<Box<AnyValue>>);
+
+#[cfg(not(feature = "no-alloc"))]
+impl ValueSlot {
+    const fn empty() -> Self {
+        Self(None)
+    }
+
+    fn new<T: NavigationValue>(value: T) -> Self {
+        Self(Some(Box::new(value)))
+    }
+
+    fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn get<T: NavigationValue + Clone>(&self) -> Option<T> {
+        let value: &dyn Any = self.0.as_deref()?;
+        value.downcast_ref::<T>().cloned()
+    }
+
+    fn take<T: NavigationValue>(&mut self) -> Option<T> {
+        if !self.0.as_deref().is_some_and(|value| value.is::<T>()) {
+            return None;
+        }
+
+        let value: Box<dyn Any> = self.0.take()?;
+        value.downcast::<T>().ok().map(|value| *value)
+    }
+}
+
+/// Значение параметра экрана или pop_with, хранится байтами вместе с TypeId чтобы
+/// работать без кучи
+#[cfg(feature = "no-alloc")]
+#[repr(C, align(16))]
+struct ValueSlot {
+    type_id: Option<TypeId>,
+    bytes: [MaybeUninit<u8>; RESULT_CAPACITY],
+}
+
+#[cfg(feature = "no-alloc")]
+impl ValueSlot {
+    const fn empty() -> Self {
+        Self {
+            type_id: None,
+            bytes: [MaybeUninit::uninit(); RESULT_CAPACITY],
+        }
+    }
+
+    fn new<T: NavigationValue>(value: T) -> Self {
+        const { assert!(size_of::<T>() <= RESULT_CAPACITY) };
+
+        let mut slot = Self::empty();
+
+        // SAFETY: размер проверен выше, запись без выравнивания
+        unsafe { slot.bytes.as_mut_ptr().cast::<T>().write_unaligned(value) };
+        slot.type_id = Some(TypeId::of::<T>());
+        slot
+    }
+
+    fn is_some(&self) -> bool {
+        self.type_id.is_some()
+    }
+
+    fn get<T: NavigationValue>(&self) -> Option<T> {
+        if self.type_id != Some(TypeId::of::<T>()) {
+            return None;
+        }
+
+        // SAFETY: значение этого типа было записано в new
+        Some(unsafe { self.bytes.as_ptr().cast::<T>().read_unaligned() })
+    }
+
+    fn take<T: NavigationValue>(&mut self) -> Option<T> {
+        let value = self.get();
+        self.type_id = None;
+        value
+    }
+}
+
+struct History {
+    routes: [Option<Route>; NAVIGATION_DEPTH],
+
+    // Параметр push_with каждой записи истории
+    args: [ValueSlot; NAVIGATION_DEPTH],
+    len: usize,
+
+    // Переход который будет выполнен после текущего события или тика, если за одно
+    // событие запрошено несколько переходов то выполняется последний
+    pending: Option<Action>,
+    pending_args: ValueSlot,
+
+    // Параметр экрана который сейчас показывает навигация, после показа он переходит в
+    // запись истории
+    showing_args: ValueSlot,
+
+    // Значение из pop_with, живёт до конца события NavigationResult
+    result: ValueSlot,
+
+    // Экран с параметром вызывается в режиме подготовки: вместо флэша он сообщает свою
+    // точку входа без параметров через stage
+    staging: bool,
+    staged: Option<fn()>,
+}
+
+impl History {
+    const fn new() -> Self {
+        Self {
+            routes: [None; NAVIGATION_DEPTH],
+            args: [const { ValueSlot::empty() }; NAVIGATION_DEPTH],
+            len: 0,
+            pending: None,
+            pending_args: ValueSlot::empty(),
+            showing_args: ValueSlot::empty(),
+            result: ValueSlot::empty(),
+            staging: false,
+            staged: None,
+        }
+    }
+
+    fn push(&mut self, route: Route, args: ValueSlot) {
+        if self.len == NAVIGATION_DEPTH {
+            self.routes.rotate_left(1);
+            self.args.rotate_left(1);
+            self.len -= 1;
+        }
+
+        self.routes[self.len] = Some(route);
+        self.args[self.len] = args;
+        self.len += 1;
+    }
+
+    fn request(&mut self, action: impl Into<Option<Action>>, args: ValueSlot) {
+        self.pending = action.into();
+        self.pending_args = args;
+    }
+
+    fn top(&self) -> Option<Route> {
+        self.len.checked_sub(1).and_then(|index| self.routes[index])
+    }
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut HISTORY: History = History::new();
+
+#[cfg(feature = "safety-multithread")]
+static HISTORY: OnceLock<Mutex<History>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+fn with_history<R>(f: impl FnOnce(&mut History) -> R) -> R {
+    unsafe { f(&mut *::core::ptr::addr_of_mut!(HISTORY)) }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn with_history<R>(f: impl FnOnce(&mut History) -> R) -> R {
+    f(&mut HISTORY
+        .get_or_init(|| Mutex::new(History::new()))
+        .lock()
+        .unwrap())
+}
+
+/// Переход на экран с добавлением его в историю
+pub fn push(screen: fn()) {
+    with_history(|history| history.request(Action::Push(screen), ValueSlot::empty()));
+}
+
+/// Возвращение на предыдущий экран. Возвращает false если предыдущего экрана нет, в
+/// этом случае ничего не происходит
+pub fn pop() -> bool {
+    with_history(|history| {
+        if history.len < 2 {
+            return false;
+        }
+
+        history.request(Action::Pop, ValueSlot::empty());
+        true
+    })
+}
+
+/// Заменяет текущий экран, назад вернуться на него нельзя
+pub fn replace(screen: fn()) {
+    with_history(|history| history.request(Action::Replace(screen), ValueSlot::empty()));
+}
+
+/// Очищает историю и переходит на экран, он становится корнем
+pub fn reset(screen: fn()) {
+    with_history(|history| history.request(Action::Reset(screen), ValueSlot::empty()));
+}
+
+/// Переход на экран с параметром. Параметр доступен экрану во флэше Build и Navigate,
+/// в том числе при возвращении на него назад. Параметр хранится в записи истории, поэтому
+/// один экран может быть в истории несколько раз с разными параметрами
+pub fn push_with<A: NavigationValue + Clone>(screen: fn(Prop<A>), args: A) {
+    if let Some(entry) = stage_screen(screen) {
+        with_history(|history| history.request(Action::Push(entry), ValueSlot::new(args)));
+    }
+}
+
+/// То же что и replace, но с параметром
+pub fn replace_with<A: NavigationValue + Clone>(screen: fn(Prop<A>), args: A) {
+    if let Some(entry) = stage_screen(screen) {
+        with_history(|history| history.request(Action::Replace(entry), ValueSlot::new(args)));
+    }
+}
+
+/// То же что и reset, но с параметром
+pub fn reset_with<A: NavigationValue + Clone>(screen: fn(Prop<A>), args: A) {
+    if let Some(entry) = stage_screen(screen) {
+        with_history(|history| history.request(Action::Reset(entry), ValueSlot::new(args)));
+    }
+}
+
+/// Возвращение на предыдущий экран со значением, предыдущий экран получит событие
+/// NavigationResult. Ограничения на тип значения см. в NavigationValue
+pub fn pop_with<T: NavigationValue>(value: T) -> bool {
+    with_history(|history| {
+        if history.len < 2 {
+            return false;
+        }
+
+        history.result = ValueSlot::new(value);
+        history.request(Action::Pop, ValueSlot::empty());
+        true
+    })
+}
+
+/// Забирает значение из pop_with если текущее событие NavigationResult и тип совпадает.
+/// Значение выдаётся один раз, иначе реактивный цикл экрана получал бы его на каждой
+/// итерации
+pub fn result<T: NavigationValue>() -> Option<T> {
+    if !matches!(get_current_event(), CurrentEvent::NavigationResult) {
+        return None;
+    }
+
+    with_history(|history| history.result.take())
+}
+
+/// Вызывает замыкание со значением из pop_with если оно пришло в этом событии. Спарки
+/// изменённые в замыкании обновляют экран как и в обработчиках виджетов
+pub fn on_result<T: NavigationValue, F: FnMut(T)>(mut f: F) {
+    if let Some(value) = result::<T>() {
+        f(value);
+    }
+}
+
+/// Используется сгенерированным кодом экрана с параметрами: в режиме подготовки экран
+/// только сохраняет параметры
+pub fn is_staging() -> bool {
+    with_history(|history| history.staging)
+}
+
+/// Используется сгенерированным кодом экрана с параметрами, сообщает точку входа
+/// которую рантайм вызывает как обычный экран без параметров
+pub fn stage(entry: fn()) {
+    with_history(|history| history.staged = Some(entry));
+}
+
+/// Используется сгенерированным кодом экрана с параметрами во флэше Build и Navigate:
+/// параметр записи истории которую сейчас показывает навигация
+pub fn args<A: NavigationValue + Clone>() -> Prop<A> {
+    with_history(|history| history.showing_args.get())
+}
+
+fn stage_screen<A>(screen: fn(Prop<A>)) -> Option<fn()> {
+    with_history(|history| {
+        history.staging = true;
+        history.staged = None;
+    });
+
+    screen(None);
+
+    with_history(|history| {
+        history.staging = false;
+        history.staged.take()
+    })
+}
+
+/// Количество экранов в истории включая текущий
+pub fn depth() -> usize {
+    with_history(|history| history.len)
+}
+
+/// Можно ли вернуться назад через pop
+pub fn can_pop() -> bool {
+    depth() > 1
+}
+
+/// Текущий экран на вершине истории
+pub fn current() -> Option<Route> {
+    with_history(|history| history.top())
+}
+
+/// Запускает корневой экран приложения, история начинается с него
+pub(crate) fn start(root: fn()) {
+    with_history(|history| *history = History::new());
+
+    // Первый экран показывается без перехода
+    set_focus_id(0);
+    root();
+    record_top(root, true);
+    apply_pending();
+}
+
+/// Выполняет отложенный переход, вызывается после события и тика
+pub(crate) fn apply_pending() {
+    for _ in 0..MAX_REDIRECTS {
+        let Some(action) = with_history(|history| history.pending.take()) else {
+            return;
+        };
+
+        // Параметр нового экрана доступен ему через args во время показа
+        with_history(|history| {
+            history.showing_args = mem::replace(&mut history.pending_args, ValueSlot::empty())
+        });
+
+        match action {
+            Action::Push(screen) => {
+                show(screen, Direction::Forward);
+                record_top(screen, true);
+            }
+
+            Action::Pop => {
+                let previous = with_history(|history| {
+                    if history.len < 2 {
+                        return None;
+                    }
+
+                    history.len -= 1;
+                    history.routes[history.len] = None;
+                    history.args[history.len] = ValueSlot::empty();
+
+                    // Экран получает параметр своей записи, после показа он возвращается
+                    let top = history.len - 1;
+                    history.showing_args = mem::replace(&mut history.args[top], ValueSlot::empty());
+                    history.top()
+                });
+
+                if let Some(route) = previous {
+                    show(route.screen, Direction::Back);
+
+                    with_history(|history| {
+                        let top = history.len - 1;
+                        history.args[top] =
+                            mem::replace(&mut history.showing_args, ValueSlot::empty());
+                    });
+
+                    if with_history(|history| history.result.is_some()) {
+                        dispatch_event(CurrentEvent::NavigationResult);
+                        with_history(|history| history.result = ValueSlot::empty());
+                    }
+                }
+            }
+
+            Action::Replace(screen) => {
+                show(screen, Direction::Forward);
+                record_top(screen, false);
+            }
+
+            Action::Reset(screen) => {
+                with_history(|history| {
+                    let args = mem::replace(&mut history.showing_args, ValueSlot::empty());
+                    *history = History::new();
+                    history.showing_args = args;
+                });
+
+                show(screen, Direction::Back);
+                record_top(screen, true);
+            }
+        }
+    }
+
+    with_history(|history| history.request(None, ValueSlot::empty()));
+}
+
+/// Вызывает экран так чтобы он прошёл флэш Build или Navigate даже если он уже в фокусе,
+/// вход на экран анимируется переходом из set_transition
+fn show(screen: fn(), direction: Direction) {
+    transition::arm(direction);
+    set_focus_id(0);
+    screen();
+}
+
+/// Записывает экран в историю с id который он установил себе во флэше, параметр показа
+/// переходит в запись
+fn record_top(screen: fn(), push: bool) {
+    let route = Route {
+        screen,
+        id: get_focus_id(),
+    };
+
+    with_history(|history| {
+        let args = mem::replace(&mut history.showing_args, ValueSlot::empty());
+
+        if !push && history.len > 0 {
+            history.routes[history.len - 1] = Some(route);
+            history.args[history.len - 1] = args;
+        } else {
+            history.push(route, args);
+        }
+    });
+}
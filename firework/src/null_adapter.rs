@@ -10,7 +10,7 @@ pub fn null_adapter(_cmd: AdapterCommand) -> AdapterResult {
             title: _title,
             width: _width,
             height: _height,
-            listener: _,
+            ..
         } => {
             #[cfg(feature = "detail")]
             println!(
@@ -35,6 +35,48 @@ pub fn null_adapter(_cmd: AdapterCommand) -> AdapterResult {
             AdapterResult::Handle(0)
         }
 
+        AdapterCommand::NewText { layout: _layout } => {
+            #[cfg(feature = "detail")]
+            println!("[NULL_ADAPTER] NewText: layout={:?}", _layout);
+
+            AdapterResult::Handle(0)
+        }
+
+        AdapterCommand::NewImage { layout: _layout } => {
+            #[cfg(feature = "detail")]
+            println!("[NULL_ADAPTER] NewImage: layout={:?}", _layout);
+
+            AdapterResult::Handle(0)
+        }
+
+        AdapterCommand::LoadImage { bytes: _bytes } => {
+            #[cfg(feature = "detail")]
+            println!("[NULL_ADAPTER] LoadImage: {} bytes", _bytes.len());
+
+            AdapterResult::Handle(0)
+        }
+
+        AdapterCommand::LoadImageFromPath { path: _path } => {
+            #[cfg(feature = "detail")]
+            println!("[NULL_ADAPTER] LoadImageFromPath: path='{}'", _path);
+
+            AdapterResult::Handle(0)
+        }
+
+        AdapterCommand::LoadFont { bytes: _bytes } => {
+            #[cfg(feature = "detail")]
+            println!("[NULL_ADAPTER] LoadFont: {} bytes", _bytes.len());
+
+            AdapterResult::Handle(0)
+        }
+
+        AdapterCommand::MeasureText(_id) => {
+            #[cfg(feature = "detail")]
+            println!("[NULL_ADAPTER] MeasureText: id={}", _id);
+
+            AdapterResult::Size(0, 0)
+        }
+
         AdapterCommand::SetPosition(_id, _pos) => {
             #[cfg(feature = "detail")]
             println!(
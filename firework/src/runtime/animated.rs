@@ -0,0 +1,197 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+// Анимированные спарки
+//
+// ```ignore
+// let mut width: f32 = spark!(100.0, animate(300ms, ease_out));
+//
+// button! {
+//     on_click: || width = 300.0,
+// }
+// ```
+//
+// В статике экрана такой спарк хранится как Animated<T>, а пользователь работает с
+// текущим значением T. Присваивание меняет цель анимации, дальше сгенерированный код
+// каждый кадр продвигает значение к цели и помечает бит спарка пока анимация не
+// закончится
+
+/// Функция плавности анимации
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Переводит прогресс от 0 до 1 в прогресс с учётом плавности (кубические кривые)
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Тип который можно интерполировать между двумя значениями. PartialEq нужен чтобы
+/// присваивание той же цели не перезапускало анимацию
+pub trait Animatable: Copy + PartialEq {
+    fn lerp(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+impl Animatable for f64 {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t as f64
+    }
+}
+
+macro_rules! impl_animatable_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Animatable for $ty {
+                fn lerp(from: Self, to: Self, t: f32) -> Self {
+                    let value = from as f64 + (to as f64 - from as f64) * t as f64;
+                    value.round() as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_animatable_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl<A: Animatable, B: Animatable> Animatable for (A, B) {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        (A::lerp(from.0, to.0, t), B::lerp(from.1, to.1, t))
+    }
+}
+
+impl<A: Animatable, B: Animatable, C: Animatable> Animatable for (A, B, C) {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        (
+            A::lerp(from.0, to.0, t),
+            B::lerp(from.1, to.1, t),
+            C::lerp(from.2, to.2, t),
+        )
+    }
+}
+
+impl<A: Animatable, B: Animatable, C: Animatable, D: Animatable> Animatable for (A, B, C, D) {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        (
+            A::lerp(from.0, to.0, t),
+            B::lerp(from.1, to.1, t),
+            C::lerp(from.2, to.2, t),
+            D::lerp(from.3, to.3, t),
+        )
+    }
+}
+
+impl<T: Animatable, const N: usize> Animatable for [T; N] {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        core::array::from_fn(|index| T::lerp(from[index], to[index], t))
+    }
+}
+
+/// Значение которое плавно идёт к цели за duration_ms миллисекунд. Не зависит от
+/// времени само по себе, его продвигает advance
+#[derive(Debug, Clone, Copy)]
+pub struct Animated<T> {
+    from: T,
+    to: T,
+    value: T,
+    elapsed_ms: u32,
+    duration_ms: u32,
+    easing: Easing,
+}
+
+impl<T: Animatable> Animated<T> {
+    /// Неподвижное значение, анимация начнётся при первой смене цели
+    pub const fn new(value: T, duration_ms: u32, easing: Easing) -> Self {
+        Self {
+            from: value,
+            to: value,
+            value,
+            elapsed_ms: duration_ms,
+            duration_ms,
+            easing,
+        }
+    }
+
+    /// Текущее значение
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Значение к которому идёт анимация
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.elapsed_ms < self.duration_ms
+    }
+
+    /// Начинает анимацию от текущего значения к новой цели и возвращает текущее значение.
+    /// При нулевой длительности значение сразу становится целью, та же цель ничего не
+    /// меняет и идущая к ней анимация продолжается
+    pub fn retarget(&mut self, target: T) -> T {
+        if target == self.to {
+            return self.value;
+        }
+
+        self.from = self.value;
+        self.to = target;
+        self.elapsed_ms = 0;
+
+        if self.duration_ms == 0 {
+            self.value = target;
+        }
+
+        self.value
+    }
+
+    /// Продвигает анимацию на dt миллисекунд. Возвращает новое значение пока анимация
+    /// идёт (последний раз ровно цель), после окончания None
+    pub fn advance(&mut self, dt: u32) -> Option<T> {
+        if !self.is_animating() {
+            return None;
+        }
+
+        self.elapsed_ms = self.elapsed_ms.saturating_add(dt).min(self.duration_ms);
+
+        self.value = if self.is_animating() {
+            let progress = self.elapsed_ms as f32 / self.duration_ms as f32;
+            T::lerp(self.from, self.to, self.easing.apply(progress))
+        } else {
+            self.to
+        };
+
+        Some(self.value)
+    }
+
+    /// Используется сгенерированным кодом при возврате спарка в статику
+    pub fn with_value(mut self, value: T) -> Self {
+        self.value = value;
+        self
+    }
+}
@@ -1,24 +1,49 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
-use futures::Stream;
+use futures::channel::mpsc::{self, TryRecvError};
 use std::fmt;
 use std::ops::{Deref, DerefMut};
-use std::pin::Pin;
-use std::task::{Context, Poll};
-use tokio::sync::mpsc;
-use tokio::time::{self, Duration};
 
+use crate::runtime::executor::{self, Spawner, TaskHandle};
+
+/// Задача асинхронного спарка. Задача живёт пока живёт AsyncSpark: при Drop (уход с
+/// экрана с #[ui(drop_on_leave)], сброс состояния) и при повторном create она отменяется.
+/// Задачу запускает установленный Spawner, см. runtime::executor
 pub struct AsyncSpark<T> {
-    stream: Option<Pin<Box<dyn Stream<Item = AsyncStatus<T>> + Unpin + Send>>>,
-    status: Option<AsyncStatus<T>>,
+    receiver: Option<mpsc::UnboundedReceiver<AsyncStatus<T>>>,
+
+    // Последний статус который пришёл из задачи, но ещё не был записан в спарк. Ready
+    // сюда не попадает, его значение лежит в ready, None после Ready значит что последним
+    // пришло именно оно
+    latest: Option<AsyncStatus<T>>,
+
+    // Последнее значение Ready которое ещё не было записано в спарк. Хранится отдельно
+    // так как Loading или Error после него в том же кадре не должны терять значение для
+    // спарка с типом T
+    ready: Option<T>,
+
+    handle: Option<TaskHandle>,
+
+    // Запрос на перезапуск из restart!, задачу заново создаёт сгенерированный код экрана
+    // так как только он знает текущие значения аргументов замыкания
+    restart: bool,
+}
+
+impl<T: Default + Send + 'static> Default for AsyncSpark<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T: Default + Send + 'static> AsyncSpark<T> {
     pub fn new() -> Self {
         AsyncSpark {
-            stream: None,
-            status: None,
+            receiver: None,
+            latest: None,
+            ready: None,
+            handle: None,
+            restart: false,
         }
     }
 
@@ -27,40 +52,65 @@ impl<T: Default + Send + 'static> AsyncSpark<T> {
         F: FnOnce(AsyncController<T>) -> Fut + Send + 'static,
         Fut: std::future::Future<Output = ()> + Send,
     {
-        let (tx, rx) = mpsc::unbounded_channel();
-        let _ = tx.send(AsyncStatus::Loading);
+        let (tx, rx) = mpsc::unbounded();
+        let _ = tx.unbounded_send(AsyncStatus::Loading);
+
+        let controller = AsyncController::new(tx, executor::spawner());
 
-        let controller = AsyncController::new(tx);
+        // Статусы прошлой задачи больше не нужны, её значение устарело
+        self.cancel();
+        self.latest = None;
+        self.ready = None;
 
-        tokio::spawn(async move {
+        self.handle = Some(executor::spawn(async move {
             producer(controller).await;
-        });
+        }));
 
-        self.stream = Some(Box::pin(
-            tokio_stream::wrappers::UnboundedReceiverStream::new(rx),
-        ));
+        self.receiver = Some(rx);
     }
 
-    pub fn poll(&mut self) -> Option<AsyncStatus<T>> {
-        if let Some(status) = self.status.take() {
-            return Some(status);
+    /// Отменяет задачу, статусы которые она успела отправить остаются
+    pub fn cancel(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
         }
+    }
 
-        if let Some(stream) = self.stream.as_mut() {
-            let waker = futures::task::noop_waker();
-            let mut cx = Context::from_waker(&waker);
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
 
-            match stream.as_mut().poll_next(&mut cx) {
-                Poll::Ready(Some(status)) => {
-                    self.status = Some(status);
-                    return self.status.take();
-                }
+    /// Задача ещё идёт или её статусы ещё не записаны в спарк. Компонент опрашивает
+    /// задачу на кадрах и запрашивает их только пока это так
+    pub fn is_pending(&self) -> bool {
+        self.is_running()
+            || self.receiver.is_some()
+            || self.ready.is_some()
+            || self.latest.is_some()
+    }
 
-                Poll::Ready(None) => {
-                    self.stream = None;
-                }
+    /// Просит сгенерированный код заново создать задачу на следующем кадре
+    pub fn request_restart(&mut self) {
+        self.restart = true;
+    }
+
+    /// Забирает запрос на перезапуск
+    pub fn take_restart(&mut self) -> bool {
+        std::mem::take(&mut self.restart)
+    }
 
-                Poll::Pending => {}
+    pub fn poll(&mut self) -> Option<AsyncStatus<T>> {
+        if let Some(receiver) = self.receiver.as_mut() {
+            match receiver.try_recv() {
+                Ok(status) => return Some(status),
+
+                // Задача завершилась и все статусы уже забраны
+                Err(TryRecvError::Closed) => self.receiver = None,
+
+                // Новых статусов нет
+                Err(TryRecvError::Empty) => {}
             }
         }
 
@@ -68,11 +118,117 @@ impl<T: Default + Send + 'static> AsyncSpark<T> {
     }
 }
 
+impl<T: Default + Send + 'static> AsyncSpark<T> {
+    /// Забирает все статусы которые успела отправить задача, вызывается тик функцией
+    /// экрана. Возвращает true если пришёл новый статус или запрошен перезапуск
+    pub fn poll_ready(&mut self) -> bool {
+        let mut is_changed = self.restart;
+
+        while let Some(status) = self.poll() {
+            match status {
+                AsyncStatus::Ready(value) => {
+                    self.ready = Some(value);
+                    self.latest = None;
+                }
+
+                status => self.latest = Some(status),
+            }
+
+            is_changed = true;
+        }
+
+        is_changed
+    }
+
+    /// Значение для записи в спарк с типом T во флэше AsyncReady, загрузка и ошибки
+    /// такому спарку не видны
+    pub fn take_ready(&mut self) -> Option<T> {
+        self.latest = None;
+        self.ready.take()
+    }
+
+    /// Значение для записи в спарк с типом AsyncValue<T>, последний пришедший статус
+    pub fn take_value(&mut self) -> Option<AsyncValue<T>> {
+        let ready = self.ready.take();
+
+        match self.latest.take() {
+            Some(status) => Some(AsyncValue::from(status)),
+            None => ready.map(AsyncValue::Ready),
+        }
+    }
+}
+
+impl<T> Drop for AsyncSpark<T> {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Тип асинхронного спарка который видит не только значение, но и загрузку с ошибкой.
+/// Переключение состояния обновляет спарк как обычное присваивание, поэтому спиннер и
+/// сообщение об ошибке описываются обычными реактивными if
+///
+/// ```ignore
+/// let mut user: AsyncValue<String> = spark!(AsyncValue::Loading, async move |bridge| {
+///     bridge.send(load_user().await);
+/// });
+///
+/// if user.is_loading() {
+///     text! { content: "Загрузка..." }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub enum AsyncValue<T> {
+    #[default]
+    Loading,
+    Ready(T),
+    Error(AsyncError),
+}
+
+impl<T> AsyncValue<T> {
+    pub fn is_loading(&self) -> bool {
+        matches!(self, AsyncValue::Loading)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        matches!(self, AsyncValue::Ready(_))
+    }
+
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            AsyncValue::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&AsyncError> {
+        match self {
+            AsyncValue::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<AsyncStatus<T>> for AsyncValue<T> {
+    fn from(status: AsyncStatus<T>) -> Self {
+        match status {
+            AsyncStatus::Loading => AsyncValue::Loading,
+            AsyncStatus::Ready(value) => AsyncValue::Ready(value),
+            AsyncStatus::Error(error) => AsyncValue::Error(error),
+        }
+    }
+}
+
 /// A middleware for managing asynchronous state from an asynchronous closure. It is needed to
 /// avoid tying the user to a single specific runtime
 pub struct AsyncController<T: Default> {
     tx: mpsc::UnboundedSender<AsyncStatus<T>>,
 
+    // Исполнитель который запустил задачу, от него берётся таймер для sleep
+    spawner: &'static dyn Spawner,
+
     // Изначально это значение дефолтное, но потом оно используется как прокси для того чтобы
     // при следующем DerefMut или Drop забрать это значение, сделать отправку и уже вернуть
     // мутабельную ссылку снова на дефолтное значение
@@ -84,34 +240,37 @@ pub struct AsyncController<T: Default> {
 }
 
 impl<T: Default> AsyncController<T> {
-    pub fn new(tx: mpsc::UnboundedSender<AsyncStatus<T>>) -> Self {
+    pub fn new(tx: mpsc::UnboundedSender<AsyncStatus<T>>, spawner: &'static dyn Spawner) -> Self {
         Self {
             tx,
+            spawner,
             value: T::default(),
             is_first: true,
         }
     }
 
     pub fn send(&self, value: T) {
-        let _ = self.tx.send(AsyncStatus::Ready(value));
+        let _ = self.tx.unbounded_send(AsyncStatus::Ready(value));
     }
 
     pub fn error(&self) {
-        let _ = self.tx.send(AsyncStatus::Error(AsyncError::new()));
+        let _ = self
+            .tx
+            .unbounded_send(AsyncStatus::Error(AsyncError::new()));
     }
 
     pub fn error_message(&self, msg: impl Into<String>) {
         let _ = self
             .tx
-            .send(AsyncStatus::Error(AsyncError::with_message(msg)));
+            .unbounded_send(AsyncStatus::Error(AsyncError::with_message(msg)));
     }
 
     pub async fn sleep_s(&self, secs: u64) {
-        time::sleep(Duration::from_secs(secs)).await;
+        self.spawner.sleep(secs.saturating_mul(1000)).await;
     }
 
     pub async fn sleep_ms(&self, ms: u64) {
-        time::sleep(Duration::from_millis(ms)).await;
+        self.spawner.sleep(ms).await;
     }
 }
 
@@ -130,7 +289,7 @@ impl<T: Default> DerefMut for AsyncController<T> {
         // канал
         if !self.is_first {
             let old = std::mem::take(&mut self.value);
-            let _ = self.tx.send(AsyncStatus::Ready(old));
+            let _ = self.tx.unbounded_send(AsyncStatus::Ready(old));
         }
 
         self.is_first = false;
@@ -146,10 +305,10 @@ impl<T: Default> Drop for AsyncController<T> {
         //  - DerefMut, 10 отправляется через канал, теперь тут 20
         //  - Drop, всё ещё 20 которая не была отправлена, но благодаря этой логике тут
         //    отправка произойдёт если был deref_mut
-        if self.is_first {
+        if !self.is_first {
             let _ = self
                 .tx
-                .send(AsyncStatus::Ready(std::mem::take(&mut self.value)));
+                .unbounded_send(AsyncStatus::Ready(std::mem::take(&mut self.value)));
         }
     }
 }
@@ -160,10 +319,17 @@ pub enum AsyncStatus<T> {
     Loading,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct AsyncError {
     message: Option<String>,
 }
 
+impl Default for AsyncError {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AsyncError {
     pub fn new() -> Self {
         AsyncError { message: None }
@@ -174,6 +340,10 @@ impl AsyncError {
             message: Some(msg.into()),
         }
     }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
 }
 
 impl fmt::Display for AsyncError {
@@ -3,65 +3,361 @@
 
 use core::mem;
 
+#[cfg(not(feature = "no-alloc"))]
+use std::collections::HashMap;
+#[cfg(not(feature = "no-alloc"))]
+use std::hash::Hash;
+
+#[cfg(feature = "safety-multithread")]
+use std::sync::Mutex;
+
 use crate::std_widgets::widget::Widget;
 
+/// Ёмкость списка цикла без атрибута #[capacity(N)]
+pub const DEFAULT_LIST_CAPACITY: usize = 64;
+
+/// Что делает список в режиме no-alloc когда в проходе элементов больше чем его ёмкость
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListOverflow {
+    /// Паника с подсказкой увеличить #[capacity(N)]
+    #[default]
+    Panic,
+
+    /// Размонтирует самый ранний элемент прохода, список показывает последние N элементов
+    DropOldest,
+
+    /// Новый элемент не попадает в список и размонтируется в end_pass. Он создаётся
+    /// заново на каждом проходе, поэтому политика подходит для редкого переполнения
+    Ignore,
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut LIST_OVERFLOW: ListOverflow = ListOverflow::Panic;
+
+#[cfg(feature = "safety-multithread")]
+static LIST_OVERFLOW: Mutex<ListOverflow> = Mutex::new(ListOverflow::Panic);
+
+/// Устанавливает политику переполнения для всех списков циклов
+#[cfg(not(feature = "safety-multithread"))]
+pub fn set_list_overflow(policy: ListOverflow) {
+    unsafe {
+        *::core::ptr::addr_of_mut!(LIST_OVERFLOW) = policy;
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn set_list_overflow(policy: ListOverflow) {
+    *LIST_OVERFLOW.lock().unwrap() = policy;
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+pub fn list_overflow() -> ListOverflow {
+    unsafe { *::core::ptr::addr_of!(LIST_OVERFLOW) }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn list_overflow() -> ListOverflow {
+    *LIST_OVERFLOW.lock().unwrap()
+}
+
+/// Ключ элемента списка. С кучей ключи хэшируются чтобы поиск в прошлом кадре был
+/// за O(1), в режиме no-alloc поиск линейный и достаточно сравнения
+#[cfg(not(feature = "no-alloc"))]
+pub trait ListKey: Eq + Hash {}
+
+#[cfg(not(feature = "no-alloc"))]
+impl<K: Eq + Hash> ListKey for K {}
+
+#[cfg(feature = "no-alloc")]
+pub trait ListKey: Eq {}
+
+#[cfg(feature = "no-alloc")]
+impl<K: Eq> ListKey for K {}
+
 /// Результат поиска элемента в списке
-pub enum ListEntry<'a, K, T> {
+pub enum ListEntry<'a, K, T, const N: usize = DEFAULT_LIST_CAPACITY> {
     /// Элемент найден в старом кадре и перенесён в текущий
     Occupied(&'a mut T),
 
     /// Элемента с таким ключом нет и нужно вставить новый
-    Vacant(VacantEntry<'a, K, T>),
+    Vacant(VacantEntry<'a, K, T, N>),
 }
 
 /// Структура для вставки нового элемента
-pub struct VacantEntry<'a, K, T> {
-    list: &'a mut DynList<K, T>,
+pub struct VacantEntry<'a, K, T, const N: usize = DEFAULT_LIST_CAPACITY> {
+    list: &'a mut DynList<K, T, N>,
     key: K,
 }
 
-impl<'a, K: Eq + PartialEq, T: Widget> VacantEntry<'a, K, T> {
+impl<'a, K: ListKey, T: Widget, const N: usize> VacantEntry<'a, K, T, N> {
     /// Вставляет созданный виджет в список и возвращает мутабельную ссылку на него
     pub fn insert(self, value: T) -> &'a mut T {
         self.list.insert_at_current(self.key, value)
     }
+
+    /// Забирает скин из пула списка с переиспользованием (см. DynList::with_recycling)
+    /// и привязывает его к ключу. Пропсы у скина остались от прошлого ключа, их
+    /// выставляет вызывающий. Если пул пуст, возвращает запись обратно для insert
+    pub fn recycle(self) -> Result<&'a mut T, Self> {
+        match self.list.take_free() {
+            Some((item, order)) => Ok(self.list.insert_recycled(self.key, item, order)),
+            None => Err(self),
+        }
+    }
+}
+
+// Позиция которую элементу последней выставила компоновка
+type Placed = Option<(i32, i32)>;
+
+/// Шаг Z между соседними элементами списка, промежуток нужен чтобы переставленный
+/// элемент вставал между соседями сменой только своего Z
+pub const LIST_Z_STEP: i32 = 16;
+
+/// Порядок отрисовки элемента: адаптер рисует по Z, а при равном Z по порядку создания,
+/// который повторяет seq
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct ZOrder {
+    z: i32,
+    seq: u32,
+}
+
+/// Элемент текущего прохода
+struct Slot<K, T> {
+    key: K,
+    item: T,
+
+    // Индекс элемента в прошлом проходе, None у вставленных в этом проходе
+    previous: Option<usize>,
+
+    placed: Placed,
+
+    // Элемент переставлен относительно остальных
+    moved: bool,
+
+    // Строка lazy_list к которой относится элемент
+    row: usize,
+
+    order: ZOrder,
+
+    // Порядок ближайшего следующего элемента который остался на месте, считается в
+    // order_z
+    upper: Option<ZOrder>,
+}
+
+impl<K, T> Slot<K, T> {
+    fn new(key: K, item: T, row: usize, seq: u32) -> Self {
+        Self {
+            key,
+            item,
+            previous: None,
+            placed: None,
+            moved: false,
+            row,
+            order: ZOrder { z: 0, seq },
+            upper: None,
+        }
+    }
+
+    // Элемент сохранился с прошлого прохода и остался на месте
+    fn is_anchor(&self) -> bool {
+        self.previous.is_some() && !self.moved
+    }
+}
+
+/// Первый шаг order_z, слоты идут с конца: каждый слот берёт Z своего скина (его мог
+/// поменять пропс z) и запоминает порядок ближайшего следующего элемента который
+/// остался на месте
+fn find_upper<'a, K: 'a, T: Widget + 'a>(reversed: impl Iterator<Item = &'a mut Slot<K, T>>) {
+    let mut upper = None;
+
+    for slot in reversed {
+        slot.order.z = slot.item.current_z();
+        slot.upper = upper;
+
+        if slot.is_anchor() {
+            upper = Some(slot.order);
+        }
+    }
+}
+
+/// Выставляет Z элементам цикла в stack! так чтобы порядок отрисовки совпадал с порядком
+/// списка. Оставшиеся на месте элементы сохраняют свой Z, а переставленные и вставленные
+/// в середину встают между соседями, поэтому перестановка стоит по одному SetZ на
+/// перемещённый элемент. Элемент который и так рисуется между соседями (например новый
+/// в конце списка) Z не получает. Оставшийся на месте элемент меняет Z только если его
+/// перекрыл сосед снизу, получивший Z в этом же вызове. Перед вызовом слоты проходят
+/// find_upper
+fn order_z<'a, K: 'a, T: Widget + 'a>(slots: impl Iterator<Item = &'a mut Slot<K, T>>) {
+    let mut lower: Option<ZOrder> = None;
+
+    // Сосед снизу получил новый Z
+    let mut pushed = false;
+
+    for slot in slots {
+        let fits = |order: ZOrder| lower.is_none_or(|lower| order > lower);
+
+        // Сосед сверху мог оказаться ниже если ему самому пришлось сменить Z
+        let upper = slot.upper.filter(|&upper| fits(upper));
+
+        let reorder = if slot.is_anchor() {
+            pushed && !fits(slot.order)
+        } else {
+            !(fits(slot.order) && upper.is_none_or(|upper| slot.order < upper))
+        };
+
+        pushed = false;
+
+        if reorder {
+            let z = place_z(lower, upper, slot.order.seq);
+
+            if z != slot.order.z {
+                slot.order.z = z;
+                slot.item.set_z(z);
+                pushed = true;
+            }
+        }
+
+        lower = Some(slot.order);
+    }
+}
+
+/// Z для элемента с порядком создания seq между lower и upper
+fn place_z(lower: Option<ZOrder>, upper: Option<ZOrder>, seq: u32) -> i32 {
+    match (lower, upper) {
+        (None, None) => 0,
+        (Some(lower), None) => lower.z.saturating_add(LIST_Z_STEP),
+        (None, Some(upper)) => upper.z.saturating_sub(LIST_Z_STEP),
+
+        (Some(lower), Some(upper)) => {
+            let middle = ((lower.z as i64 + upper.z as i64) / 2) as i32;
+
+            // Середина промежутка, а если его нет то Z одного из соседей когда порядок
+            // создания ставит элемент между ними. Иначе элемент встаёт выше, а соседи
+            // сверху получат новый Z следом за ним
+            [middle, lower.z, upper.z]
+                .into_iter()
+                .find(|&z| lower < ZOrder { z, seq } && ZOrder { z, seq } < upper)
+                .unwrap_or(lower.z.saturating_add(LIST_Z_STEP))
+        }
+    }
 }
 
+/// Список виджетов цикла с ключами. Каждый проход цикла (флэш) обходит элементы заново:
+/// begin_pass переносит элементы прошлого прохода в старые, entry забирает элемент по
+/// ключу из старых или просит создать новый, end_pass размонтирует элементы которые не
+/// были запрошены. Порядок элементов это порядок вызовов entry
+///
+/// Старые элементы лежат в слотах, а ключи в хэш таблице с индексом слота, поэтому
+/// весь проход занимает O(n), память таблицы и векторов переиспользуется между проходами
+///
+/// В end_pass список находит переставленные элементы: среди сохранившихся элементов
+/// самая длинная подпоследовательность с возрастающими прошлыми индексами остаётся на
+/// месте, а остальные считаются перемещёнными. Список помнит позицию каждого элемента и
+/// компоновка размещает элемент только если его место изменилось, поэтому перестановка
+/// двух строк отсортированного списка стоит двух SetPosition, а не по одной на каждую
+/// строку. Элементы которые перекрываются так же получают новый Z через order_z
+///
+/// Список с переиспользованием (lazy_list) не размонтирует ушедшие элементы, а прячет
+/// их в пул, и новый ключ получает скин из пула через VacantEntry::recycle. Так прокрутка
+/// длинного списка меняет пропсы у нескольких скинов вместо создания и удаления строк
+///
+/// N это ёмкость списка в режиме no-alloc (#[capacity(N)] у виджета цикла), с кучей
+/// список растёт без ограничений и N не используется
 #[cfg(not(feature = "no-alloc"))]
-pub struct DynList<K, T> {
-    current_items: Vec<(K, T)>,
-    old_items: Vec<(K, T)>,
+pub struct DynList<K, T, const N: usize = DEFAULT_LIST_CAPACITY> {
+    current_items: Vec<Slot<K, T>>,
+    old_items: Vec<Option<(T, Placed, ZOrder)>>,
+    old_index: HashMap<K, usize>,
+    changed: bool,
+
+    // Сколько строк lazy_list прошёл текущий проход, включая строки вне окна
+    rows: usize,
+
+    // Начало lazy_list в содержимом scroll по последней компоновке
+    top: i32,
+
+    // Порядок создания следующего нового элемента
+    seq: u32,
+
+    // Спрятанные скины которые ждут нового ключа, Z остаётся с тем что видит адаптер
+    recycling: bool,
+    free: Vec<(T, ZOrder)>,
+
+    reorder: ReorderBuffers,
 }
 
+/// Буферы поиска возрастающей подпоследовательности в end_pass, очищаются в начале
+/// каждого прохода и сохраняют ёмкость
 #[cfg(not(feature = "no-alloc"))]
-impl<K: Eq + PartialEq, T: Widget> Default for DynList<K, T> {
+#[derive(Default)]
+struct ReorderBuffers {
+    retained: Vec<usize>,
+    previous: Vec<usize>,
+    tails: Vec<usize>,
+    parent: Vec<usize>,
+    keep: Vec<bool>,
+}
+
+impl<K: ListKey, T: Widget, const N: usize> Default for DynList<K, T, N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<K: Eq + PartialEq, T: Widget> DynList<K, T> {
+#[cfg(not(feature = "no-alloc"))]
+impl<K: ListKey, T: Widget, const N: usize> DynList<K, T, N> {
     pub fn new() -> Self {
         Self {
             current_items: Vec::new(),
             old_items: Vec::new(),
+            old_index: HashMap::new(),
+            changed: false,
+            rows: 0,
+            top: 0,
+            seq: 0,
+            recycling: false,
+            free: Vec::new(),
+            reorder: ReorderBuffers::default(),
         }
     }
 
+    /// Список который прячет ушедшие элементы в пул вместо размонтирования
+    pub fn with_recycling(mut self) -> Self {
+        self.recycling = true;
+        self
+    }
+
     /// Вызывается до цикла
     pub fn begin_pass(&mut self) {
-        mem::swap(&mut self.current_items, &mut self.old_items);
-        self.current_items.clear();
+        // Элементы которые не забрал прошлый проход без end_pass не должны потеряться
+        self.unmount_old();
+        self.rows = 0;
+
+        for slot in self.current_items.drain(..) {
+            // При повторяющемся ключе прошлый элемент с этим ключом остаётся без
+            // индекса и будет размонтирован в end_pass
+            self.old_index.insert(slot.key, self.old_items.len());
+            self.old_items
+                .push(Some((slot.item, slot.placed, slot.order)));
+        }
     }
 
     /// Поиск элемента по ключу
-    pub fn entry(&mut self, key: K) -> ListEntry<'_, K, T> {
-        let found_idx = self.old_items.iter().position(|(k, _)| k == &key);
+    pub fn entry(&mut self, key: K) -> ListEntry<'_, K, T, N> {
+        let found = self
+            .old_index
+            .remove_entry(&key)
+            .and_then(|(key, idx)| Some((key, idx, self.old_items[idx].take()?)));
 
-        if let Some(idx) = found_idx {
-            let item = self.old_items.swap_remove(idx);
-            self.current_items.push(item);
-            ListEntry::Occupied(&mut self.current_items.last_mut().unwrap().1)
+        if let Some((key, idx, (item, placed, order))) = found {
+            self.current_items.push(Slot {
+                previous: Some(idx),
+                placed,
+                order,
+                ..Slot::new(key, item, self.current_row(), order.seq)
+            });
+
+            ListEntry::Occupied(&mut self.current_items.last_mut().unwrap().item)
         } else {
             ListEntry::Vacant(VacantEntry { list: self, key })
         }
@@ -69,95 +365,731 @@ impl<K: Eq + PartialEq, T: Widget> DynList<K, T> {
 
     /// Вспомогательный метод для VacantEntry
     fn insert_at_current(&mut self, key: K, value: T) -> &mut T {
-        self.current_items.push((key, value));
-        &mut self.current_items.last_mut().unwrap().1
+        self.changed = true;
+        self.seq = self.seq.wrapping_add(1);
+        self.current_items
+            .push(Slot::new(key, value, self.current_row(), self.seq));
+        &mut self.current_items.last_mut().unwrap().item
+    }
+
+    fn take_free(&mut self) -> Option<(T, ZOrder)> {
+        self.free.pop()
+    }
+
+    fn insert_recycled(&mut self, key: K, item: T, order: ZOrder) -> &mut T {
+        self.changed = true;
+        item.visible(true);
+        self.current_items.push(Slot {
+            order,
+            ..Slot::new(key, item, self.current_row(), order.seq)
+        });
+        &mut self.current_items.last_mut().unwrap().item
     }
 
-    /// Завершение прохода, убивает элементы которых больше нет в коде
+    /// Завершение прохода, убивает элементы которых больше нет в коде и отмечает
+    /// переставленные
     pub fn end_pass(&mut self) {
-        for (_, item) in self.old_items.drain(..) {
-            item.unmount();
+        if self.unmount_old() {
+            self.changed = true;
+        }
+
+        let reorder = &mut self.reorder;
+        reorder.retained.clear();
+        reorder.previous.clear();
+
+        for (idx, slot) in self.current_items.iter().enumerate() {
+            if let Some(previous) = slot.previous {
+                reorder.retained.push(idx);
+                reorder.previous.push(previous);
+            }
+        }
+
+        let len = reorder.previous.len();
+        reorder.tails.resize(len, 0);
+        reorder.parent.resize(len, 0);
+        reorder.keep.resize(len, false);
+        longest_increasing(
+            &reorder.previous,
+            &mut reorder.tails,
+            &mut reorder.parent,
+            &mut reorder.keep,
+        );
+
+        for (&idx, &keep) in reorder.retained.iter().zip(&reorder.keep) {
+            self.current_items[idx].moved = !keep;
+            self.changed |= !keep;
         }
     }
 
+    fn unmount_old(&mut self) -> bool {
+        self.old_index.clear();
+
+        let mut removed = false;
+
+        for (item, _, order) in self.old_items.drain(..).flatten() {
+            if self.recycling {
+                item.visible(false);
+                self.free.push((item, order));
+            } else {
+                item.unmount();
+            }
+
+            removed = true;
+        }
+
+        removed
+    }
+
     /// Групповое управление видимостью для финализатора экрана
     pub fn visible(&self, state: bool) {
-        for (_, item) in &self.current_items {
-            item.visible(state);
+        for slot in &self.current_items {
+            slot.item.visible(state);
+        }
+    }
+
+    /// Элементы текущего кадра в порядке декларации, используется лайаутом
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.current_items.iter_mut().map(|slot| &mut slot.item)
+    }
+
+    /// Элементы вместе с позицией которую им последней выставила компоновка
+    pub fn iter_placed_mut(&mut self) -> impl Iterator<Item = (&mut T, &mut Placed)> {
+        self.current_items
+            .iter_mut()
+            .map(|slot| (&mut slot.item, &mut slot.placed))
+    }
+
+    /// Следующая строка lazy_list, вызывается сгенерированным кодом на каждой итерации
+    /// цикла до проверки окна
+    pub fn next_row(&mut self) -> usize {
+        self.rows += 1;
+        self.rows - 1
+    }
+
+    /// Количество строк которые прошёл последний проход lazy_list
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Начало lazy_list в содержимом scroll, по нему окно строк переводится в координаты
+    /// списка. Выставляется компоновкой
+    pub fn top(&self) -> i32 {
+        self.top
+    }
+
+    pub fn set_top(&mut self, top: i32) {
+        self.top = top;
+    }
+
+    // Строка для элемента который сейчас добавляется в проход
+    fn current_row(&self) -> usize {
+        self.rows.saturating_sub(1)
+    }
+
+    /// Элементы вместе с позицией от компоновки и строкой lazy_list
+    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = (&mut T, &mut Placed, usize)> {
+        self.current_items
+            .iter_mut()
+            .map(|slot| (&mut slot.item, &mut slot.placed, slot.row))
+    }
+
+    /// Выставляет Z переставленным элементам, вызывается после end_pass для циклов в
+    /// stack!, где элементы перекрываются и порядок отрисовки зависит от Z. Новый Z
+    /// считается от Z соседей, поэтому заданный пропсом z у остальных элементов остаётся
+    pub fn order_z(&mut self) {
+        find_upper(self.current_items.iter_mut().rev());
+        order_z(self.current_items.iter_mut());
+    }
+
+    /// Изменился ли состав или порядок элементов с прошлой проверки. Используется
+    /// компоновкой чтобы разместить список заново, флаг сбрасывается
+    pub fn take_changed(&mut self) -> bool {
+        mem::take(&mut self.changed)
+    }
+
+    /// Widget::release для всех элементов, вызывается при сбросе списка экраном
+    pub fn release(&mut self) {
+        for slot in &mut self.current_items {
+            slot.item.release();
+        }
+
+        for (item, ..) in self.old_items.iter_mut().flatten() {
+            item.release();
+        }
+
+        for (item, _) in &mut self.free {
+            item.release();
         }
     }
 }
 
+/// Отмечает в keep элементы самой длинной возрастающей подпоследовательности seq за
+/// O(n log n). Буферы tails, parent и keep должны быть не короче seq
+fn longest_increasing(seq: &[usize], tails: &mut [usize], parent: &mut [usize], keep: &mut [bool]) {
+    // tails[k] это индекс последнего элемента в лучшей подпоследовательности длины k + 1
+    let mut len = 0;
+
+    for (idx, &value) in seq.iter().enumerate() {
+        let pos = tails[..len].partition_point(|&tail| seq[tail] < value);
+
+        parent[idx] = if pos > 0 { tails[pos - 1] } else { usize::MAX };
+        tails[pos] = idx;
+        keep[idx] = false;
+
+        if pos == len {
+            len += 1;
+        }
+    }
+
+    if len == 0 {
+        return;
+    }
+
+    let mut idx = tails[len - 1];
+
+    loop {
+        keep[idx] = true;
+
+        if parent[idx] == usize::MAX {
+            break;
+        }
+
+        idx = parent[idx];
+    }
+}
+
+/// Буферы поиска возрастающей подпоследовательности в end_pass. Хранятся в списке (то
+/// есть в статике экрана), а не на стеке, так как при большом #[capacity(N)] четыре
+/// массива длины N могут не поместиться в стек встраиваемой платформы
+#[cfg(feature = "no-alloc")]
+struct ReorderBuffers<const N: usize> {
+    previous: [usize; N],
+    tails: [usize; N],
+    parent: [usize; N],
+    keep: [bool; N],
+}
+
 #[cfg(feature = "no-alloc")]
-pub struct DynList<K, T> {
-    current_items: [Option<(K, T)>; 64],
-    old_items: [Option<(K, T)>; 64],
+pub struct DynList<K, T, const N: usize = DEFAULT_LIST_CAPACITY> {
+    current_items: [Option<Slot<K, T>>; N],
+    old_items: [Option<Slot<K, T>>; N],
     current_count: usize,
+    changed: bool,
+
+    // Элемент отброшенный политикой Ignore, живёт до end_pass
+    overflow: Option<T>,
+
+    rows: usize,
+    top: i32,
+    seq: u32,
+    reorder: ReorderBuffers<N>,
+
+    recycling: bool,
+    free: [Option<(T, ZOrder)>; N],
 }
 
 #[cfg(feature = "no-alloc")]
-impl<K: Eq + PartialEq, T: Widget> DynList<K, T> {
+impl<K: ListKey, T: Widget, const N: usize> DynList<K, T, N> {
     pub fn new() -> Self {
         Self {
-            current_items: [const { None }; 64],
-            old_items: [const { None }; 64],
+            current_items: [const { None }; N],
+            old_items: [const { None }; N],
             current_count: 0,
+            changed: false,
+            overflow: None,
+            rows: 0,
+            top: 0,
+            seq: 0,
+            reorder: ReorderBuffers {
+                previous: [0; N],
+                tails: [0; N],
+                parent: [0; N],
+                keep: [false; N],
+            },
+            recycling: false,
+            free: [const { None }; N],
         }
     }
 
+    pub fn with_recycling(mut self) -> Self {
+        self.recycling = true;
+        self
+    }
+
     pub fn begin_pass(&mut self) {
         mem::swap(&mut self.current_items, &mut self.old_items);
         self.current_count = 0;
+        self.rows = 0;
     }
 
-    pub fn entry(&mut self, key: K) -> ListEntry<'_, K, T> {
-        let mut found_idx = None;
-        for i in 0..64 {
-            if let Some((ref k, _)) = self.old_items[i] {
-                if k == &key {
-                    found_idx = Some(i);
-                    break;
-                }
-            }
-        }
+    pub fn entry(&mut self, key: K) -> ListEntry<'_, K, T, N> {
+        // При политике Ignore заполненный список не принимает и существующие элементы,
+        // они уходят в insert как новые, а старые размонтируются в end_pass
+        let ignore = self.current_count == N && list_overflow() == ListOverflow::Ignore;
+
+        let found_idx = self
+            .old_items
+            .iter()
+            .position(|slot| slot.as_ref().is_some_and(|slot| slot.key == key))
+            .filter(|_| !ignore);
 
         if let Some(idx) = found_idx {
-            let item = self.old_items[idx].take().unwrap();
-            let pos = self.current_count;
-            self.current_items[pos] = Some(item);
-            self.current_count += 1;
-            ListEntry::Occupied(&mut self.current_items[pos].as_mut().unwrap().1)
+            let mut slot = self.old_items[idx].take().unwrap();
+            slot.previous = Some(idx);
+            slot.row = self.current_row();
+
+            let pos = self.reserve();
+            self.current_items[pos] = Some(slot);
+            ListEntry::Occupied(&mut self.current_items[pos].as_mut().unwrap().item)
         } else {
             ListEntry::Vacant(VacantEntry { list: self, key })
         }
     }
 
+    /// Индекс для следующего элемента прохода, при переполнении применяет политику
+    fn reserve(&mut self) -> usize {
+        if self.current_count < N {
+            self.current_count += 1;
+            return self.current_count - 1;
+        }
+
+        match list_overflow() {
+            ListOverflow::Panic => panic!(
+                "DynList capacity {} is exceeded, increase #[capacity(N)] or disable no-alloc mode",
+                N
+            ),
+
+            // Самый ранний элемент уходит, остальные сдвигаются к началу
+            ListOverflow::DropOldest => {
+                self.current_items.rotate_left(1);
+
+                if let Some(slot) = self.current_items[N - 1].take() {
+                    slot.item.unmount();
+                }
+
+                self.changed = true;
+                N - 1
+            }
+
+            ListOverflow::Ignore => unreachable!("Ignore policy does not reserve slots"),
+        }
+    }
+
     fn insert_at_current(&mut self, key: K, value: T) -> &mut T {
-        let pos = self.current_count;
+        self.changed = true;
+
+        if self.current_count == N && list_overflow() == ListOverflow::Ignore {
+            value.visible(false);
+
+            if let Some(previous) = self.overflow.replace(value) {
+                previous.unmount();
+            }
+
+            return self.overflow.as_mut().unwrap();
+        }
+
+        let pos = self.reserve();
+        self.seq = self.seq.wrapping_add(1);
+        self.current_items[pos] = Some(Slot::new(key, value, self.current_row(), self.seq));
+        &mut self.current_items[pos].as_mut().unwrap().item
+    }
+
+    // Заполненный список с политикой Ignore не берёт скин из пула, новый элемент уходит
+    // в insert
+    fn take_free(&mut self) -> Option<(T, ZOrder)> {
+        if self.current_count == N && list_overflow() == ListOverflow::Ignore {
+            return None;
+        }
 
-        // На 16 битном устройстве (embedded) 16 * 2 = 32 байта, это нормально
-        if pos >= 16 {
-            panic!("DynList is overflow, disable no-alloc mode");
+        self.free.iter_mut().find_map(Option::take)
+    }
+
+    fn insert_recycled(&mut self, key: K, item: T, order: ZOrder) -> &mut T {
+        self.changed = true;
+        item.visible(true);
+
+        let pos = self.reserve();
+        self.current_items[pos] = Some(Slot {
+            order,
+            ..Slot::new(key, item, self.current_row(), order.seq)
+        });
+        &mut self.current_items[pos].as_mut().unwrap().item
+    }
+
+    // Прячет элемент в пул, а если пул полон то размонтирует
+    fn release_to_free(&mut self, item: T, order: ZOrder) {
+        if !self.recycling {
+            item.unmount();
+            return;
         }
 
-        self.current_items[pos] = Some((key, value));
-        self.current_count += 1;
-        &mut self.current_items[pos].as_mut().unwrap().1
+        match self.free.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                item.visible(false);
+                *slot = Some((item, order));
+            }
+
+            None => item.unmount(),
+        }
     }
 
     pub fn end_pass(&mut self) {
-        for i in 0..16 {
-            if let Some(item) = self.old_items[i].take() {
-                item.unmount();
+        if let Some(item) = self.overflow.take() {
+            item.unmount();
+        }
+
+        for idx in 0..N {
+            if let Some(slot) = self.old_items[idx].take() {
+                self.release_to_free(slot.item, slot.order);
+                self.changed = true;
             }
         }
+
+        let reorder = &mut self.reorder;
+        let mut len = 0;
+
+        for slot in self.current_items[..self.current_count].iter().flatten() {
+            if let Some(idx) = slot.previous {
+                reorder.previous[len] = idx;
+                len += 1;
+            }
+        }
+
+        longest_increasing(
+            &reorder.previous[..len],
+            &mut reorder.tails,
+            &mut reorder.parent,
+            &mut reorder.keep,
+        );
+
+        let retained = self.current_items[..self.current_count]
+            .iter_mut()
+            .flatten()
+            .filter(|slot| slot.previous.is_some());
+
+        for (slot, &keep) in retained.zip(&reorder.keep) {
+            slot.moved = !keep;
+            self.changed |= !keep;
+        }
     }
 
     pub fn visible(&self, state: bool) {
-        for i in 0..self.current_count {
-            if let Some((_, item)) = &self.current_items[i] {
-                item.visible(state);
+        for slot in self.current_items[..self.current_count].iter().flatten() {
+            slot.item.visible(state);
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.current_items[..self.current_count]
+            .iter_mut()
+            .flatten()
+            .map(|slot| &mut slot.item)
+    }
+
+    pub fn iter_placed_mut(&mut self) -> impl Iterator<Item = (&mut T, &mut Placed)> {
+        self.current_items[..self.current_count]
+            .iter_mut()
+            .flatten()
+            .map(|slot| (&mut slot.item, &mut slot.placed))
+    }
+
+    pub fn next_row(&mut self) -> usize {
+        self.rows += 1;
+        self.rows - 1
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn current_row(&self) -> usize {
+        self.rows.saturating_sub(1)
+    }
+
+    pub fn top(&self) -> i32 {
+        self.top
+    }
+
+    pub fn set_top(&mut self, top: i32) {
+        self.top = top;
+    }
+
+    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = (&mut T, &mut Placed, usize)> {
+        self.current_items[..self.current_count]
+            .iter_mut()
+            .flatten()
+            .map(|slot| (&mut slot.item, &mut slot.placed, slot.row))
+    }
+
+    pub fn order_z(&mut self) {
+        find_upper(
+            self.current_items[..self.current_count]
+                .iter_mut()
+                .rev()
+                .flatten(),
+        );
+        order_z(
+            self.current_items[..self.current_count]
+                .iter_mut()
+                .flatten(),
+        );
+    }
+
+    pub fn take_changed(&mut self) -> bool {
+        mem::take(&mut self.changed)
+    }
+
+    pub fn release(&mut self) {
+        let slots = self
+            .current_items
+            .iter_mut()
+            .chain(self.old_items.iter_mut());
+
+        for slot in slots.flatten() {
+            slot.item.release();
+        }
+
+        if let Some(item) = self.overflow.as_mut() {
+            item.release();
+        }
+
+        for (item, _) in self.free.iter_mut().flatten() {
+            item.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Constraints, Size};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    // Порядок создания элементов, при равном Z адаптер рисует их по нему
+    static CREATED: AtomicU32 = AtomicU32::new(0);
+
+    // Виджет который записывает свой id при размонтировании и считает смены Z
+    struct Item {
+        id: u32,
+        unmounted: Rc<RefCell<Vec<u32>>>,
+        created: u32,
+        z: i32,
+        z_updates: usize,
+    }
+
+    impl Widget for Item {
+        fn position(&mut self, _position: (i32, i32)) {}
+        fn visible(&self, _state: bool) {}
+
+        fn set_z(&mut self, z: i32) {
+            self.z = z;
+            self.z_updates += 1;
+        }
+
+        fn current_z(&self) -> i32 {
+            self.z
+        }
+
+        fn unmount(self) {
+            self.unmounted.borrow_mut().push(self.id);
+        }
+
+        fn layout(&mut self, _constraints: Constraints) -> Size {
+            Size {
+                width: 0,
+                height: 0,
             }
         }
     }
+
+    fn pass<const N: usize>(
+        list: &mut DynList<u32, Item, N>,
+        keys: &[u32],
+        unmounted: &Rc<RefCell<Vec<u32>>>,
+    ) {
+        list.begin_pass();
+
+        for &key in keys {
+            if let ListEntry::Vacant(entry) = list.entry(key) {
+                entry.insert(Item {
+                    id: key,
+                    unmounted: unmounted.clone(),
+                    created: CREATED.fetch_add(1, Ordering::Relaxed),
+                    z: 0,
+                    z_updates: 0,
+                });
+            }
+        }
+
+        list.end_pass();
+    }
+
+    fn ids<const N: usize>(list: &mut DynList<u32, Item, N>) -> Vec<u32> {
+        list.iter_mut().map(|item| item.id).collect()
+    }
+
+    #[test]
+    fn test_dyn_list_reconcile() {
+        let unmounted = Rc::new(RefCell::new(Vec::new()));
+        let mut list: DynList<u32, Item> = DynList::new();
+
+        pass(&mut list, &[1, 2, 3, 4], &unmounted);
+        assert_eq!(ids(&mut list), [1, 2, 3, 4]);
+
+        // Порядок берётся из прохода, удалённые ключи размонтируются
+        pass(&mut list, &[4, 2, 5], &unmounted);
+        assert_eq!(ids(&mut list), [4, 2, 5]);
+
+        let mut removed = unmounted.borrow().clone();
+        removed.sort();
+        assert_eq!(removed, [1, 3]);
+    }
+
+    #[cfg(not(feature = "no-alloc"))]
+    #[test]
+    fn test_dyn_list_large_pass() {
+        let unmounted = Rc::new(RefCell::new(Vec::new()));
+        let mut list: DynList<u32, Item> = DynList::new();
+
+        let keys: Vec<u32> = (0..2000).collect();
+        pass(&mut list, &keys, &unmounted);
+
+        let reversed: Vec<u32> = keys.iter().rev().copied().collect();
+        pass(&mut list, &reversed, &unmounted);
+
+        assert_eq!(ids(&mut list), reversed);
+        assert!(unmounted.borrow().is_empty());
+    }
+
+    // Сколько раз элементам менялся Z, порядок отрисовки совпадает с порядком списка
+    fn z_updates<const N: usize>(list: &mut DynList<u32, Item, N>) -> usize {
+        list.order_z();
+
+        let order: Vec<(i32, u32)> = list.iter_mut().map(|item| (item.z, item.created)).collect();
+        assert!(order.windows(2).all(|pair| pair[0] < pair[1]), "{order:?}");
+
+        list.iter_mut()
+            .map(|item| mem::take(&mut item.z_updates))
+            .sum()
+    }
+
+    #[test]
+    fn test_dyn_list_moved() {
+        let unmounted = Rc::new(RefCell::new(Vec::new()));
+        let mut list: DynList<u32, Item> = DynList::new();
+
+        // Новые элементы в конце и так рисуются по порядку создания
+        pass(&mut list, &[0, 1, 2, 3, 4], &unmounted);
+        assert!(list.take_changed());
+        assert_eq!(z_updates(&mut list), 0);
+
+        // Тот же порядок ничего не меняет
+        pass(&mut list, &[0, 1, 2, 3, 4], &unmounted);
+        assert!(!list.take_changed());
+        assert_eq!(z_updates(&mut list), 0);
+
+        // Перенос одной строки в начало это одно перемещение
+        pass(&mut list, &[3, 0, 1, 2, 4], &unmounted);
+        assert!(list.take_changed());
+        assert_eq!(z_updates(&mut list), 1);
+
+        // Обмен двух строк это два перемещения
+        pass(&mut list, &[3, 2, 1, 0, 4], &unmounted);
+        assert!(list.take_changed());
+        assert_eq!(z_updates(&mut list), 2);
+
+        // Новый элемент встаёт между соседями, удаление Z не меняет
+        pass(&mut list, &[3, 9, 1, 0, 4], &unmounted);
+        assert!(list.take_changed());
+        assert_eq!(z_updates(&mut list), 1);
+    }
+
+    #[test]
+    fn test_dyn_list_order_z_without_gap() {
+        let unmounted = Rc::new(RefCell::new(Vec::new()));
+        let mut list: DynList<u32, Item> = DynList::new();
+
+        pass(&mut list, &[0, 1], &unmounted);
+        z_updates(&mut list);
+
+        // Промежуток между соседями кончается, дальше элементы встают по порядку
+        // создания или сдвигают соседей сверху
+        for key in 2..40 {
+            let mut keys: Vec<u32> = list.iter_mut().map(|item| item.id).collect();
+            keys.insert(1, key);
+            pass(&mut list, &keys, &unmounted);
+            z_updates(&mut list);
+        }
+    }
+
+    #[test]
+    fn test_dyn_list_recycling() {
+        let unmounted = Rc::new(RefCell::new(Vec::new()));
+        let mut list: DynList<u32, Item, 4> = DynList::new().with_recycling();
+
+        let pass_recycled = |list: &mut DynList<u32, Item, 4>, keys: &[u32]| {
+            list.begin_pass();
+
+            for &key in keys {
+                if let ListEntry::Vacant(entry) = list.entry(key)
+                    && let Err(entry) = entry.recycle()
+                {
+                    entry.insert(Item {
+                        id: key,
+                        unmounted: unmounted.clone(),
+                        created: CREATED.fetch_add(1, Ordering::Relaxed),
+                        z: 0,
+                        z_updates: 0,
+                    });
+                }
+            }
+
+            list.end_pass();
+        };
+
+        pass_recycled(&mut list, &[0, 1, 2]);
+        pass_recycled(&mut list, &[1, 2]);
+        assert!(list.take_changed());
+
+        // Ушедший элемент ждёт в пуле, а новый ключ получает его скин вместо создания
+        pass_recycled(&mut list, &[1, 2, 3]);
+        assert_eq!(ids(&mut list), vec![1, 2, 0]);
+        assert!(unmounted.borrow().is_empty());
+    }
+
+    // Политика глобальная, поэтому все варианты проверяются в одном тесте
+    #[cfg(feature = "no-alloc")]
+    #[test]
+    fn test_dyn_list_overflow() {
+        let unmounted = Rc::new(RefCell::new(Vec::new()));
+
+        // Элементы за пределами ёмкости размонтируются, а не теряются
+        let mut list = DynList::<u32, Item, 4>::new();
+        pass(&mut list, &[0, 1, 2, 3], &unmounted);
+        pass(&mut list, &[], &unmounted);
+        assert_eq!(unmounted.borrow().len(), 4);
+
+        set_list_overflow(ListOverflow::DropOldest);
+        let mut list = DynList::<u32, Item, 3>::new();
+        unmounted.borrow_mut().clear();
+        pass(&mut list, &[0, 1, 2, 3, 4], &unmounted);
+        assert_eq!(ids(&mut list), [2, 3, 4]);
+        assert_eq!(*unmounted.borrow(), [0, 1]);
+
+        set_list_overflow(ListOverflow::Ignore);
+        let mut list = DynList::<u32, Item, 3>::new();
+        unmounted.borrow_mut().clear();
+        pass(&mut list, &[0, 1, 2, 3, 4], &unmounted);
+        assert_eq!(ids(&mut list), [0, 1, 2]);
+        assert_eq!(*unmounted.borrow(), [3, 4]);
+
+        set_list_overflow(ListOverflow::Panic);
+        let mut list = DynList::<u32, Item, 3>::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            pass(&mut list, &[0, 1, 2, 3], &unmounted);
+        }));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,272 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+// Запуск задач асинхронных спарков
+//
+// Фреймворк не привязан к конкретному асинхронному рантайму: задачи запускает Spawner
+// который приложение устанавливает при старте. Из коробки есть два:
+//  - LocalExecutor, однопоточный исполнитель который опрашивается тиком, таймеры идут
+//    по часам тика. Не требует tokio, подходит для встроенных систем
+//  - TokioSpawner (фича tokio), задачи уходят в tokio::spawn
+//
+// ```ignore
+// static TOKIO: firework_ui::TokioSpawner = firework_ui::TokioSpawner;
+//
+// fn main() {
+//     firework_ui::set_spawner(&TOKIO);
+//     firework_ui::run(root_screen);
+// }
+// ```
+//
+// Без set_spawner используется TokioSpawner если включена фича tokio, иначе LocalExecutor
+
+#[cfg(feature = "safety-multithread")]
+use std::sync::{Mutex, OnceLock};
+
+use futures::future::{AbortHandle, Abortable};
+use futures::task::{ArcWake, waker_ref};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Waker};
+
+use crate::get_time_ms;
+
+/// Задача которую запускает Spawner
+pub type BoxTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Запускает задачи асинхронных спарков и даёт им таймер. Задачи создаются только из
+/// потока интерфейса, а sleep вызывается из самих задач
+pub trait Spawner: Send + Sync {
+    fn spawn(&self, task: BoxTask);
+
+    /// Future которое завершится через duration_ms миллисекунд
+    fn sleep(&self, duration_ms: u64) -> BoxTask;
+}
+
+/// Запущенная задача, отмена работает одинаково для любого Spawner
+#[derive(Debug)]
+pub struct TaskHandle {
+    abort: AbortHandle,
+    finished: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    /// Отменяет задачу, она будет удалена исполнителем при следующем опросе
+    pub fn abort(&self) {
+        self.abort.abort();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire) || self.abort.is_aborted()
+    }
+}
+
+/// Запускает задачу через установленный Spawner
+pub fn spawn<F>(task: F) -> TaskHandle
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (abort, registration) = AbortHandle::new_pair();
+    let finished = Arc::new(AtomicBool::new(false));
+
+    let task_finished = finished.clone();
+    spawner().spawn(Box::pin(async move {
+        let _ = Abortable::new(task, registration).await;
+        task_finished.store(true, Ordering::Release);
+    }));
+
+    TaskHandle { abort, finished }
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut SPAWNER: Option<&'static dyn Spawner> = None;
+
+#[cfg(feature = "safety-multithread")]
+static SPAWNER: Mutex<Option<&'static dyn Spawner>> = Mutex::new(None);
+
+/// Устанавливает исполнитель задач, вызывается при старте приложения до первого экрана
+#[cfg(not(feature = "safety-multithread"))]
+pub fn set_spawner(spawner: &'static dyn Spawner) {
+    unsafe {
+        *::core::ptr::addr_of_mut!(SPAWNER) = Some(spawner);
+    }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn set_spawner(spawner: &'static dyn Spawner) {
+    *SPAWNER.lock().unwrap() = Some(spawner);
+}
+
+/// Установленный исполнитель задач или исполнитель по умолчанию
+#[cfg(not(feature = "safety-multithread"))]
+pub fn spawner() -> &'static dyn Spawner {
+    unsafe { (*::core::ptr::addr_of!(SPAWNER)).unwrap_or_else(default_spawner) }
+}
+
+#[cfg(feature = "safety-multithread")]
+pub fn spawner() -> &'static dyn Spawner {
+    SPAWNER.lock().unwrap().unwrap_or_else(default_spawner)
+}
+
+#[cfg(feature = "tokio")]
+fn default_spawner() -> &'static dyn Spawner {
+    &TokioSpawner
+}
+
+#[cfg(not(feature = "tokio"))]
+fn default_spawner() -> &'static dyn Spawner {
+    &LocalExecutor
+}
+
+/// Задачи уходят в tokio::spawn, поэтому экран должен работать внутри tokio рантайма
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioSpawner;
+
+#[cfg(feature = "tokio")]
+impl Spawner for TokioSpawner {
+    fn spawn(&self, task: BoxTask) {
+        tokio::spawn(task);
+    }
+
+    fn sleep(&self, duration_ms: u64) -> BoxTask {
+        Box::pin(tokio::time::sleep(std::time::Duration::from_millis(
+            duration_ms,
+        )))
+    }
+}
+
+/// Встроенный однопоточный исполнитель. Каждый кадр (handle_tick) он будит задачи
+/// у которых истёк таймер и опрашивает разбуженные, поэтому значение отправленное
+/// задачей попадает в спарк в том же кадре. Без кадров задачи стоят
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalExecutor;
+
+impl Spawner for LocalExecutor {
+    fn spawn(&self, task: BoxTask) {
+        with_local(|local| {
+            local.tasks.push(LocalTask {
+                future: task,
+                waker: Arc::new(TaskWaker {
+                    // Новая задача опрашивается на ближайшем кадре
+                    woken: AtomicBool::new(true),
+                }),
+            })
+        });
+    }
+
+    fn sleep(&self, duration_ms: u64) -> BoxTask {
+        Box::pin(LocalSleep {
+            deadline_ms: get_time_ms().saturating_add(duration_ms),
+        })
+    }
+}
+
+struct TaskWaker {
+    woken: AtomicBool,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.store(true, Ordering::Release);
+    }
+}
+
+struct LocalTask {
+    future: BoxTask,
+    waker: Arc<TaskWaker>,
+}
+
+struct LocalState {
+    tasks: Vec<LocalTask>,
+
+    // Таймеры sleep: момент по часам тика и кого разбудить
+    timers: Vec<(u64, Waker)>,
+}
+
+impl LocalState {
+    const fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            timers: Vec::new(),
+        }
+    }
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut LOCAL_STATE: LocalState = LocalState::new();
+
+#[cfg(feature = "safety-multithread")]
+static LOCAL_STATE: OnceLock<Mutex<LocalState>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+fn with_local<R>(f: impl FnOnce(&mut LocalState) -> R) -> R {
+    unsafe { f(&mut *::core::ptr::addr_of_mut!(LOCAL_STATE)) }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn with_local<R>(f: impl FnOnce(&mut LocalState) -> R) -> R {
+    f(&mut LOCAL_STATE
+        .get_or_init(|| Mutex::new(LocalState::new()))
+        .lock()
+        .unwrap())
+}
+
+struct LocalSleep {
+    deadline_ms: u64,
+}
+
+impl Future for LocalSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if get_time_ms() >= self.deadline_ms {
+            return Poll::Ready(());
+        }
+
+        let deadline_ms = self.deadline_ms;
+        with_local(|local| local.timers.push((deadline_ms, cx.waker().clone())));
+
+        Poll::Pending
+    }
+}
+
+/// Кадр встроенного исполнителя, вызывается из handle_tick после продвижения часов
+pub(crate) fn tick() {
+    let now = get_time_ms();
+
+    let expired: Vec<Waker> = with_local(|local| {
+        let (expired, pending) = std::mem::take(&mut local.timers)
+            .into_iter()
+            .partition(|(deadline_ms, _)| *deadline_ms <= now);
+
+        local.timers = pending;
+        expired.into_iter().map(|(_, waker)| waker).collect()
+    });
+
+    for waker in expired {
+        waker.wake();
+    }
+
+    // Задачи забираются на время опроса: задача может создать таймер или новую задачу,
+    // а состояние исполнителя в это время не должно быть занято
+    let mut tasks = with_local(|local| std::mem::take(&mut local.tasks));
+
+    tasks.retain_mut(|task| {
+        if !task.waker.woken.swap(false, Ordering::AcqRel) {
+            return true;
+        }
+
+        let waker = waker_ref(&task.waker);
+        let mut cx = Context::from_waker(&waker);
+
+        task.future.as_mut().poll(&mut cx).is_pending()
+    });
+
+    with_local(|local| {
+        tasks.append(&mut local.tasks);
+        local.tasks = tasks;
+    });
+}
@@ -1,7 +1,20 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+pub mod animated;
+
 #[cfg(feature = "async")]
 pub mod async_spark;
 
+#[cfg(feature = "async")]
+pub mod executor;
+
 pub mod dyn_list;
+
+pub mod resources;
+
+pub mod scroll;
+
+pub mod touch;
+
+pub mod transition;
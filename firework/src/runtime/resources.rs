@@ -0,0 +1,46 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use firework_adapter::{AdapterCommand, AdapterResult};
+
+use crate::adapter_command;
+
+/// Хэндл текстуры адаптера. Текстуры не удаляются при навигации (RemoveAll), поэтому
+/// одну текстуру можно загрузить при старте и показывать на разных экранах
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Texture(pub usize);
+
+/// Хэндл шрифта адаптера, как и текстура переживает навигацию
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Font(pub usize);
+
+/// Загружает изображение из байтов (например include_bytes!), возвращает None если
+/// адаптер не смог декодировать формат
+pub fn load_image(bytes: &[u8]) -> Option<Texture> {
+    match adapter_command(AdapterCommand::LoadImage { bytes }) {
+        AdapterResult::Handle(handle) => Some(Texture(handle)),
+        _ => None,
+    }
+}
+
+/// Загружает изображение по пути, путь интерпретирует адаптер
+pub fn load_image_path(path: &str) -> Option<Texture> {
+    match adapter_command(AdapterCommand::LoadImageFromPath { path }) {
+        AdapterResult::Handle(handle) => Some(Texture(handle)),
+        _ => None,
+    }
+}
+
+/// Освобождает текстуру, после этого хэндл использовать нельзя
+pub fn free_texture(texture: Texture) {
+    let _ = adapter_command(AdapterCommand::FreeTexture(texture.0));
+}
+
+/// Регистрирует шрифт из байтов TTF или OTF (например include_bytes!), возвращает None
+/// если адаптер не смог его разобрать
+pub fn load_font(bytes: &[u8]) -> Option<Font> {
+    match adapter_command(AdapterCommand::LoadFont { bytes }) {
+        AdapterResult::Handle(handle) => Some(Font(handle)),
+        _ => None,
+    }
+}
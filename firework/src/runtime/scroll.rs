@@ -0,0 +1,338 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use core::mem;
+
+use firework_adapter::{AdapterClickPhase, AdapterCommand, AdapterResult};
+
+use crate::layout::{LayoutParams, Size, viewport};
+use crate::runtime::touch::{TOUCH_SLOP, cancel_press};
+use crate::{
+    CurrentEvent, LifeCycle, adapter_command, get_current_event, get_time_ms, on_frame,
+    request_tick,
+};
+
+/// Какая доля скорости инерции остаётся через миллисекунду
+pub const SCROLL_FRICTION: f32 = 0.997;
+
+/// Скорость инерции в пикселях за миллисекунду ниже которой прокрутка останавливается
+pub const SCROLL_MIN_VELOCITY: f32 = 0.02;
+
+/// Если палец стоял на месте дольше этого времени перед отпусканием, инерции нет
+pub const FLING_TIMEOUT_MS: u64 = 100;
+
+/// Перетаскивание содержимого пальцем
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    // Последняя координата пальца по оси прокрутки
+    position: i32,
+
+    // Координата и время по часам тика на начало текущего замера скорости. События
+    // между кадрами приходят с одним временем, поэтому скорость считается за кадр
+    sample_position: i32,
+    sample_ms: u64,
+
+    // Палец ушёл по оси прокрутки дальше TOUCH_SLOP и прокрутка забрала нажатие себе
+    claimed: bool,
+}
+
+/// Состояние контейнера scroll! {}, хранится в структуре экрана. Сгенерированный код
+/// вызывает update в начале тела scroll на каждой итерации экрана: перетаскивание
+/// пальцем дальше TOUCH_SLOP забирает нажатие у детей (нажатый ребёнок получает
+/// on_cancel) и сдвигает содержимое, после отпускания прокрутка продолжается по инерции
+/// на кадрах. Компоновка сдвигает детей на offset, обрезает их по невидимому прямоугольнику
+/// окна (SetClipTo) и через place сообщает размер окна и содержимого
+///
+/// Прокрутку можно привязать к спарку, тогда спарк получает offset при каждом сдвиге,
+/// а присваивание спарку прокручивает контейнер программно
+///
+/// ```ignore
+/// let mut top = spark!(0);
+///
+/// button! {
+///     label: "Наверх",
+///     on_click: || top = 0,
+/// }
+///
+/// scroll! {
+///     layout! {
+///         offset: top,
+///     }
+///
+///     // Окно строк lazy_list берёт из прокрутки этого scroll
+///     lazy_list! {
+///         layout! {
+///             extent: 40,
+///         }
+///
+///         for (i, item) in items.iter().enumerate() {
+///             text! {
+///                 content: item,
+///
+///                 #[key_type(usize)]
+///                 key: i,
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrollView {
+    // Невидимый прямоугольник окна по которому обрезаются дети
+    clip: Option<usize>,
+
+    // Прокрутка с дробной частью, иначе медленная инерция терялась бы на округлении
+    offset: f32,
+
+    // Скорость инерции в пикселях за миллисекунду, положительная прокручивает вперёд
+    velocity: f32,
+
+    drag: Option<Drag>,
+
+    horizontal: bool,
+
+    // viewport из layout! {}, длина окна до первой компоновки
+    viewport: i32,
+
+    // Окно после последней компоновки: левый верхний угол и размер
+    origin: (i32, i32),
+    size: (i32, i32),
+
+    // Насколько содержимое длиннее окна, None пока не было компоновки
+    max_offset: Option<i32>,
+
+    // Значение offset из layout! {} которое прокрутка видела последним. Для привязанного
+    // спарка это то, что прокрутка в него записала
+    seen: Option<i32>,
+
+    // Прокрутка сдвинулась и детей нужно скомпоновать заново
+    changed: bool,
+}
+
+impl ScrollView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Текущая прокрутка в логических пикселях
+    pub fn offset(&self) -> i32 {
+        self.offset.round() as i32
+    }
+
+    /// Хэндл прямоугольника по которому обрезаются дети
+    pub fn clip(&self) -> Option<usize> {
+        self.clip
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.horizontal
+    }
+
+    /// Длина окна по оси прокрутки. До первой компоновки окно берётся из viewport, а
+    /// без него окно занимает экран
+    pub fn window(&self) -> i32 {
+        let (placed, screen) = if self.horizontal {
+            (self.size.0, viewport().width as i32)
+        } else {
+            (self.size.1, viewport().height as i32)
+        };
+
+        if placed > 0 {
+            placed
+        } else if self.viewport > 0 {
+            self.viewport
+        } else {
+            screen
+        }
+    }
+
+    /// Продолжается ли прокрутка по инерции
+    pub fn is_flinging(&self) -> bool {
+        self.drag.is_none() && self.velocity != 0.0
+    }
+
+    /// Прокручивает к offset и останавливает инерцию
+    pub fn scroll_to(&mut self, offset: i32) {
+        self.velocity = 0.0;
+        self.set_offset(offset as f32);
+    }
+
+    /// Сдвинулась ли прокрутка с прошлой проверки, флаг сбрасывается
+    pub fn take_changed(&mut self) -> bool {
+        mem::take(&mut self.changed)
+    }
+
+    /// Обработка итерации экрана. params это LayoutParams из layout! {}: если их offset
+    /// изменился не из-за прокрутки, то это программная прокрутка. Если bound (offset
+    /// привязан к спарку) то возвращает новую прокрутку после сдвига пальцем или инерцией,
+    /// её нужно записать в спарк
+    pub fn update(&mut self, event: LifeCycle, params: LayoutParams, bound: bool) -> Option<i32> {
+        self.horizontal = params.horizontal;
+        self.viewport = params.viewport;
+
+        if self.clip.is_none() {
+            self.clip = new_clip();
+        }
+
+        if self.seen != Some(params.offset) {
+            self.seen = Some(params.offset);
+            self.scroll_to(params.offset);
+        }
+
+        // Кадры двигают инерцию, но без подписки: пока инерция идёт, scroll сам
+        // запрашивает следующий кадр, а в покое экран не получает Tick
+        on_frame(event, |dt| self.fling(dt));
+
+        if matches!(event, LifeCycle::Event)
+            && let CurrentEvent::Touch { x, y, phase, .. } = get_current_event()
+        {
+            self.on_touch(x as i32, y as i32, phase);
+        }
+
+        if self.is_flinging() {
+            request_tick();
+        }
+
+        let offset = self.offset();
+        if bound && self.seen != Some(offset) {
+            self.seen = Some(offset);
+            return Some(offset);
+        }
+
+        None
+    }
+
+    /// Окно и длина содержимого после компоновки, вызывается из LayoutCursor::finish_scroll
+    pub fn place(&mut self, origin: (i32, i32), size: Size, content: i32) {
+        let window = if self.horizontal {
+            size.width
+        } else {
+            size.height
+        };
+
+        // Содержимое могло стать короче, прокрутка не уходит за его конец
+        self.max_offset = Some((content - window).max(0));
+        self.set_offset(self.offset);
+
+        if let Some(clip) = self.clip {
+            if self.origin != origin {
+                let _ = adapter_command(AdapterCommand::SetPosition(clip, origin));
+                self.origin = origin;
+            }
+
+            if self.size != (size.width, size.height) {
+                let _ = adapter_command(AdapterCommand::SetSize(clip, (size.width, size.height)));
+                self.size = (size.width, size.height);
+            }
+        }
+    }
+
+    fn set_offset(&mut self, offset: f32) {
+        let offset = match self.max_offset {
+            Some(max) => offset.clamp(0.0, max as f32),
+            None => offset.max(0.0),
+        };
+
+        if offset.round() != self.offset.round() {
+            self.changed = true;
+        }
+
+        self.offset = offset;
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.origin.0
+            && y >= self.origin.1
+            && x < self.origin.0 + self.size.0
+            && y < self.origin.1 + self.size.1
+    }
+
+    fn on_touch(&mut self, x: i32, y: i32, phase: AdapterClickPhase) {
+        let position = if self.horizontal { x } else { y };
+        let now = get_time_ms();
+
+        match phase {
+            // Касание останавливает инерцию даже если палец потом не сдвинется
+            AdapterClickPhase::Began if self.contains(x, y) => {
+                self.velocity = 0.0;
+                self.drag = Some(Drag {
+                    position,
+                    sample_position: position,
+                    sample_ms: now,
+                    claimed: false,
+                });
+            }
+
+            AdapterClickPhase::Moved => {
+                let Some(mut drag) = self.drag else {
+                    return;
+                };
+
+                // До TOUCH_SLOP касание остаётся нажатием на ребёнка, после прокрутка
+                // забирает его и сдвигает содержимое на весь путь пальца
+                if !drag.claimed {
+                    if drag.position.abs_diff(position) <= TOUCH_SLOP {
+                        return;
+                    }
+
+                    drag.claimed = true;
+                    cancel_press();
+                }
+
+                // Палец вверх сдвигает содержимое вверх, то есть увеличивает прокрутку
+                self.set_offset(self.offset + (drag.position - position) as f32);
+                drag.position = position;
+
+                if now > drag.sample_ms {
+                    self.velocity =
+                        (drag.sample_position - position) as f32 / (now - drag.sample_ms) as f32;
+                    drag.sample_position = position;
+                    drag.sample_ms = now;
+                }
+
+                self.drag = Some(drag);
+            }
+
+            AdapterClickPhase::Ended | AdapterClickPhase::Cancelled => {
+                if let Some(drag) = self.drag.take()
+                    && now.saturating_sub(drag.sample_ms) > FLING_TIMEOUT_MS
+                {
+                    self.velocity = 0.0;
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn fling(&mut self, dt: u32) {
+        if !self.is_flinging() {
+            return;
+        }
+
+        self.set_offset(self.offset + self.velocity * dt as f32);
+        self.velocity *= SCROLL_FRICTION.powi(dt as i32);
+
+        // Инерция гаснет на краю содержимого и на малой скорости
+        let at_edge = (self.velocity < 0.0 && self.offset <= 0.0)
+            || (self.velocity > 0.0
+                && self.max_offset.is_some_and(|max| self.offset >= max as f32));
+
+        if at_edge || self.velocity.abs() < SCROLL_MIN_VELOCITY {
+            self.velocity = 0.0;
+        }
+    }
+}
+
+// Прямоугольник обрезки не рисуется и не перехватывает касания, адаптер берёт от него
+// только форму
+fn new_clip() -> Option<usize> {
+    match adapter_command(AdapterCommand::NewRect { layout: 1 }) {
+        AdapterResult::Handle(handle) => {
+            let _ = adapter_command(AdapterCommand::SetVisible(handle, false));
+            Some(handle)
+        }
+
+        _ => None,
+    }
+}
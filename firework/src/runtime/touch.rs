@@ -0,0 +1,263 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+#[cfg(feature = "safety-multithread")]
+use std::sync::{Mutex, OnceLock};
+
+use crate::{AdapterClickPhase, CurrentEvent, get_current_event};
+
+/// Сколько миллисекунд палец должен удерживаться на виджете чтобы сработал on_long_press
+pub const LONG_PRESS_MS: u64 = 500;
+
+/// На сколько логических пикселей палец может сдвинуться от точки нажатия чтобы нажатие
+/// всё ещё считалось удержанием на месте
+pub const TOUCH_SLOP: u32 = 8;
+
+/// Жест который может обработать виджет. Каждому жесту соответствует пропс с замыканием
+/// (on_click, on_press и т.д.), сгенерированный код спрашивает через is_gesture относится
+/// ли текущее событие к хэндлу скина виджета
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchGesture {
+    /// Палец нажал на виджет (on_press)
+    Press,
+
+    /// Палец нажал на виджет и водит по экрану, виджет получает все движения до отпускания
+    /// даже если палец ушёл за его пределы (on_move)
+    Move,
+
+    /// Палец отпустил на том же виджете на котором нажал (on_release)
+    Release,
+
+    /// То же что и Release, но только если до этого не сработало долгое нажатие (on_click)
+    Click,
+
+    /// Нажатие отменено адаптером, палец отпустил за пределами виджета или нажатие
+    /// перехватил контейнер, например scroll! {} начал прокрутку (on_cancel)
+    Cancel,
+
+    /// Палец удерживается на виджете дольше LONG_PRESS_MS и не сдвигается дальше
+    /// TOUCH_SLOP (on_long_press)
+    LongPress,
+}
+
+/// Состояние текущего нажатия. Нажатие привязывается к хэндлу на фазе Began и живёт до
+/// Ended или Cancelled, по нему определяется парность нажатия и отпускания
+#[derive(Debug, Clone, Copy)]
+struct PressState {
+    /// Хэндл на который нажали, None если нажатия нет или оно было мимо виджетов
+    handle: Option<usize>,
+
+    /// Координаты нажатия
+    x: u32,
+    y: u32,
+
+    /// Время нажатия по часам тика в миллисекундах
+    pressed_at: u64,
+
+    /// Долгое нажатие уже было отправлено, повторно оно не отправляется, а отпускание
+    /// после него не считается кликом
+    long_fired: bool,
+
+    /// Палец ушёл с виджета или дальше TOUCH_SLOP, долгого нажатия уже не будет
+    long_cancelled: bool,
+
+    /// Хэндл нажатия которое перехватили во время текущего события, он получает Cancel
+    cancelled: Option<usize>,
+}
+
+impl PressState {
+    const fn new() -> Self {
+        Self {
+            handle: None,
+            x: 0,
+            y: 0,
+            pressed_at: 0,
+            long_fired: false,
+            long_cancelled: false,
+            cancelled: None,
+        }
+    }
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut PRESS_STATE: PressState = PressState::new();
+
+#[cfg(feature = "safety-multithread")]
+static PRESS_STATE: OnceLock<Mutex<PressState>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+fn with_press_state<R>(f: impl FnOnce(&mut PressState) -> R) -> R {
+    unsafe { f(&mut *::core::ptr::addr_of_mut!(PRESS_STATE)) }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn with_press_state<R>(f: impl FnOnce(&mut PressState) -> R) -> R {
+    f(&mut PRESS_STATE
+        .get_or_init(|| Mutex::new(PressState::new()))
+        .lock()
+        .unwrap())
+}
+
+/// Хэндл на который сейчас нажат палец
+pub fn pressed_handle() -> Option<usize> {
+    with_press_state(|state| state.handle)
+}
+
+/// Перехватывает текущее нажатие, например когда палец начал прокрутку. Нажатый виджет
+/// получает Cancel в этом же событии, а дальше не получает ни движения, ни отпускания, ни
+/// клика, ни долгого нажатия
+pub(crate) fn cancel_press() {
+    with_press_state(|state| {
+        if let Some(handle) = state.handle.take() {
+            state.cancelled = Some(handle);
+        }
+    });
+}
+
+/// Относится ли текущее событие к жесту gesture на хэндле handle. Вызывается
+/// сгенерированным кодом виджетов в фазе Event
+pub fn is_gesture(gesture: TouchGesture, handle: usize) -> bool {
+    let state = with_press_state(|state| *state);
+    let pressed = state.handle == Some(handle);
+
+    if gesture == TouchGesture::Cancel && state.cancelled == Some(handle) {
+        return true;
+    }
+
+    match (gesture, get_current_event()) {
+        (
+            TouchGesture::Press,
+            CurrentEvent::Touch {
+                hit_object_id,
+                phase: AdapterClickPhase::Began,
+                ..
+            },
+        ) => hit_object_id == Some(handle),
+
+        (
+            TouchGesture::Move,
+            CurrentEvent::Touch {
+                phase: AdapterClickPhase::Moved,
+                ..
+            },
+        ) => pressed,
+
+        (
+            TouchGesture::Release,
+            CurrentEvent::Touch {
+                hit_object_id,
+                phase: AdapterClickPhase::Ended,
+                ..
+            },
+        ) => pressed && hit_object_id == Some(handle),
+
+        (
+            TouchGesture::Click,
+            CurrentEvent::Touch {
+                hit_object_id,
+                phase: AdapterClickPhase::Ended,
+                ..
+            },
+        ) => pressed && hit_object_id == Some(handle) && !state.long_fired,
+
+        // Отпускание за пределами виджета отменяет нажатие
+        (
+            TouchGesture::Cancel,
+            CurrentEvent::Touch {
+                hit_object_id,
+                phase: AdapterClickPhase::Ended,
+                ..
+            },
+        ) => pressed && hit_object_id != Some(handle),
+
+        (
+            TouchGesture::Cancel,
+            CurrentEvent::Touch {
+                phase: AdapterClickPhase::Cancelled,
+                ..
+            },
+        ) => pressed,
+
+        (TouchGesture::LongPress, CurrentEvent::LongPress { hit_object_id, .. }) => {
+            hit_object_id == handle
+        }
+
+        _ => false,
+    }
+}
+
+/// Обновляет состояние нажатия перед отправкой события в фокус. Began привязывает нажатие
+/// к хэндлу под пальцем, Moved за пределы виджета или дальше TOUCH_SLOP отменяет
+/// ожидающее долгое нажатие
+pub(crate) fn before_dispatch(event: &CurrentEvent, now: u64) {
+    let CurrentEvent::Touch {
+        x,
+        y,
+        hit_object_id,
+        phase,
+    } = *event
+    else {
+        return;
+    };
+
+    with_press_state(|state| match phase {
+        AdapterClickPhase::Began => {
+            *state = PressState {
+                handle: hit_object_id,
+                x,
+                y,
+                pressed_at: now,
+                long_fired: false,
+                long_cancelled: false,
+                cancelled: None,
+            }
+        }
+
+        AdapterClickPhase::Moved => {
+            state.long_cancelled |= hit_object_id != state.handle
+                || x.abs_diff(state.x) > TOUCH_SLOP
+                || y.abs_diff(state.y) > TOUCH_SLOP;
+        }
+
+        _ => {}
+    });
+}
+
+/// Сбрасывает нажатие после того как Ended или Cancelled были обработаны виджетами.
+/// Перехват нажатия отправляет Cancel только в том событии в котором он случился
+pub(crate) fn after_dispatch(event: &CurrentEvent) {
+    with_press_state(|state| {
+        state.cancelled = None;
+
+        if let CurrentEvent::Touch {
+            phase: AdapterClickPhase::Ended | AdapterClickPhase::Cancelled,
+            ..
+        } = event
+        {
+            *state = PressState::new();
+        }
+    });
+}
+
+/// Проверяет по часам тика не пора ли отправить долгое нажатие. Возвращает событие только
+/// один раз за нажатие
+pub(crate) fn poll_long_press(now: u64) -> Option<CurrentEvent> {
+    with_press_state(|state| {
+        let handle = state.handle?;
+
+        if state.long_fired
+            || state.long_cancelled
+            || now.saturating_sub(state.pressed_at) < LONG_PRESS_MS
+        {
+            return None;
+        }
+
+        state.long_fired = true;
+
+        Some(CurrentEvent::LongPress {
+            x: state.x,
+            y: state.y,
+            hit_object_id: handle,
+        })
+    })
+}
@@ -0,0 +1,214 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+#[cfg(feature = "safety-multithread")]
+use std::sync::{Mutex, OnceLock};
+
+use crate::layout::viewport;
+use crate::runtime::animated::Easing;
+use crate::{AdapterCommand, adapter_command};
+
+// Индексы сцен адаптера для SetSceneOffset и SetSceneOpacity
+const CURRENT_SCENE: u8 = 0;
+const DETACHED_SCENE: u8 = 1;
+
+/// Анимация перехода между экранами при навигации. Пока переход идёт примитивы старого
+/// экрана продолжают рисоваться (AdapterCommand::DetachAll), а после его окончания
+/// удаляются (AdapterCommand::RemoveDetached). Переход продвигается тиком
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Transition {
+    /// Старый экран удаляется сразу через RemoveAll
+    #[default]
+    None,
+
+    /// Новый экран въезжает справа и выталкивает старый, при возвращении назад
+    /// направление обратное
+    Slide { duration_ms: u32 },
+
+    /// Старый экран растворяется, новый проявляется
+    Fade { duration_ms: u32 },
+}
+
+impl Transition {
+    fn duration_ms(self) -> u32 {
+        match self {
+            Transition::None => 0,
+            Transition::Slide { duration_ms } | Transition::Fade { duration_ms } => duration_ms,
+        }
+    }
+}
+
+/// Направление навигации, от него зависит сторона с которой въезжает экран при Slide
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Direction {
+    Forward,
+    Back,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Active {
+    transition: Transition,
+    direction: Direction,
+    elapsed_ms: u32,
+}
+
+struct TransitionState {
+    preset: Transition,
+
+    // Направление следующего входа на экран, устанавливается навигацией перед вызовом
+    // экрана. Экран вызванный не через навигацию не анимируется
+    armed: Option<Direction>,
+
+    active: Option<Active>,
+}
+
+impl TransitionState {
+    const fn new() -> Self {
+        Self {
+            preset: Transition::None,
+            armed: None,
+            active: None,
+        }
+    }
+}
+
+#[cfg(not(feature = "safety-multithread"))]
+static mut TRANSITION_STATE: TransitionState = TransitionState::new();
+
+#[cfg(feature = "safety-multithread")]
+static TRANSITION_STATE: OnceLock<Mutex<TransitionState>> = OnceLock::new();
+
+#[cfg(not(feature = "safety-multithread"))]
+fn with_state<R>(f: impl FnOnce(&mut TransitionState) -> R) -> R {
+    unsafe { f(&mut *::core::ptr::addr_of_mut!(TRANSITION_STATE)) }
+}
+
+#[cfg(feature = "safety-multithread")]
+fn with_state<R>(f: impl FnOnce(&mut TransitionState) -> R) -> R {
+    f(&mut TRANSITION_STATE
+        .get_or_init(|| Mutex::new(TransitionState::new()))
+        .lock()
+        .unwrap())
+}
+
+/// Переход для следующих навигаций, текущий переход (если он идёт) доигрывается
+pub fn set_transition(transition: Transition) {
+    with_state(|state| state.preset = transition);
+}
+
+/// Идёт ли сейчас переход
+pub fn is_transitioning() -> bool {
+    with_state(|state| state.active.is_some())
+}
+
+/// Сбрасывает состояние при запуске приложения
+pub(crate) fn reset(preset: Transition) {
+    with_state(|state| *state = TransitionState::new());
+    set_transition(preset);
+}
+
+/// Следующий вход на экран будет анимирован
+pub(crate) fn arm(direction: Direction) {
+    with_state(|state| state.armed = Some(direction));
+}
+
+/// Вызывается сгенерированным кодом экрана при Build и Navigate вместо RemoveAll.
+/// Если экран показывает навигация и задан переход то старые примитивы остаются в
+/// уходящей сцене, иначе удаляются. Подписка прошлого экрана на кадры (tick!)
+/// сбрасывается
+pub fn enter_screen() {
+    crate::reset_tick_subscription();
+
+    let active = with_state(|state| {
+        let direction = state.armed.take()?;
+
+        if state.preset.duration_ms() == 0 {
+            return None;
+        }
+
+        let active = Active {
+            transition: state.preset,
+            direction,
+            elapsed_ms: 0,
+        };
+
+        state.active = Some(active);
+        Some(active)
+    });
+
+    match active {
+        Some(active) => {
+            adapter_command(AdapterCommand::DetachAll);
+            apply(active, 0.0);
+        }
+
+        None => {
+            with_state(|state| state.active = None);
+            adapter_command(AdapterCommand::RemoveAll);
+        }
+    }
+}
+
+/// Продвигает переход на dt миллисекунд, вызывается каждый кадр до тик функции экрана
+pub(crate) fn tick(dt: u32) {
+    let Some(active) = with_state(|state| {
+        let active = state.active.as_mut()?;
+        active.elapsed_ms = active.elapsed_ms.saturating_add(dt);
+
+        let active = *active;
+        if active.elapsed_ms >= active.transition.duration_ms() {
+            state.active = None;
+        }
+
+        Some(active)
+    }) else {
+        return;
+    };
+
+    let duration = active.transition.duration_ms();
+
+    if active.elapsed_ms >= duration {
+        adapter_command(AdapterCommand::RemoveDetached);
+        adapter_command(AdapterCommand::SetSceneOffset(CURRENT_SCENE, (0, 0)));
+        adapter_command(AdapterCommand::SetSceneOpacity(CURRENT_SCENE, 255));
+        return;
+    }
+
+    apply(active, active.elapsed_ms as f32 / duration as f32);
+}
+
+/// Смещение и прозрачность сцен для прогресса от 0 до 1
+fn apply(active: Active, progress: f32) {
+    let eased = Easing::EaseOut.apply(progress);
+
+    match active.transition {
+        Transition::None => {}
+
+        Transition::Slide { .. } => {
+            let width = viewport().width as f32;
+            let side = match active.direction {
+                Direction::Forward => 1.0,
+                Direction::Back => -1.0,
+            };
+
+            let current = (side * width * (1.0 - eased)) as i32;
+            let detached = (-side * width * eased) as i32;
+
+            adapter_command(AdapterCommand::SetSceneOffset(CURRENT_SCENE, (current, 0)));
+            adapter_command(AdapterCommand::SetSceneOffset(
+                DETACHED_SCENE,
+                (detached, 0),
+            ));
+        }
+
+        Transition::Fade { .. } => {
+            let current = (255.0 * eased) as u8;
+
+            adapter_command(AdapterCommand::SetSceneOpacity(CURRENT_SCENE, current));
+            adapter_command(AdapterCommand::SetSceneOpacity(
+                DETACHED_SCENE,
+                255 - current,
+            ));
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use core::fmt::Display;
+
+use super::surface::{Surface, SurfaceState};
+use super::text::TextAlign;
+use super::widget::Widget;
+use crate::layout::{Constraints, Size};
+
+/// Кнопка: прямоугольник фона с тенью и скруглением и подпись по центру. Без width и
+/// height кнопка получает размер по подписи с отступами padding. Цвет фона меняется по
+/// состоянию (наведение, нажатие, выключена), выключенная кнопка не вызывает замыкания
+/// обработчиков
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultButtonSkin {
+    surface: Surface,
+}
+
+impl DefaultButtonSkin {
+    pub fn new(layout: u16) -> Option<Self> {
+        let mut surface = Surface::new(layout, TextAlign::Center, (16, 10), (0, 0), false)?;
+
+        surface.set_color((33, 150, 243, 255));
+        surface.set_label_color((255, 255, 255, 255));
+        surface.set_corner_radius(8);
+        surface.set_shadow(((0, 2), 4, (0, 0, 0, 60)));
+
+        Some(Self { surface })
+    }
+
+    /// Заменяет подпись кнопки, принимает всё что реализует Display
+    pub fn label<T: Display>(&mut self, label: T) -> Self {
+        self.surface.set_label(label);
+        *self
+    }
+
+    pub fn font_size(&mut self, size: u16) -> Self {
+        self.surface.set_font_size(size);
+        *self
+    }
+
+    /// Устанавливает цвет подписи
+    pub fn text_color(self, color: (u8, u8, u8)) -> Self {
+        self.surface
+            .set_label_color((color.0, color.1, color.2, 255));
+        self
+    }
+
+    /// Устанавливает цвет фона, цвета состояний без явного значения вычисляются из него
+    pub fn color(&mut self, color: (u8, u8, u8)) -> Self {
+        self.surface.set_color((color.0, color.1, color.2, 255));
+        *self
+    }
+
+    /// Устанавливает цвет фона с альфа-каналом
+    pub fn color_alpha(&mut self, color: (u8, u8, u8, u8)) -> Self {
+        self.surface.set_color(color);
+        *self
+    }
+
+    pub fn hover_color(&mut self, color: (u8, u8, u8)) -> Self {
+        self.surface
+            .set_hover_color((color.0, color.1, color.2, 255));
+        *self
+    }
+
+    pub fn pressed_color(&mut self, color: (u8, u8, u8)) -> Self {
+        self.surface
+            .set_pressed_color((color.0, color.1, color.2, 255));
+        *self
+    }
+
+    pub fn disabled_color(&mut self, color: (u8, u8, u8)) -> Self {
+        self.surface
+            .set_disabled_color((color.0, color.1, color.2, 255));
+        *self
+    }
+
+    pub fn disabled(&mut self, disabled: bool) -> Self {
+        self.surface.set_disabled(disabled);
+        *self
+    }
+
+    /// Устанавливает скругление всех углов
+    pub fn corner_radius(self, radius: u16) -> Self {
+        self.surface.set_corner_radius(radius);
+        self
+    }
+
+    /// Устанавливает обводку (толщина, RGBA цвет), толщина 0 выключает обводку
+    pub fn border(self, border: (u16, (u8, u8, u8, u8))) -> Self {
+        self.surface.set_border(border);
+        self
+    }
+
+    /// Устанавливает тень (смещение, размытие, RGBA цвет)
+    pub fn shadow(self, shadow: ((i16, i16), u16, (u8, u8, u8, u8))) -> Self {
+        self.surface.set_shadow(shadow);
+        self
+    }
+
+    /// Устанавливает отступ подписи от краёв (по горизонтали, по вертикали)
+    pub fn padding(&mut self, padding: (i32, i32)) -> Self {
+        self.surface.set_padding(padding);
+        *self
+    }
+
+    pub fn width(&mut self, width: i32) -> Self {
+        self.surface.set_width(width);
+        *self
+    }
+
+    pub fn height(&mut self, height: i32) -> Self {
+        self.surface.set_height(height);
+        *self
+    }
+
+    /// Устанавливает позицию кнопки (левый верхний угол)
+    pub fn position(&mut self, position: (i32, i32)) -> Self {
+        self.surface.set_position(position);
+        *self
+    }
+
+    /// Устанавливает Z-индекс
+    pub fn z(&mut self, z: i32) -> Self {
+        self.surface.set_z(z);
+        *self
+    }
+
+    /// Устанавливает видимость кнопки
+    pub fn visible(self, visible: bool) -> Self {
+        self.surface.set_visible(visible);
+        self
+    }
+
+    pub fn state(&self) -> SurfaceState {
+        self.surface.state()
+    }
+
+    pub fn __id(&self) -> usize {
+        self.surface.rect
+    }
+}
+
+impl Widget for DefaultButtonSkin {
+    fn position(&mut self, position: (i32, i32)) {
+        self.surface.set_position(position);
+    }
+
+    fn visible(&self, state: bool) {
+        self.surface.set_visible(state);
+    }
+
+    fn set_z(&mut self, z: i32) {
+        self.surface.set_z(z);
+    }
+
+    fn current_z(&self) -> i32 {
+        self.surface.z
+    }
+
+    fn unmount(self) {
+        self.surface.set_visible(false);
+        self.surface.remove();
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.surface.layout(constraints)
+    }
+
+    fn on_event(&mut self) {
+        self.surface.on_event();
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.surface.is_disabled()
+    }
+
+    fn clip_to(&mut self, clip: usize) {
+        self.surface.clip_to(clip);
+    }
+}
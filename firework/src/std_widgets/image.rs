@@ -0,0 +1,208 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use firework_adapter::{AdapterCommand, AdapterResult};
+
+use super::widget::Widget;
+use crate::adapter_command;
+use crate::layout::{Constraints, Size};
+use crate::runtime::resources::{Texture, free_texture, load_image, load_image_path};
+
+/// Способ вписывания текстуры в размер изображения, значение совпадает с
+/// AdapterCommand::SetImageFit
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ImageFit {
+    /// Целиком внутри с сохранением пропорций
+    #[default]
+    Contain = 0,
+
+    /// Заполняет размер с сохранением пропорций, лишнее обрезается
+    Cover = 1,
+
+    /// Растягивается без сохранения пропорций
+    Fill = 2,
+}
+
+/// Изображение. Текстуру можно загрузить самим виджетом через bytes или path, тогда
+/// виджет владеет ей и освобождает при замене и размонтировании, либо передать готовую
+/// через texture (см. load_image), такую текстуру виджет не освобождает. Размер задаётся
+/// через width и height как у прямоугольника
+///
+/// Скин не Copy, так как владеет текстурой: копия могла бы освободить её второй раз
+#[derive(Debug)]
+pub struct DefaultImageSkin {
+    handle: usize,
+
+    // Текстура которую загрузил сам виджет
+    owned: Option<Texture>,
+
+    // Размер который задал пользователь
+    size: (i32, i32),
+
+    // Размер который последним был отправлен адаптеру
+    measured: (i32, i32),
+
+    // Хэндл по которому обрезается изображение (scroll! {})
+    clip: Option<usize>,
+
+    z: i32,
+    _layout: u16,
+}
+
+impl DefaultImageSkin {
+    pub fn new(layout: u16) -> Option<Self> {
+        match adapter_command(AdapterCommand::NewImage { layout }) {
+            AdapterResult::Handle(handle) => {
+                adapter_command(AdapterCommand::SetHitGroup(handle, crate::TOUCH_HIT_GROUP));
+
+                Some(Self {
+                    handle,
+                    owned: None,
+                    size: (0, 0),
+                    measured: (0, 0),
+                    clip: None,
+                    z: 0,
+                    _layout: layout,
+                })
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Загружает изображение из байтов (например include_bytes!) и показывает его
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        let texture = load_image(bytes);
+        self.set_owned(texture);
+        self
+    }
+
+    /// Загружает изображение по пути и показывает его, путь интерпретирует адаптер
+    pub fn path(&mut self, path: &str) -> &mut Self {
+        let texture = load_image_path(path);
+        self.set_owned(texture);
+        self
+    }
+
+    /// Показывает текстуру которой владеет пользователь
+    pub fn texture(&mut self, texture: Texture) -> &mut Self {
+        self.release_owned();
+        let _ = adapter_command(AdapterCommand::SetImage(self.handle, texture.0));
+        self
+    }
+
+    pub fn fit(&mut self, fit: ImageFit) -> &mut Self {
+        let _ = adapter_command(AdapterCommand::SetImageFit(self.handle, fit as u8));
+        self
+    }
+
+    /// Устанавливает позицию изображения (левый верхний угол)
+    pub fn position(&mut self, position: (i32, i32)) -> &mut Self {
+        let _ = adapter_command(AdapterCommand::SetPosition(self.handle, position));
+        self
+    }
+
+    pub fn width(&mut self, width: i32) -> &mut Self {
+        self.size.0 = width;
+        self.measured = self.size;
+        let _ = adapter_command(AdapterCommand::SetSize(self.handle, self.size));
+        self
+    }
+
+    pub fn height(&mut self, height: i32) -> &mut Self {
+        self.size.1 = height;
+        self.measured = self.size;
+        let _ = adapter_command(AdapterCommand::SetSize(self.handle, self.size));
+        self
+    }
+
+    /// Устанавливает Z-индекс
+    pub fn z(&mut self, z: i32) -> &mut Self {
+        self.z = z;
+        let _ = adapter_command(AdapterCommand::SetZ(self.handle, z));
+        self
+    }
+
+    /// Устанавливает видимость изображения
+    pub fn visible(&self, visible: bool) -> &Self {
+        let _ = adapter_command(AdapterCommand::SetVisible(self.handle, visible));
+        self
+    }
+
+    pub fn __id(&self) -> usize {
+        self.handle
+    }
+
+    /// Заменяет собственную текстуру. Старая освобождается после SetImage чтобы адаптер
+    /// не рисовал освобождённую текстуру. Если загрузка не удалась остаётся старая
+    fn set_owned(&mut self, texture: Option<Texture>) {
+        let Some(texture) = texture else {
+            return;
+        };
+
+        let _ = adapter_command(AdapterCommand::SetImage(self.handle, texture.0));
+        self.release_owned();
+        self.owned = Some(texture);
+    }
+
+    fn release_owned(&mut self) {
+        if let Some(texture) = self.owned.take() {
+            free_texture(texture);
+        }
+    }
+}
+
+impl Widget for DefaultImageSkin {
+    fn position(&mut self, position: (i32, i32)) {
+        DefaultImageSkin::position(self, position);
+    }
+
+    fn visible(&self, state: bool) {
+        DefaultImageSkin::visible(self, state);
+    }
+
+    fn set_z(&mut self, z: i32) {
+        DefaultImageSkin::z(self, z);
+    }
+
+    fn current_z(&self) -> i32 {
+        self.z
+    }
+
+    fn unmount(mut self) {
+        self.visible(false);
+        crate::adapter_command(crate::AdapterCommand::Remove(self.handle));
+        self.release_owned();
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let width = self
+            .size
+            .0
+            .clamp(constraints.min_width, constraints.max_width);
+
+        let height = self
+            .size
+            .1
+            .clamp(constraints.min_height, constraints.max_height);
+
+        if (width, height) != self.measured {
+            let _ = adapter_command(AdapterCommand::SetSize(self.handle, (width, height)));
+            self.measured = (width, height);
+        }
+
+        Size { width, height }
+    }
+
+    fn clip_to(&mut self, clip: usize) {
+        if self.clip != Some(clip) {
+            let _ = adapter_command(AdapterCommand::SetClipTo(self.handle, clip));
+            self.clip = Some(clip);
+        }
+    }
+
+    fn release(&mut self) {
+        self.release_owned();
+    }
+}
@@ -1,9 +1,19 @@
 // Часть проекта Firework с открытым исходным кодом.
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
+pub mod app_bar;
+pub mod button;
+pub mod image;
+mod surface;
+pub mod text;
 pub mod widget;
 
+pub use app_bar::DefaultAppBarSkin;
+pub use button::DefaultButtonSkin;
 use firework_adapter::{AdapterCommand, AdapterResult};
+pub use image::{DefaultImageSkin, ImageFit};
+pub use surface::SurfaceState;
+pub use text::{DefaultTextSkin, TextAlign, TextStyle};
 use widget::Widget;
 
 use crate::adapter_command;
@@ -12,7 +22,18 @@ use crate::layout::{Constraints, Size};
 #[derive(Debug, Clone, Copy)]
 pub struct DefaultRectSkin {
     handle: usize,
+
+    // Размер который задал пользователь
     size: (i32, i32),
+
+    // Размер который последним был отправлен адаптеру, после компоновки он может быть
+    // меньше заданного если прямоугольник не влез в ограничения
+    measured: (i32, i32),
+
+    // Хэндл по которому обрезается прямоугольник (scroll! {}), None если обрезки нет
+    clip: Option<usize>,
+
+    z: i32,
     _layout: u16,
 }
 
@@ -25,6 +46,9 @@ impl DefaultRectSkin {
                 Some(Self {
                     handle,
                     size: (0, 0),
+                    measured: (0, 0),
+                    clip: None,
+                    z: 0,
                     _layout: layout,
                 })
             }
@@ -41,12 +65,14 @@ impl DefaultRectSkin {
 
     pub fn width(&mut self, width: i32) -> Self {
         self.size.0 = width;
+        self.measured = self.size;
         let _ = adapter_command(AdapterCommand::SetSize(self.handle, self.size));
         *self
     }
 
     pub fn height(&mut self, height: i32) -> Self {
         self.size.1 = height;
+        self.measured = self.size;
         let _ = adapter_command(AdapterCommand::SetSize(self.handle, self.size));
         *self
     }
@@ -67,9 +93,10 @@ impl DefaultRectSkin {
     }
 
     /// Устанавливает Z-индекс
-    pub fn z(self, z: i32) -> Self {
+    pub fn z(&mut self, z: i32) -> Self {
+        self.z = z;
         let _ = adapter_command(AdapterCommand::SetZ(self.handle, z));
-        self
+        *self
     }
 
     /// Устанавливает видимость прямоугольника
@@ -90,7 +117,7 @@ impl DefaultRectSkin {
 }
 
 impl Widget for DefaultRectSkin {
-    fn position(&self, position: (i32, i32)) {
+    fn position(&mut self, position: (i32, i32)) {
         DefaultRectSkin::position(*self, position);
     }
 
@@ -98,6 +125,14 @@ impl Widget for DefaultRectSkin {
         DefaultRectSkin::visible(*self, state);
     }
 
+    fn set_z(&mut self, z: i32) {
+        DefaultRectSkin::z(self, z);
+    }
+
+    fn current_z(&self) -> i32 {
+        self.z
+    }
+
     fn unmount(self) {
         self.visible(false);
         crate::adapter_command(crate::AdapterCommand::Remove(self.__id()));
@@ -114,14 +149,20 @@ impl Widget for DefaultRectSkin {
             .1
             .clamp(constraints.min_height, constraints.max_height);
 
-        if width != self.size.0 || height != self.size.1 {
+        // Заданный размер не меняется, поэтому при увеличении ограничений (например при
+        // повороте экрана) прямоугольник вернётся к нему
+        if (width, height) != self.measured {
             let _ = adapter_command(AdapterCommand::SetSize(self.handle, (width, height)));
-            self.size = (width, height);
+            self.measured = (width, height);
         }
 
-        Size {
-            width: self.size.0,
-            height: self.size.1,
+        Size { width, height }
+    }
+
+    fn clip_to(&mut self, clip: usize) {
+        if self.clip != Some(clip) {
+            let _ = adapter_command(AdapterCommand::SetClipTo(self.handle, clip));
+            self.clip = Some(clip);
         }
     }
 }
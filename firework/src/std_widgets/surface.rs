@@ -0,0 +1,386 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use core::fmt::Display;
+
+use firework_adapter::{AdapterClickPhase, AdapterCommand, AdapterResult};
+
+use super::text::{TextAlign, push_plain_text};
+use crate::layout::{Constraints, Size, root_constraints};
+use crate::{CurrentEvent, adapter_command, get_current_event};
+
+/// Визуальное состояние составного скина
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurfaceState {
+    Normal,
+
+    /// Курсор над виджетом (только desktop адаптеры, см. AdapterClickPhase::Moved)
+    Hover,
+
+    /// Палец нажал на виджет и находится над ним
+    Pressed,
+
+    Disabled,
+}
+
+/// Общая часть составных скинов (кнопка, app bar): прямоугольник фона с обводкой, тенью
+/// и скруглением и текст подписи поверх него. Хит группу получает только прямоугольник,
+/// поэтому ResolveHit всегда возвращает его хэндл и подпись не перехватывает касания
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Surface {
+    pub rect: usize,
+    pub label: usize,
+
+    // Левый верхний угол
+    position: (i32, i32),
+
+    // Размер который задал пользователь, 0 по оси означает размер по подписи
+    size: (i32, i32),
+
+    // Размер который последним был отправлен адаптеру
+    measured: (i32, i32),
+
+    // Размер подписи по MeasureText
+    label_size: (i32, i32),
+
+    // Отступ подписи от краёв прямоугольника (по горизонтали, по вертикали)
+    padding: (i32, i32),
+
+    // Растягивать ли ширину на все доступное место (app bar)
+    fill_width: bool,
+
+    // Последние ограничения лайаута, вне лайаута это размер окна
+    constraints: Constraints,
+
+    label_align: TextAlign,
+
+    color: (u8, u8, u8, u8),
+    hover_color: Option<(u8, u8, u8, u8)>,
+    pressed_color: Option<(u8, u8, u8, u8)>,
+    disabled_color: Option<(u8, u8, u8, u8)>,
+
+    // Цвет который сейчас показан, чтобы не отправлять одинаковые SetColor
+    shown_color: (u8, u8, u8, u8),
+
+    disabled: bool,
+    hovered: bool,
+    pressed: bool,
+
+    // Хэндл по которому обрезаются фон и подпись (scroll! {})
+    clip: Option<usize>,
+
+    // Z-индекс фона
+    pub z: i32,
+}
+
+impl Surface {
+    /// Создаёт фон и подпись. Отступ подписи, заданный размер и растяжение по ширине
+    /// передаются сразу чтобы размер был отправлен адаптеру один раз
+    pub fn new(
+        layout: u16,
+        label_align: TextAlign,
+        padding: (i32, i32),
+        size: (i32, i32),
+        fill_width: bool,
+    ) -> Option<Self> {
+        let rect = match adapter_command(AdapterCommand::NewRect { layout }) {
+            AdapterResult::Handle(handle) => handle,
+            _ => return None,
+        };
+
+        let label = match adapter_command(AdapterCommand::NewText { layout }) {
+            AdapterResult::Handle(handle) => handle,
+            _ => {
+                let _ = adapter_command(AdapterCommand::Remove(rect));
+                return None;
+            }
+        };
+
+        adapter_command(AdapterCommand::SetHitGroup(rect, crate::TOUCH_HIT_GROUP));
+        adapter_command(AdapterCommand::SetTextAlign(label, label_align as u8));
+
+        let mut surface = Self {
+            rect,
+            label,
+            position: (0, 0),
+            size,
+            measured: (0, 0),
+            label_size: (0, 0),
+            padding,
+            fill_width,
+            constraints: root_constraints(),
+            label_align,
+            color: (0, 0, 0, 0),
+            hover_color: None,
+            pressed_color: None,
+            disabled_color: None,
+            shown_color: (0, 0, 0, 0),
+            disabled: false,
+            hovered: false,
+            pressed: false,
+            clip: None,
+            z: 0,
+        };
+
+        surface.apply_size();
+        Some(surface)
+    }
+
+    pub fn state(&self) -> SurfaceState {
+        if self.disabled {
+            SurfaceState::Disabled
+        } else if self.pressed && self.hovered {
+            SurfaceState::Pressed
+        } else if self.hovered {
+            SurfaceState::Hover
+        } else {
+            SurfaceState::Normal
+        }
+    }
+
+    /// Цвет фона для текущего состояния. Если цвет состояния не задан он вычисляется из
+    /// основного: нажатие темнее, наведение светлее, выключенный полупрозрачный
+    fn state_color(&self) -> (u8, u8, u8, u8) {
+        let (r, g, b, a) = self.color;
+
+        match self.state() {
+            SurfaceState::Normal => self.color,
+            SurfaceState::Hover => self.hover_color.unwrap_or((
+                r + (255 - r) / 10,
+                g + (255 - g) / 10,
+                b + (255 - b) / 10,
+                a,
+            )),
+            SurfaceState::Pressed => self.pressed_color.unwrap_or((
+                (r as u16 * 4 / 5) as u8,
+                (g as u16 * 4 / 5) as u8,
+                (b as u16 * 4 / 5) as u8,
+                a,
+            )),
+            SurfaceState::Disabled => self.disabled_color.unwrap_or((r, g, b, a / 2)),
+        }
+    }
+
+    fn apply_color(&mut self) {
+        let color = self.state_color();
+
+        if color != self.shown_color {
+            let _ = adapter_command(AdapterCommand::SetColor(self.rect, color));
+            self.shown_color = color;
+        }
+    }
+
+    pub fn set_color(&mut self, color: (u8, u8, u8, u8)) {
+        self.color = color;
+        self.apply_color();
+    }
+
+    pub fn set_hover_color(&mut self, color: (u8, u8, u8, u8)) {
+        self.hover_color = Some(color);
+        self.apply_color();
+    }
+
+    pub fn set_pressed_color(&mut self, color: (u8, u8, u8, u8)) {
+        self.pressed_color = Some(color);
+        self.apply_color();
+    }
+
+    pub fn set_disabled_color(&mut self, color: (u8, u8, u8, u8)) {
+        self.disabled_color = Some(color);
+        self.apply_color();
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+
+        if disabled {
+            self.pressed = false;
+        }
+
+        self.apply_color();
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Заменяет подпись, размер по подписи и её положение пересчитываются. Длина
+    /// подписи ограничена так же как у DefaultTextSkin::content
+    pub fn set_label<T: Display>(&mut self, label: T) {
+        push_plain_text(self.label, label);
+        self.measure_label();
+    }
+
+    pub fn set_font_size(&mut self, size: u16) {
+        let _ = adapter_command(AdapterCommand::SetFontSize(self.label, size));
+        self.measure_label();
+    }
+
+    pub fn set_label_color(&self, color: (u8, u8, u8, u8)) {
+        let _ = adapter_command(AdapterCommand::SetColor(self.label, color));
+    }
+
+    pub fn set_padding(&mut self, padding: (i32, i32)) {
+        self.padding = padding;
+        self.apply_size();
+    }
+
+    pub fn set_width(&mut self, width: i32) {
+        self.size.0 = width;
+        self.apply_size();
+    }
+
+    pub fn set_height(&mut self, height: i32) {
+        self.size.1 = height;
+        self.apply_size();
+    }
+
+    pub fn set_corner_radius(&self, radius: u16) {
+        let _ = adapter_command(AdapterCommand::SetCornerRadius(
+            self.rect,
+            (radius, radius, radius, radius),
+        ));
+    }
+
+    pub fn set_border(&self, border: (u16, (u8, u8, u8, u8))) {
+        let _ = adapter_command(AdapterCommand::SetBorder(self.rect, border.0, border.1));
+    }
+
+    pub fn set_shadow(&self, shadow: ((i16, i16), u16, (u8, u8, u8, u8))) {
+        let _ = adapter_command(AdapterCommand::SetShadow(
+            self.rect, shadow.0, shadow.1, shadow.2,
+        ));
+    }
+
+    /// Подпись всегда на один Z индекс выше фона
+    pub fn set_z(&mut self, z: i32) {
+        self.z = z;
+        let _ = adapter_command(AdapterCommand::SetZ(self.rect, z));
+        let _ = adapter_command(AdapterCommand::SetZ(self.label, z + 1));
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        let _ = adapter_command(AdapterCommand::SetVisible(self.rect, visible));
+        let _ = adapter_command(AdapterCommand::SetVisible(self.label, visible));
+    }
+
+    pub fn set_position(&mut self, position: (i32, i32)) {
+        self.position = position;
+        let _ = adapter_command(AdapterCommand::SetPosition(self.rect, position));
+        self.place_label();
+    }
+
+    pub fn clip_to(&mut self, clip: usize) {
+        if self.clip != Some(clip) {
+            let _ = adapter_command(AdapterCommand::SetClipTo(self.rect, clip));
+            let _ = adapter_command(AdapterCommand::SetClipTo(self.label, clip));
+            self.clip = Some(clip);
+        }
+    }
+
+    pub fn remove(&self) {
+        let _ = adapter_command(AdapterCommand::Remove(self.label));
+        let _ = adapter_command(AdapterCommand::Remove(self.rect));
+    }
+
+    fn measure_label(&mut self) {
+        self.label_size = match adapter_command(AdapterCommand::MeasureText(self.label)) {
+            AdapterResult::Size(width, height) => (width as i32, height as i32),
+            _ => (0, 0),
+        };
+
+        self.apply_size();
+    }
+
+    /// Размер с учётом размера по подписи, заполнения и ограничений
+    fn resolve_size(&self) -> (i32, i32) {
+        let width = if self.fill_width {
+            self.constraints.max_width
+        } else if self.size.0 > 0 {
+            self.size.0
+        } else {
+            self.label_size.0 + self.padding.0 * 2
+        };
+
+        let height = if self.size.1 > 0 {
+            self.size.1
+        } else {
+            self.label_size.1 + self.padding.1 * 2
+        };
+
+        (
+            width.clamp(self.constraints.min_width, self.constraints.max_width),
+            height.clamp(self.constraints.min_height, self.constraints.max_height),
+        )
+    }
+
+    fn apply_size(&mut self) {
+        let size = self.resolve_size();
+
+        if size != self.measured {
+            let _ = adapter_command(AdapterCommand::SetSize(self.rect, size));
+            self.measured = size;
+            self.place_label();
+        }
+    }
+
+    /// Подпись центрируется по вертикали, по горизонтали она ставится по выравниванию
+    fn place_label(&self) {
+        let (width, height) = self.measured;
+        let (label_width, label_height) = self.label_size;
+
+        let x = match self.label_align {
+            TextAlign::Left => self.padding.0,
+            TextAlign::Center => (width - label_width) / 2,
+            TextAlign::Right => width - label_width - self.padding.0,
+        };
+        let y = (height - label_height) / 2;
+
+        let _ = adapter_command(AdapterCommand::SetPosition(
+            self.label,
+            (self.position.0 + x, self.position.1 + y),
+        ));
+    }
+
+    pub fn layout(&mut self, constraints: Constraints) -> Size {
+        self.constraints = constraints;
+        self.apply_size();
+
+        Size {
+            width: self.measured.0,
+            height: self.measured.1,
+        }
+    }
+
+    /// Обновляет визуальное состояние по текущему касанию
+    pub fn on_event(&mut self) {
+        let CurrentEvent::Touch {
+            hit_object_id,
+            phase,
+            ..
+        } = get_current_event()
+        else {
+            return;
+        };
+
+        let over = hit_object_id == Some(self.rect);
+
+        match phase {
+            AdapterClickPhase::Began => {
+                self.pressed = over && !self.disabled;
+                self.hovered = over;
+            }
+
+            AdapterClickPhase::Moved => self.hovered = over,
+
+            // После отпускания наведения нет, на сенсорном экране курсора не существует,
+            // а desktop адаптер вернёт наведение следующим Moved
+            AdapterClickPhase::Ended | AdapterClickPhase::Cancelled => {
+                self.pressed = false;
+                self.hovered = false;
+            }
+        }
+
+        self.apply_color();
+    }
+}
@@ -0,0 +1,301 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use core::fmt::Display;
+#[cfg(feature = "no-alloc")]
+use core::fmt::Write;
+
+use firework_adapter::{AdapterCommand, AdapterResult};
+
+use super::widget::Widget;
+use crate::adapter_command;
+use crate::layout::{Constraints, Size, root_constraints};
+use crate::runtime::resources::Font;
+
+/// Максимальная длина текста в байтах для content в режиме no-alloc. Без кучи текст
+/// форматируется в буфер на стэке и всё что не влезло обрезается, с кучей длина не
+/// ограничена
+pub const TEXT_BUFFER_SIZE: usize = 256;
+
+/// Стиль сегмента текста, значение совпадает с режимом в AdapterCommand::PushText
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextStyle {
+    #[default]
+    Regular = 0,
+    Bold = 1,
+    Italic = 2,
+    BoldItalic = 3,
+}
+
+/// Выравнивание текста, значение совпадает с AdapterCommand::SetTextAlign
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left = 0,
+    Center = 1,
+    Right = 2,
+}
+
+/// Буфер на стэке для форматирования content через Display
+#[cfg(feature = "no-alloc")]
+struct TextBuffer {
+    bytes: [u8; TEXT_BUFFER_SIZE],
+    len: usize,
+}
+
+#[cfg(feature = "no-alloc")]
+impl TextBuffer {
+    fn new() -> Self {
+        Self {
+            bytes: [0; TEXT_BUFFER_SIZE],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+}
+
+#[cfg(feature = "no-alloc")]
+impl Write for TextBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        // Обрезка по границе символа чтобы буфер всегда оставался валидным UTF-8
+        let mut end = s.len().min(TEXT_BUFFER_SIZE - self.len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        self.bytes[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+
+        Ok(())
+    }
+}
+
+/// Заменяет текст объекта handle одним обычным сегментом из Display
+#[cfg(not(feature = "no-alloc"))]
+pub(crate) fn push_plain_text<T: Display>(handle: usize, content: T) {
+    let text = content.to_string();
+    push_plain_str(handle, &text);
+}
+
+/// В no-alloc текст длиннее TEXT_BUFFER_SIZE обрезается по границе символа
+#[cfg(feature = "no-alloc")]
+pub(crate) fn push_plain_text<T: Display>(handle: usize, content: T) {
+    let mut buffer = TextBuffer::new();
+    let _ = write!(buffer, "{}", content);
+    push_plain_str(handle, buffer.as_str());
+}
+
+fn push_plain_str(handle: usize, text: &str) {
+    let _ = adapter_command(AdapterCommand::ClearText(handle));
+    let _ = adapter_command(AdapterCommand::PushText {
+        handle,
+        text,
+        mode: TextStyle::Regular as u8,
+    });
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultTextSkin {
+    handle: usize,
+
+    // Переносить ли текст по ширине которую лайаут отдал тексту
+    wrap: bool,
+
+    // Ширина переноса которая последней была отправлена адаптеру
+    wrap_width: u32,
+
+    // Хэндл по которому обрезается текст (scroll! {})
+    clip: Option<usize>,
+
+    z: i32,
+    _layout: u16,
+}
+
+impl DefaultTextSkin {
+    pub fn new(layout: u16) -> Option<Self> {
+        match adapter_command(AdapterCommand::NewText { layout }) {
+            AdapterResult::Handle(handle) => {
+                adapter_command(AdapterCommand::SetHitGroup(handle, crate::TOUCH_HIT_GROUP));
+
+                Some(Self {
+                    handle,
+                    wrap: false,
+                    wrap_width: 0,
+                    clip: None,
+                    z: 0,
+                    _layout: layout,
+                })
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Заменяет текст одним обычным сегментом. Принимает всё что реализует Display,
+    /// поэтому можно передать как строку так и число. В no-alloc длина ограничена
+    /// TEXT_BUFFER_SIZE байтами
+    pub fn content<T: Display>(self, content: T) -> Self {
+        push_plain_text(self.handle, content);
+        self
+    }
+
+    /// Заменяет текст набором сегментов со своим стилем
+    pub fn spans(self, spans: &[(&str, TextStyle)]) -> Self {
+        let _ = adapter_command(AdapterCommand::ClearText(self.handle));
+
+        for (text, style) in spans {
+            let _ = adapter_command(AdapterCommand::PushText {
+                handle: self.handle,
+                text,
+                mode: *style as u8,
+            });
+        }
+        self
+    }
+
+    pub fn align(self, align: TextAlign) -> Self {
+        let _ = adapter_command(AdapterCommand::SetTextAlign(self.handle, align as u8));
+        self
+    }
+
+    /// Включает перенос по словам. Ширина переноса берётся из ограничений лайаута, вне
+    /// лайаута это ширина окна
+    pub fn wrap(&mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+
+        let width = if wrap {
+            root_constraints().max_width.max(0) as u32
+        } else {
+            0
+        };
+
+        if width != self.wrap_width {
+            let _ = adapter_command(AdapterCommand::SetTextWrapWidth(self.handle, width));
+            self.wrap_width = width;
+        }
+
+        *self
+    }
+
+    /// Устанавливает шрифт из load_font
+    pub fn font(self, font: Font) -> Self {
+        let _ = adapter_command(AdapterCommand::SetFont(self.handle, font.0));
+        self
+    }
+
+    pub fn font_size(self, size: u16) -> Self {
+        let _ = adapter_command(AdapterCommand::SetFontSize(self.handle, size));
+        self
+    }
+
+    /// Устанавливает позицию текста (левый верхний угол)
+    pub fn position(self, position: (i32, i32)) -> Self {
+        let _ = adapter_command(AdapterCommand::SetPosition(self.handle, position));
+        self
+    }
+
+    /// Устанавливает цвет текста
+    pub fn color(self, color: (u8, u8, u8)) -> Self {
+        let _ = adapter_command(AdapterCommand::SetColor(
+            self.handle,
+            (color.0, color.1, color.2, 255),
+        ));
+        self
+    }
+
+    /// Устанавливает цвет текста с альфа-каналом
+    pub fn color_alpha(self, color: (u8, u8, u8, u8)) -> Self {
+        let _ = adapter_command(AdapterCommand::SetColor(self.handle, color));
+        self
+    }
+
+    /// Устанавливает Z-индекс
+    pub fn z(&mut self, z: i32) -> Self {
+        self.z = z;
+        let _ = adapter_command(AdapterCommand::SetZ(self.handle, z));
+        *self
+    }
+
+    /// Устанавливает видимость текста
+    pub fn visible(self, visible: bool) -> Self {
+        let _ = adapter_command(AdapterCommand::SetVisible(self.handle, visible));
+        self
+    }
+
+    pub fn __id(&self) -> usize {
+        self.handle
+    }
+}
+
+impl Widget for DefaultTextSkin {
+    fn position(&mut self, position: (i32, i32)) {
+        DefaultTextSkin::position(*self, position);
+    }
+
+    fn visible(&self, state: bool) {
+        DefaultTextSkin::visible(*self, state);
+    }
+
+    fn set_z(&mut self, z: i32) {
+        DefaultTextSkin::z(self, z);
+    }
+
+    fn current_z(&self) -> i32 {
+        self.z
+    }
+
+    fn unmount(self) {
+        self.visible(false);
+        crate::adapter_command(crate::AdapterCommand::Remove(self.__id()));
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        // Перенос зависит от ширины поэтому он выставляется до измерения
+        if self.wrap {
+            let width = constraints.max_width.max(0) as u32;
+
+            if width != self.wrap_width {
+                let _ = adapter_command(AdapterCommand::SetTextWrapWidth(self.handle, width));
+                self.wrap_width = width;
+            }
+        }
+
+        let (width, height) = match adapter_command(AdapterCommand::MeasureText(self.handle)) {
+            AdapterResult::Size(width, height) => (width as i32, height as i32),
+            _ => (0, 0),
+        };
+
+        Size {
+            width: width.clamp(constraints.min_width, constraints.max_width),
+            height: height.clamp(constraints.min_height, constraints.max_height),
+        }
+    }
+
+    fn clip_to(&mut self, clip: usize) {
+        if self.clip != Some(clip) {
+            let _ = adapter_command(AdapterCommand::SetClipTo(self.handle, clip));
+            self.clip = Some(clip);
+        }
+    }
+}
+
+// Буфер есть только в no-alloc
+#[cfg(all(test, feature = "no-alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_buffer_truncates_on_char_boundary() {
+        let mut buffer = TextBuffer::new();
+        let _ = write!(buffer, "{}", "a".repeat(TEXT_BUFFER_SIZE - 1));
+        let _ = write!(buffer, "ё");
+
+        // Двухбайтовый символ не влез целиком и был отброшен
+        assert_eq!(buffer.as_str().len(), TEXT_BUFFER_SIZE - 1);
+    }
+}
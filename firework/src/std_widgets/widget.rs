@@ -6,8 +6,39 @@ use crate::layout::{Constraints, Size};
 /// Трейт который должны реализовать все скины для поддержки видимости в списках. Он
 /// гарантирует наличие метода visible
 pub trait Widget {
-    fn position(&self, position: (i32, i32));
+    /// Принимает &mut self так как составные скины (несколько примитивов) запоминают
+    /// позицию чтобы расставлять свои примитивы относительно неё при смене размера
+    fn position(&mut self, position: (i32, i32));
     fn visible(&self, state: bool);
     fn unmount(self);
     fn layout(&mut self, constraints: Constraints) -> Size;
+
+    /// Вызывается в фазе Event для каждого события до замыканий обработчиков. Скины с
+    /// визуальными состояниями (нажатие, наведение) читают здесь текущее событие
+    fn on_event(&mut self) {}
+
+    /// Выключенный виджет не вызывает замыкания обработчиков событий
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Обрезает примитивы виджета по форме объекта clip (SetClipTo). Вызывается
+    /// компоновкой для детей scroll! {} при каждом размещении, поэтому скин должен сам
+    /// пропускать повторную отправку того же хэндла
+    fn clip_to(&mut self, _clip: usize) {}
+
+    /// Z-индекс который выставляет список цикла в stack! переставленному элементу чтобы
+    /// порядок отрисовки совпадал с порядком элементов, см. DynList::order_z
+    fn set_z(&mut self, _z: i32) {}
+
+    /// Z-индекс который сейчас у скина: заданный пропсом z или последний set_z. По нему
+    /// список ставит переставленные элементы между соседями не трогая остальные
+    fn current_z(&self) -> i32 {
+        0
+    }
+
+    /// Освобождает ресурсы адаптера которые не удаляет RemoveAll, например загруженные
+    /// скином текстуры. Вызывается когда экран сбрасывает скин без unmount (Navigate,
+    /// #[ui(reset_on_enter)], #[ui(drop_on_leave)]), примитивы к этому моменту уже удалены
+    fn release(&mut self) {}
 }
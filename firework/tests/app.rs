@@ -0,0 +1,55 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+use firework_adapter::TestCommand;
+use firework_ui::{AdapterCommand, AdapterResult, App, null_adapter, ui};
+use std::sync::Mutex;
+
+static RUN_LOOP: Mutex<Option<TestCommand>> = Mutex::new(None);
+
+fn adapter(command: AdapterCommand) -> AdapterResult {
+    if let AdapterCommand::RunLoop { .. } = command {
+        *RUN_LOOP.lock().unwrap() = Some(TestCommand::from(command));
+    }
+
+    null_adapter(command)
+}
+
+#[ui]
+fn test_app_screen() {
+    rect! {}
+}
+
+#[test]
+fn test_app_run_loop_config() {
+    App::new("Kiosk")
+        .size(1080, 1920)
+        .min_size(540, 960)
+        .resizable(false)
+        .fps(30)
+        .background((10, 20, 30, 255))
+        .run_with_adapter(adapter, test_app_screen);
+
+    let Some(TestCommand::RunLoop {
+        title,
+        width,
+        height,
+        min_size,
+        max_size,
+        resizable,
+        fps,
+        background,
+        ..
+    }) = RUN_LOOP.lock().unwrap().clone()
+    else {
+        panic!("RunLoop was not sent");
+    };
+
+    assert_eq!(title, "Kiosk");
+    assert_eq!((width, height), (1080, 1920));
+    assert_eq!(min_size, Some((540, 960)));
+    assert_eq!(max_size, None);
+    assert!(!resizable);
+    assert_eq!(fps, 30);
+    assert_eq!(background, (10, 20, 30, 255));
+}
@@ -0,0 +1,344 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+#![cfg(feature = "async")]
+
+mod common;
+
+use crate::common::{TestHarness, TestStep};
+use firework_adapter::TestCommand;
+use firework_ui::runtime::async_spark::AsyncSpark;
+use firework_ui::{AdapterClickPhase, AsyncValue, CurrentEvent, LocalExecutor, set_spawner, ui};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+type AdapterCommand = TestCommand;
+
+// Тесты идут на встроенном исполнителе (LocalExecutor), он опрашивает задачи в начале
+// каждого кадра, поэтому значение приходит в предсказуемый момент. Исполнитель
+// устанавливается явно так как с фичей tokio по умолчанию используется TokioSpawner
+fn local() {
+    set_spawner(&LocalExecutor);
+}
+
+#[ui]
+fn test_async_spark_ready_screen() {
+    let mut red: u8 = spark!(0, async move |mut bridge| {
+        *bridge = 50;
+        *bridge = 100;
+    });
+
+    rect! {
+        color: (red, 0, 0),
+    }
+}
+
+#[test]
+fn test_async_spark_ready() {
+    local();
+
+    let commands = TestHarness::run_with_steps(
+        test_async_spark_ready_screen,
+        &[
+            // Оба значения пришли за один кадр, в спарк попадает последнее
+            TestStep::Tick(16),
+            TestStep::Tick(16),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![AdapterCommand::SetColor(0, (100, 0, 0, 255))]
+    );
+}
+
+#[ui]
+fn test_async_spark_ready_then_error_screen() {
+    let mut red: u8 = spark!(0, async move |bridge| {
+        bridge.send(100);
+        bridge.error();
+    });
+
+    rect! {
+        color: (red, 0, 0),
+    }
+}
+
+#[test]
+fn test_async_spark_ready_then_error() {
+    local();
+
+    let commands = TestHarness::run_with_steps(
+        test_async_spark_ready_then_error_screen,
+        &[TestStep::Tick(16)],
+    );
+
+    // Ошибка после значения в том же кадре не отменяет значение, спарку с типом T
+    // ошибки не видны
+    assert_eq!(
+        commands,
+        vec![AdapterCommand::SetColor(0, (100, 0, 0, 255))]
+    );
+}
+
+#[ui]
+fn test_async_spark_status_screen() {
+    let mut user: AsyncValue<u8> = spark!(AsyncValue::Loading, async move |bridge| {
+        bridge.error_message("offline");
+    });
+
+    if user.is_loading() {
+        rect! {
+            color: (0, 0, 255),
+        }
+    }
+
+    if user
+        .error()
+        .is_some_and(|error| error.message() == Some("offline"))
+    {
+        rect! {
+            color: (255, 0, 0),
+        }
+    }
+}
+
+#[test]
+fn test_async_spark_status() {
+    local();
+
+    let commands =
+        TestHarness::run_with_steps(test_async_spark_status_screen, &[TestStep::Tick(16)]);
+
+    // Спиннер скрывается, появляется баннер с ошибкой
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetColor(0, (255, 0, 0, 255)),
+            AdapterCommand::SetVisible(0, false),
+            AdapterCommand::SetVisible(0, true),
+        ]
+    );
+}
+
+static RESTART_RUNS: AtomicU8 = AtomicU8::new(0);
+
+#[ui]
+fn test_async_spark_restart_screen() {
+    let mut red: u8 = spark!(0, async move |bridge| {
+        let run = RESTART_RUNS.fetch_add(1, Ordering::Relaxed) + 1;
+        bridge.send(run * 50);
+    });
+
+    rect! {
+        color: (red, 0, 0),
+        on_click: || restart!(red),
+    }
+}
+
+#[test]
+fn test_async_spark_restart() {
+    local();
+
+    let commands = TestHarness::run_with_steps(
+        test_async_spark_restart_screen,
+        &[
+            TestStep::Tick(16),
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            // Задача создаётся заново на следующем кадре и выполняется на кадре после
+            TestStep::Tick(16),
+            TestStep::Tick(16),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (50, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (100, 0, 0, 255)),
+        ]
+    );
+}
+
+#[ui]
+fn test_async_spark_effect_screen() {
+    let mut query: u8 = spark!(1);
+    let mut doubled: u8 = spark!(0, async move |bridge, query: u8| {
+        bridge.send(query * 2);
+    });
+
+    effect!(query, {
+        restart!(doubled);
+    });
+
+    rect! {
+        color: (doubled, 0, 0),
+        on_click: || query += 10,
+    }
+}
+
+#[test]
+fn test_async_spark_effect() {
+    local();
+
+    let commands = TestHarness::run_with_steps(
+        test_async_spark_effect_screen,
+        &[
+            TestStep::Tick(16),
+            // Задача перезапускается с новым значением query
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            TestStep::Tick(16),
+            TestStep::Tick(16),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (2, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (22, 0, 0, 255)),
+        ]
+    );
+}
+
+#[ui]
+fn test_async_spark_sleep_screen() {
+    let mut red: u8 = spark!(0, async move |bridge| {
+        bridge.sleep_ms(50).await;
+        bridge.send(100);
+    });
+
+    rect! {
+        color: (red, 0, 0),
+    }
+}
+
+#[test]
+fn test_async_spark_sleep() {
+    local();
+
+    // Таймер идёт по часам тика: задача начинает ждать на первом кадре и просыпается
+    // на кадре где прошло 50 мс
+    let commands =
+        TestHarness::run_with_steps(test_async_spark_sleep_screen, &[TestStep::Tick(16); 4]);
+
+    assert!(commands.is_empty());
+
+    let commands =
+        TestHarness::run_with_steps(test_async_spark_sleep_screen, &[TestStep::Tick(16); 5]);
+
+    assert_eq!(
+        commands,
+        vec![AdapterCommand::SetColor(0, (100, 0, 0, 255))]
+    );
+}
+
+static CANCEL_SPARK: Mutex<Option<AsyncSpark<u8>>> = Mutex::new(None);
+static CANCEL_DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct CancelGuard;
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        CANCEL_DROPPED.store(true, Ordering::Relaxed);
+    }
+}
+
+#[ui]
+fn test_async_spark_cancel_screen() {
+    rect! {
+        color: (0, 0, 0),
+    }
+}
+
+fn create_pending_task() {
+    let mut spark = AsyncSpark::<u8>::new();
+    spark.create(|_bridge| async move {
+        let _guard = CancelGuard;
+        std::future::pending::<()>().await;
+    });
+
+    *CANCEL_SPARK.lock().unwrap() = Some(spark);
+}
+
+fn assert_task_running() {
+    let spark = CANCEL_SPARK.lock().unwrap();
+
+    assert!(spark.as_ref().is_some_and(|spark| spark.is_running()));
+    assert!(!CANCEL_DROPPED.load(Ordering::Relaxed));
+}
+
+fn drop_task() {
+    CANCEL_SPARK.lock().unwrap().take();
+}
+
+#[test]
+fn test_async_spark_cancel_on_drop() {
+    local();
+
+    TestHarness::run_with_steps(
+        test_async_spark_cancel_screen,
+        &[
+            TestStep::Call(create_pending_task),
+            TestStep::Tick(16),
+            TestStep::Call(assert_task_running),
+            // Задача отменяется вместе со спарком и удаляется исполнителем на кадре
+            TestStep::Call(drop_task),
+            TestStep::Tick(16),
+        ],
+    );
+
+    assert!(CANCEL_DROPPED.load(Ordering::Relaxed));
+}
+
+static PENDING_SPARK: Mutex<Option<AsyncSpark<u8>>> = Mutex::new(None);
+
+fn create_finished_task() {
+    let mut spark = AsyncSpark::<u8>::new();
+    spark.create(|mut bridge| async move {
+        *bridge = 5;
+    });
+
+    assert!(spark.is_pending());
+    *PENDING_SPARK.lock().unwrap() = Some(spark);
+}
+
+fn assert_pending_until_delivered() {
+    let mut guard = PENDING_SPARK.lock().unwrap();
+    let spark = guard.as_mut().unwrap();
+
+    // Задача уже завершилась, но её значение ещё не записано в спарк
+    assert!(!spark.is_running());
+    assert!(spark.is_pending());
+
+    spark.poll_ready();
+    assert_eq!(spark.take_ready(), Some(5));
+    assert!(!spark.is_pending());
+}
+
+#[test]
+fn test_async_spark_pending() {
+    local();
+
+    TestHarness::run_with_steps(
+        test_async_spark_cancel_screen,
+        &[
+            TestStep::Call(create_finished_task),
+            TestStep::Tick(16),
+            TestStep::Call(assert_pending_until_delivered),
+        ],
+    );
+}
+
+fn touch(phase: AdapterClickPhase, hit_object_id: Option<usize>) -> CurrentEvent {
+    CurrentEvent::Touch {
+        x: 5,
+        y: 5,
+        hit_object_id,
+        phase,
+    }
+}
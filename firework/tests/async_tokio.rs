@@ -0,0 +1,97 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use crate::common::{TestHarness, TestStep};
+use firework_adapter::TestCommand;
+use firework_ui::runtime::async_spark::AsyncSpark;
+use firework_ui::{TokioSpawner, set_spawner, ui};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::runtime::{Builder, Runtime};
+
+type AdapterCommand = TestCommand;
+
+static TOKIO: TokioSpawner = TokioSpawner;
+
+// Однопоточный рантайм выполняет задачи только когда его прокручивают, поэтому значение
+// приходит в предсказуемый момент
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+    RUNTIME.get_or_init(|| {
+        set_spawner(&TOKIO);
+        Builder::new_current_thread().enable_time().build().unwrap()
+    })
+}
+
+fn drive() {
+    runtime().block_on(tokio::task::yield_now());
+}
+
+#[ui]
+fn test_tokio_spark_ready_screen() {
+    let mut red: u8 = spark!(0, async move |mut bridge| {
+        *bridge = 50;
+        *bridge = 100;
+    });
+
+    rect! {
+        color: (red, 0, 0),
+    }
+}
+
+#[test]
+fn test_tokio_spark_ready() {
+    let _enter = runtime().enter();
+
+    let commands = TestHarness::run_with_steps(
+        test_tokio_spark_ready_screen,
+        &[
+            // Задача ещё не выполнялась
+            TestStep::Tick(16),
+            TestStep::Call(drive),
+            // Оба значения пришли за один кадр, в спарк попадает последнее
+            TestStep::Tick(16),
+            TestStep::Tick(16),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![AdapterCommand::SetColor(0, (100, 0, 0, 255))]
+    );
+}
+
+#[test]
+fn test_tokio_spark_cancel_on_drop() {
+    static DROPPED: AtomicBool = AtomicBool::new(false);
+
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            DROPPED.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let _enter = runtime().enter();
+
+    let mut spark = AsyncSpark::<u8>::new();
+    spark.create(|_bridge| async move {
+        let _guard = Guard;
+        std::future::pending::<()>().await;
+    });
+
+    drive();
+    assert!(spark.is_running());
+    assert!(!DROPPED.load(Ordering::Relaxed));
+
+    // Задача отменяется вместе со спарком
+    drop(spark);
+    drive();
+    assert!(DROPPED.load(Ordering::Relaxed));
+}
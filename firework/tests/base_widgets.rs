@@ -5,7 +5,7 @@ mod common;
 
 use crate::common::TestHarness;
 use firework_adapter::TestCommand;
-use firework_ui::ui;
+use firework_ui::{AdapterClickPhase, CurrentEvent, ImageFit, TextAlign, TextStyle, ui};
 
 type AdapterCommand = TestCommand;
 
@@ -135,3 +135,357 @@ fn test_ui_rect_sugar() {
         ]
     );
 }
+
+#[ui]
+fn test_ui_text_screen() {
+    let mut count = spark!(0u32);
+
+    text! {
+        content: count,
+        align: TextAlign::Center,
+        font_size: 18,
+        wrap: true,
+        on_click: || count += 1,
+    }
+}
+
+#[test]
+fn test_ui_text() {
+    let commands = TestHarness::run(test_ui_text_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewText { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::ClearText(0),
+            AdapterCommand::PushText {
+                handle: 0,
+                text: "0".to_string(),
+                mode: 0,
+            },
+            AdapterCommand::SetTextAlign(0, 1),
+            AdapterCommand::SetFontSize(0, 18),
+            // Вне лайаута ширина переноса равна ширине окна
+            AdapterCommand::SetTextWrapWidth(0, 720),
+        ]
+    );
+}
+
+#[test]
+fn test_ui_text_reactive_content() {
+    let touch = |phase| CurrentEvent::Touch {
+        x: 0,
+        y: 0,
+        hit_object_id: Some(0),
+        phase,
+    };
+
+    let commands = TestHarness::run_with_events(
+        test_ui_text_screen,
+        &[
+            touch(AdapterClickPhase::Began),
+            touch(AdapterClickPhase::Ended),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::ClearText(0),
+            AdapterCommand::PushText {
+                handle: 0,
+                text: "1".to_string(),
+                mode: 0,
+            },
+        ]
+    );
+}
+
+#[ui]
+fn test_ui_text_long_screen() {
+    text! {
+        content: "a".repeat(300),
+    }
+
+    button! {
+        label: "b".repeat(300),
+    }
+}
+
+#[test]
+fn test_ui_text_long() {
+    let commands = TestHarness::run(test_ui_text_long_screen);
+
+    let pushed: Vec<_> = commands
+        .iter()
+        .filter_map(|command| match command {
+            AdapterCommand::PushText { text, .. } => Some(text.len()),
+            _ => None,
+        })
+        .collect();
+
+    // С кучей текст и подпись кнопки уходят целиком, в no-alloc обрезаются по буферу
+    // в 256 байт
+    #[cfg(not(feature = "no-alloc"))]
+    assert_eq!(pushed, [300, 300]);
+
+    #[cfg(feature = "no-alloc")]
+    assert_eq!(pushed, [256, 256]);
+}
+
+#[ui]
+fn test_ui_text_layout_screen() {
+    vertical! {
+        layout! {
+            padding: (0, 10, 0, 10),
+        }
+
+        text! {
+            spans: &[("Bold ", TextStyle::Bold), ("italic", TextStyle::Italic)],
+            wrap: true,
+        }
+    }
+}
+
+#[test]
+fn test_ui_text_layout() {
+    let commands = TestHarness::run(test_ui_text_layout_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewText { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::ClearText(0),
+            AdapterCommand::PushText {
+                handle: 0,
+                text: "Bold ".to_string(),
+                mode: 1,
+            },
+            AdapterCommand::PushText {
+                handle: 0,
+                text: "italic".to_string(),
+                mode: 2,
+            },
+            AdapterCommand::SetTextWrapWidth(0, 720),
+            // Лайаут переносит текст по своей ширине без padding и измеряет его
+            AdapterCommand::SetTextWrapWidth(0, 700),
+            AdapterCommand::MeasureText(0),
+            AdapterCommand::SetPosition(0, (10, 0)),
+        ]
+    );
+}
+
+#[ui]
+fn test_ui_button_screen() {
+    let mut count = spark!(0u8);
+
+    button! {
+        label: count,
+        disabled: count >= 2,
+        on_click: || count += 1,
+    }
+}
+
+#[test]
+fn test_ui_button_states() {
+    let touch = |phase, hit| CurrentEvent::Touch {
+        x: 0,
+        y: 0,
+        hit_object_id: hit,
+        phase,
+    };
+
+    let pressed = AdapterCommand::SetColor(0, (26, 120, 194, 255));
+    let normal = AdapterCommand::SetColor(0, (33, 150, 243, 255));
+    let label = |text: &str| {
+        [
+            AdapterCommand::ClearText(0),
+            AdapterCommand::PushText {
+                handle: 0,
+                text: text.to_string(),
+                mode: 0,
+            },
+            AdapterCommand::MeasureText(0),
+        ]
+    };
+
+    let commands = TestHarness::run_with_events(
+        test_ui_button_screen,
+        &[
+            touch(AdapterClickPhase::Began, Some(0)),
+            // Палец ушёл с кнопки и вернулся
+            touch(AdapterClickPhase::Moved, None),
+            touch(AdapterClickPhase::Moved, Some(0)),
+            touch(AdapterClickPhase::Ended, Some(0)),
+            touch(AdapterClickPhase::Began, Some(0)),
+            touch(AdapterClickPhase::Ended, Some(0)),
+            // Кнопка выключена и больше не реагирует
+            touch(AdapterClickPhase::Began, Some(0)),
+            touch(AdapterClickPhase::Ended, Some(0)),
+        ],
+    );
+
+    let mut expected = vec![
+        pressed.clone(),
+        normal.clone(),
+        pressed.clone(),
+        normal.clone(),
+    ];
+    expected.extend(label("1"));
+    expected.extend([pressed, normal]);
+    expected.extend(label("2"));
+    expected.push(AdapterCommand::SetColor(0, (33, 150, 243, 127)));
+
+    assert_eq!(commands, expected);
+}
+
+#[ui]
+fn test_ui_app_bar_screen() {
+    vertical! {
+        app_bar! {
+            title: "Firework",
+        }
+    }
+}
+
+#[test]
+fn test_ui_app_bar() {
+    let commands = TestHarness::run(test_ui_app_bar_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::NewText { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetTextAlign(0, 0),
+            // Панель растягивается на ширину окна
+            AdapterCommand::SetSize(0, (720, 56)),
+            AdapterCommand::SetPosition(0, (16, 28)),
+            AdapterCommand::SetColor(0, (33, 150, 243, 255)),
+            AdapterCommand::SetColor(0, (255, 255, 255, 255)),
+            AdapterCommand::SetShadow(0, (0, 2), 6, (0, 0, 0, 80)),
+            AdapterCommand::ClearText(0),
+            AdapterCommand::PushText {
+                handle: 0,
+                text: "Firework".to_string(),
+                mode: 0,
+            },
+            AdapterCommand::MeasureText(0),
+            // Компоновка
+            AdapterCommand::SetPosition(0, (0, 0)),
+            AdapterCommand::SetPosition(0, (16, 28)),
+        ]
+    );
+}
+
+#[ui]
+fn test_ui_image_screen() {
+    let mut dark = spark!(false);
+
+    image! {
+        bytes: b"\x89PNG",
+        fit: ImageFit::Cover,
+        width: 120,
+        height: 80,
+    }
+
+    image! {
+        path: if dark { "logo_dark.png" } else { "logo.png" },
+        on_click: || dark = !dark,
+    }
+}
+
+#[test]
+fn test_ui_image() {
+    let commands = TestHarness::run(test_ui_image_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewImage { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::LoadImage {
+                bytes: b"\x89PNG".to_vec(),
+            },
+            AdapterCommand::SetImage(0, 0),
+            AdapterCommand::SetImageFit(0, 1),
+            AdapterCommand::SetSize(0, (120, 0)),
+            AdapterCommand::SetSize(0, (120, 80)),
+            AdapterCommand::NewImage { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::LoadImageFromPath {
+                path: "logo.png".to_string(),
+            },
+            AdapterCommand::SetImage(0, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_ui_image_replace_frees_texture() {
+    let touch = |phase| CurrentEvent::Touch {
+        x: 0,
+        y: 0,
+        hit_object_id: Some(0),
+        phase,
+    };
+
+    let commands = TestHarness::run_with_events(
+        test_ui_image_screen,
+        &[
+            touch(AdapterClickPhase::Began),
+            touch(AdapterClickPhase::Ended),
+        ],
+    );
+
+    // Новая текстура показывается до освобождения старой
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::LoadImageFromPath {
+                path: "logo_dark.png".to_string(),
+            },
+            AdapterCommand::SetImage(0, 0),
+            AdapterCommand::FreeTexture(0),
+        ]
+    );
+}
+
+#[ui]
+fn test_ui_text_font_screen() {
+    text! {
+        content: "Firework",
+        font: firework_ui::load_font(b"\0\x01\0\0").unwrap(),
+    }
+}
+
+#[test]
+fn test_ui_text_font() {
+    let commands = TestHarness::run(test_ui_text_font_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewText { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::ClearText(0),
+            AdapterCommand::PushText {
+                handle: 0,
+                text: "Firework".to_string(),
+                mode: 0,
+            },
+            AdapterCommand::LoadFont {
+                bytes: b"\0\x01\0\0".to_vec(),
+            },
+            AdapterCommand::SetFont(0, 0),
+        ]
+    );
+}
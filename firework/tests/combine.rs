@@ -547,6 +547,7 @@ fn test_combine_else_if_routing() {
 }
 
 #[ui]
+#[allow(clippy::collapsible_match)]
 fn test_combine_match_routing_screen() {
     let mut status = spark!(0);
     let mut count = spark!(2);
@@ -2,15 +2,40 @@
 // Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
 
 use firework_adapter::TestCommand;
-use firework_ui::{AdapterCommand, AdapterResult, null_adapter, run_with_adapter};
+use firework_ui::{
+    AdapterCommand, AdapterResult, CurrentEvent, dispatch_event, handle_resize, handle_tick,
+    null_adapter, run_with_adapter,
+};
 use std::cell::RefCell;
+use std::sync::{Mutex, MutexGuard};
 
 thread_local! {
-    static COMMANDS: RefCell<Vec<TestCommand>> = RefCell::new(Vec::new());
+    static COMMANDS: RefCell<Vec<TestCommand>> = const { RefCell::new(Vec::new()) };
 }
 
+// Состояние рантайма (фокус, адаптер, событие) глобальное, а тесты идут параллельно,
+// поэтому запуск экрана вместе с событиями должен быть атомарным
+static SERIAL: Mutex<()> = Mutex::new(());
+
 pub struct TestHarness;
 
+/// Шаг сценария для TestHarness::run_with_steps
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub enum TestStep {
+    /// Отправить событие в фокус
+    Event(CurrentEvent),
+
+    /// Кадр длиной в указанное количество миллисекунд
+    Tick(u32),
+
+    /// Изменение размера окна (ширина, высота, масштаб)
+    Resize(u32, u32, u16),
+
+    /// Произвольный код между шагами, например прокрутка асинхронного рантайма
+    Call(fn()),
+}
+
 impl TestHarness {
     pub fn adapter_handler(command: AdapterCommand) -> AdapterResult {
         if !matches!(command, AdapterCommand::RunLoop { .. }) {
@@ -22,10 +47,48 @@ impl TestHarness {
         null_adapter(command)
     }
 
+    fn serial() -> MutexGuard<'static, ()> {
+        SERIAL
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[allow(dead_code)]
     pub fn run(ui_fn: fn()) -> Vec<TestCommand> {
+        let _guard = Self::serial();
+
         COMMANDS.with(|cmds| cmds.borrow_mut().clear());
         run_with_adapter(Self::adapter_handler, ui_fn);
 
         COMMANDS.with(|cmds| cmds.borrow().clone())
     }
+
+    /// Запускает экран, после чего отправляет ему события. Возвращает команды которые
+    /// были отправлены адаптеру только во время событий
+    #[allow(dead_code)]
+    pub fn run_with_events(ui_fn: fn(), events: &[CurrentEvent]) -> Vec<TestCommand> {
+        let steps: Vec<TestStep> = events.iter().map(|event| TestStep::Event(*event)).collect();
+
+        Self::run_with_steps(ui_fn, &steps)
+    }
+
+    /// То же что и run_with_events, но между событиями можно прокручивать часы тика
+    #[allow(dead_code)]
+    pub fn run_with_steps(ui_fn: fn(), steps: &[TestStep]) -> Vec<TestCommand> {
+        let _guard = Self::serial();
+
+        run_with_adapter(Self::adapter_handler, ui_fn);
+        COMMANDS.with(|cmds| cmds.borrow_mut().clear());
+
+        for step in steps {
+            match step {
+                TestStep::Event(event) => dispatch_event(*event),
+                TestStep::Tick(dt) => handle_tick(*dt),
+                TestStep::Resize(width, height, scale) => handle_resize(*width, *height, *scale),
+                TestStep::Call(f) => f(),
+            }
+        }
+
+        COMMANDS.with(|cmds| cmds.borrow().clone())
+    }
 }
@@ -0,0 +1,351 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+mod common;
+
+use crate::common::{TestHarness, TestStep};
+use firework_adapter::TestCommand;
+use firework_ui::{
+    AdapterClickPhase, AdapterKeyModifiers, AdapterKeyPhase, CurrentEvent, KeyEvent,
+    get_current_event, get_time_ms, key_code, ui,
+};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+type AdapterCommand = TestCommand;
+
+fn touch(phase: AdapterClickPhase, hit_object_id: Option<usize>) -> CurrentEvent {
+    CurrentEvent::Touch {
+        x: 5,
+        y: 5,
+        hit_object_id,
+        phase,
+    }
+}
+
+#[ui]
+fn test_event_click_screen() {
+    let mut count = spark!(0u8);
+
+    rect! {
+        color: (count, 0, 0),
+        on_click: || count += 10,
+    }
+}
+
+#[test]
+fn test_event_click() {
+    let commands = TestHarness::run_with_events(
+        test_event_click_screen,
+        &[
+            // NullAdapter всегда возвращает хэндл 0
+            touch(AdapterClickPhase::Began, Some(0)),
+            touch(AdapterClickPhase::Ended, Some(0)),
+            // Отпускание мимо виджета отменяет нажатие
+            touch(AdapterClickPhase::Began, Some(0)),
+            touch(AdapterClickPhase::Ended, None),
+            // Отпускание без нажатия не является кликом
+            touch(AdapterClickPhase::Ended, Some(0)),
+            touch(AdapterClickPhase::Began, Some(0)),
+            touch(AdapterClickPhase::Ended, Some(0)),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (10, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (20, 0, 0, 255)),
+        ]
+    );
+}
+
+#[ui]
+fn test_event_touch_phases_screen() {
+    let mut phase = spark!(0u8);
+
+    rect! {
+        color: (phase, 0, 0),
+        on_press: || phase = 1,
+        on_move: || phase = 2,
+        on_release: || phase = 3,
+        on_cancel: || phase = 4,
+        on_long_press: || phase = 5,
+    }
+}
+
+#[test]
+fn test_event_touch_phases() {
+    let commands = TestHarness::run_with_steps(
+        test_event_touch_phases_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            // Движение доставляется нажатому виджету даже за его пределами
+            TestStep::Event(touch(AdapterClickPhase::Moved, None)),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(touch(AdapterClickPhase::Cancelled, Some(0))),
+            // Долгое нажатие срабатывает по часам тика один раз
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Tick(300),
+            TestStep::Tick(300),
+            TestStep::Tick(300),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (1, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (2, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (3, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (1, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (4, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (1, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (5, 0, 0, 255)),
+        ]
+    );
+}
+
+#[ui]
+fn test_event_long_press_no_click_screen() {
+    let mut count = spark!(0u8);
+
+    rect! {
+        color: (count, 0, 0),
+        on_click: || count += 1,
+        on_long_press: || count += 10,
+    }
+}
+
+#[test]
+fn test_event_long_press_no_click() {
+    let commands = TestHarness::run_with_steps(
+        test_event_long_press_no_click_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Tick(600),
+            // После долгого нажатия отпускание не считается кликом
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+        ],
+    );
+
+    assert_eq!(commands, vec![AdapterCommand::SetColor(0, (10, 0, 0, 255))]);
+}
+
+#[ui]
+fn test_event_long_press_move_screen() {
+    let mut count = spark!(0u8);
+
+    rect! {
+        color: (count, 0, 0),
+        on_click: || count += 1,
+        on_long_press: || count += 10,
+    }
+}
+
+#[test]
+fn test_event_long_press_cancelled_by_move() {
+    let moved = |y, hit_object_id| CurrentEvent::Touch {
+        x: 5,
+        y,
+        hit_object_id,
+        phase: AdapterClickPhase::Moved,
+    };
+
+    let commands = TestHarness::run_with_steps(
+        test_event_long_press_move_screen,
+        &[
+            // Палец ушёл дальше TOUCH_SLOP, отпускание на виджете остаётся кликом
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(moved(40, Some(0))),
+            TestStep::Tick(600),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            // Палец ушёл с виджета и вернулся
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(moved(5, None)),
+            TestStep::Event(moved(5, Some(0))),
+            TestStep::Tick(600),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            // Дрожание пальца в пределах TOUCH_SLOP не мешает долгому нажатию
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(moved(8, Some(0))),
+            TestStep::Tick(600),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (1, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (2, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (12, 0, 0, 255)),
+        ]
+    );
+}
+
+#[ui]
+fn test_event_tick_screen() {
+    let mut elapsed = spark!(0u8);
+
+    tick!(dt, {
+        elapsed += dt as u8;
+    });
+
+    rect! {
+        color: (elapsed, 0, 0),
+    }
+}
+
+#[test]
+fn test_event_tick() {
+    let commands = TestHarness::run_with_steps(
+        test_event_tick_screen,
+        &[TestStep::Tick(16), TestStep::Tick(20)],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (16, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (36, 0, 0, 255)),
+        ]
+    );
+}
+
+#[ui]
+fn test_event_animated_spark_screen() {
+    let mut red: u8 = spark!(0, animate(100ms, linear));
+
+    rect! {
+        color: (red, 0, 0),
+        on_click: || red += 100,
+    }
+}
+
+#[test]
+fn test_event_animated_spark() {
+    let commands = TestHarness::run_with_steps(
+        test_event_animated_spark_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            TestStep::Tick(50),
+            // Цель меняется посреди анимации, += считается от цели
+            TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+            TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+            TestStep::Tick(50),
+            TestStep::Tick(50),
+            // Анимация закончилась, кадры больше не обновляют виджет
+            TestStep::Tick(50),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (50, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (125, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (200, 0, 0, 255)),
+        ]
+    );
+}
+
+// Кадры которые получил экран, реактивный цикл может пройти кадр несколько раз поэтому
+// кадры различаются по часам тика
+static ANIMATION_TICKS: AtomicU32 = AtomicU32::new(0);
+static ANIMATION_LAST_TICK: AtomicU64 = AtomicU64::new(u64::MAX);
+
+#[ui]
+fn test_event_animation_frames_screen() {
+    let mut red: u8 = spark!(0, animate(100ms, linear));
+
+    if let CurrentEvent::Tick { .. } = get_current_event()
+        && ANIMATION_LAST_TICK.swap(get_time_ms(), Ordering::Relaxed) != get_time_ms()
+    {
+        ANIMATION_TICKS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    rect! {
+        color: (red, 0, 0),
+        on_click: || red = 100,
+    }
+}
+
+#[test]
+fn test_event_animation_frames() {
+    let click = [
+        TestStep::Event(touch(AdapterClickPhase::Began, Some(0))),
+        TestStep::Event(touch(AdapterClickPhase::Ended, Some(0))),
+    ];
+
+    let mut steps = vec![TestStep::Tick(50)];
+    steps.extend(click);
+    steps.extend([TestStep::Tick(60), TestStep::Tick(60), TestStep::Tick(60)]);
+
+    // Та же цель не перезапускает анимацию и не запрашивает кадры
+    steps.extend(click);
+    steps.push(TestStep::Tick(60));
+
+    let commands = TestHarness::run_with_steps(test_event_animation_frames_screen, &steps);
+
+    // Кадры приходят экрану только пока анимация идёт: до клика и после её окончания
+    // экран без tick! событие Tick не получает
+    assert_eq!(ANIMATION_TICKS.load(Ordering::Relaxed), 2);
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (60, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (100, 0, 0, 255)),
+        ]
+    );
+}
+
+fn key(code: u32, phase: AdapterKeyPhase, modifiers: AdapterKeyModifiers) -> CurrentEvent {
+    CurrentEvent::Key(KeyEvent {
+        code,
+        phase,
+        modifiers,
+    })
+}
+
+#[ui]
+fn test_event_keyboard_screen() {
+    let mut last = spark!(0u8);
+
+    rect! {
+        color: (last, 0, 0),
+        on_key: |event| {
+            if event.is_down() && event.code == key_code::ENTER && !event.modifiers.ctrl {
+                last = 1;
+            }
+        },
+        on_char: |ch| last = ch as u8,
+    }
+}
+
+#[test]
+fn test_event_keyboard() {
+    let ctrl = AdapterKeyModifiers {
+        ctrl: true,
+        ..Default::default()
+    };
+
+    let commands = TestHarness::run_with_events(
+        test_event_keyboard_screen,
+        &[
+            key(key_code::ENTER, AdapterKeyPhase::Down, Default::default()),
+            // Отпускание и Ctrl+Enter не подходят под условие обработчика
+            key(key_code::ENTER, AdapterKeyPhase::Up, Default::default()),
+            CurrentEvent::Char('a'),
+            key(key_code::ENTER, AdapterKeyPhase::Down, ctrl),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::SetColor(0, (1, 0, 0, 255)),
+            AdapterCommand::SetColor(0, (b'a', 0, 0, 255)),
+        ]
+    );
+}
@@ -0,0 +1,602 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+mod common;
+
+use crate::common::{TestHarness, TestStep};
+use firework_adapter::TestCommand;
+use firework_ui::{AdapterClickPhase, CurrentEvent, ui};
+
+type AdapterCommand = TestCommand;
+
+fn touch(phase: AdapterClickPhase, y: u32) -> CurrentEvent {
+    CurrentEvent::Touch {
+        x: 5,
+        y,
+        hit_object_id: Some(0),
+        phase,
+    }
+}
+
+#[ui]
+fn test_layout_vertical_screen() {
+    vertical! {
+        layout! {
+            padding: (10, 0, 0, 20),
+            spacing: 5,
+        }
+
+        rect! {
+            width: 100,
+            height: 40,
+        }
+
+        horizontal! {
+            rect! {
+                width: 30,
+                height: 30,
+            }
+
+            rect! {
+                width: 50,
+                height: 10,
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_vertical() {
+    let commands = TestHarness::run(test_layout_vertical_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetSize(0, (100, 0)),
+            AdapterCommand::SetSize(0, (100, 40)),
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetSize(0, (30, 0)),
+            AdapterCommand::SetSize(0, (30, 30)),
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetSize(0, (50, 0)),
+            AdapterCommand::SetSize(0, (50, 10)),
+            // Компоновка после тела корневого лайаута
+            AdapterCommand::SetPosition(0, (20, 10)),
+            AdapterCommand::SetPosition(0, (20, 55)),
+            AdapterCommand::SetPosition(0, (50, 55)),
+        ]
+    );
+}
+
+#[ui]
+fn test_layout_reactive_size_screen() {
+    let mut height = spark!(10);
+
+    vertical! {
+        rect! {
+            width: 10,
+            height: height,
+        }
+
+        rect! {
+            width: 10,
+            height: 10,
+        }
+    }
+
+    if height == 10 {
+        height = 20;
+    }
+}
+
+#[test]
+fn test_layout_reactive_size() {
+    let commands = TestHarness::run(test_layout_reactive_size_screen);
+
+    assert_eq!(
+        commands,
+        vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetSize(0, (10, 0)),
+            AdapterCommand::SetSize(0, (10, 10)),
+            AdapterCommand::NewRect { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+            AdapterCommand::SetSize(0, (10, 0)),
+            AdapterCommand::SetSize(0, (10, 10)),
+            AdapterCommand::SetPosition(0, (0, 0)),
+            AdapterCommand::SetPosition(0, (0, 10)),
+            // Высота первого прямоугольника изменилась и лайаут сдвинул второй
+            AdapterCommand::SetSize(0, (10, 20)),
+            AdapterCommand::SetPosition(0, (0, 0)),
+            AdapterCommand::SetPosition(0, (0, 20)),
+        ]
+    );
+}
+
+#[ui]
+fn test_layout_resize_screen() {
+    vertical! {
+        rect! {
+            width: 1000,
+            height: 10,
+        }
+    }
+}
+
+#[test]
+fn test_layout_resize() {
+    let commands = TestHarness::run_with_steps(
+        test_layout_resize_screen,
+        &[
+            TestStep::Resize(1080, 1920, 200),
+            // Тот же размер не вызывает компоновку
+            TestStep::Resize(1080, 1920, 200),
+            TestStep::Resize(500, 800, 200),
+        ],
+    );
+
+    assert_eq!(
+        commands,
+        vec![
+            // При увеличении окна прямоугольник возвращается к заданной ширине
+            AdapterCommand::SetSize(0, (1000, 10)),
+            AdapterCommand::SetPosition(0, (0, 0)),
+            AdapterCommand::SetSize(0, (500, 10)),
+            AdapterCommand::SetPosition(0, (0, 0)),
+        ]
+    );
+}
+
+#[ui]
+fn test_layout_list_reorder_screen() {
+    let mut swapped = spark!(false);
+
+    tick!(_dt, {
+        swapped = true;
+    });
+
+    vertical! {
+        for i in 0..5 {
+            rect! {
+                width: 10,
+                height: 10,
+
+                #[key_type(i32)]
+                key: if swapped && (i == 1 || i == 3) { 4 - i } else { i },
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_list_reorder() {
+    let commands =
+        TestHarness::run_with_steps(test_layout_list_reorder_screen, &[TestStep::Tick(16)]);
+
+    let positions: Vec<_> = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetPosition(..)))
+        .collect();
+
+    // Остальные строки остались на своих местах и не получают SetPosition
+    assert_eq!(
+        positions,
+        vec![
+            &AdapterCommand::SetPosition(0, (0, 10)),
+            &AdapterCommand::SetPosition(0, (0, 30)),
+        ]
+    );
+}
+
+#[ui]
+fn test_layout_stack_reorder_screen() {
+    let mut swapped = spark!(false);
+
+    tick!(_dt, {
+        swapped = !swapped;
+    });
+
+    stack! {
+        for i in 0..5 {
+            rect! {
+                width: 10,
+                height: 10,
+                z: 5,
+
+                #[key_type(i32)]
+                key: if swapped && (i == 1 || i == 3) { 4 - i } else { i },
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_stack_reorder() {
+    let commands = TestHarness::run(test_layout_stack_reorder_screen);
+
+    // При монтировании элементы получают только свой z, порядок задаёт создание
+    let z: Vec<_> = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetZ(..)))
+        .collect();
+
+    assert_eq!(z, vec![&AdapterCommand::SetZ(0, 5); 5]);
+
+    let commands = TestHarness::run_with_steps(
+        test_layout_stack_reorder_screen,
+        &[TestStep::Tick(16), TestStep::Tick(16)],
+    );
+
+    let z: Vec<_> = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetZ(..)))
+        .collect();
+
+    // Между равными Z нет промежутка, поэтому первая перестановка сдвигает соседей
+    // сверху от их z с шагом. Обратная перестановка уже встаёт в промежутки и меняет
+    // Z только у переставленных
+    assert_eq!(
+        z,
+        vec![
+            &AdapterCommand::SetZ(0, 21),
+            &AdapterCommand::SetZ(0, 37),
+            &AdapterCommand::SetZ(0, 53),
+            &AdapterCommand::SetZ(0, 69),
+            &AdapterCommand::SetZ(0, 13),
+            &AdapterCommand::SetZ(0, 17),
+        ]
+    );
+}
+
+#[ui]
+fn test_layout_list_capacity_screen() {
+    vertical! {
+        for i in 0..3 {
+            rect! {
+                width: 10,
+                height: 10,
+
+                #[key_type(i32)]
+                #[capacity(4)]
+                key: i,
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_list_capacity() {
+    let commands = TestHarness::run(test_layout_list_capacity_screen);
+
+    let positions: Vec<_> = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetPosition(..)))
+        .collect();
+
+    // Список меньше своей ёмкости размещается как обычный список
+    assert_eq!(
+        positions,
+        vec![
+            &AdapterCommand::SetPosition(0, (0, 0)),
+            &AdapterCommand::SetPosition(0, (0, 10)),
+            &AdapterCommand::SetPosition(0, (0, 20)),
+        ]
+    );
+}
+
+#[ui]
+fn test_layout_lazy_list_screen() {
+    let mut scroll = spark!(0);
+
+    tick!(_dt, {
+        scroll = 25;
+    });
+
+    lazy_list! {
+        layout! {
+            extent: 10,
+            viewport: 30,
+            offset: scroll,
+        }
+
+        for i in 0..100 {
+            rect! {
+                width: 10,
+                height: 10,
+
+                #[key_type(i32)]
+                key: i,
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_lazy_list() {
+    let commands = TestHarness::run(test_layout_lazy_list_screen);
+
+    // Из ста строк создаются только три которые помещаются в окно
+    let created = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::NewRect { .. }))
+        .count();
+
+    assert_eq!(created, 3);
+
+    let commands = TestHarness::run_with_steps(test_layout_lazy_list_screen, &[TestStep::Tick(16)]);
+
+    // Прокрутка на 25 сдвигает окно на строки 2..6: строки 0 и 1 прячутся в пул,
+    // 3, 4 и 5 создаются, а оставшаяся строка 2 только сдвигается
+    let created = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::NewRect { .. }))
+        .count();
+
+    let hidden = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetVisible(_, false)))
+        .count();
+
+    let positions: Vec<_> = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetPosition(..)))
+        .collect();
+
+    assert_eq!(created, 3);
+    assert_eq!(hidden, 2);
+    assert!(!commands.contains(&AdapterCommand::Remove(0)));
+    assert_eq!(
+        positions,
+        vec![
+            &AdapterCommand::SetPosition(0, (0, -5)),
+            &AdapterCommand::SetPosition(0, (0, 5)),
+            &AdapterCommand::SetPosition(0, (0, 15)),
+            &AdapterCommand::SetPosition(0, (0, 25)),
+        ]
+    );
+}
+
+// Позиции строк scroll по вертикали, кнопка под ним стоит на x = 5
+fn row_positions(commands: &[TestCommand]) -> Vec<i32> {
+    commands
+        .iter()
+        .filter_map(|command| match command {
+            AdapterCommand::SetPosition(_, (0, y)) => Some(*y),
+            _ => None,
+        })
+        .collect()
+}
+
+// Общий экран тестов прокрутки. Спарк offset переживает повторный запуск экрана, поэтому
+// экран сбрасывается при каждом входе и тесты не зависят от порядка запуска
+#[ui(reset_on_enter)]
+fn test_layout_scroll_screen() {
+    let mut top = spark!(0);
+    let mut gesture = spark!(0u8);
+
+    vertical! {
+        scroll! {
+            layout! {
+                viewport: 30,
+                offset: top,
+            }
+
+            for i in 0..10 {
+                rect! {
+                    width: 10,
+                    height: 10,
+
+                    #[key_type(i32)]
+                    key: i,
+                }
+            }
+        }
+
+        horizontal! {
+            layout! {
+                padding: (0, 0, 0, 5),
+            }
+
+            rect! {
+                width: 10,
+                height: 10,
+                color: (gesture, 0, 0),
+                on_click: || top = 50,
+                on_cancel: || gesture = 4,
+                on_long_press: || gesture = 5,
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_scroll_clip() {
+    let commands = TestHarness::run(test_layout_scroll_screen);
+
+    // Каждая строка обрезается по окну scroll, а окно занимает viewport
+    let clipped = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetClipTo(..)))
+        .count();
+
+    assert_eq!(clipped, 10);
+    assert!(commands.contains(&AdapterCommand::SetSize(0, (10, 30))));
+}
+
+#[test]
+fn test_layout_scroll_drag() {
+    let commands = TestHarness::run_with_steps(
+        test_layout_scroll_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, 25)),
+            TestStep::Event(touch(AdapterClickPhase::Moved, 15)),
+            TestStep::Event(touch(AdapterClickPhase::Began, 29)),
+            TestStep::Event(touch(AdapterClickPhase::Moved, 0)),
+            TestStep::Event(touch(AdapterClickPhase::Began, 29)),
+            TestStep::Event(touch(AdapterClickPhase::Moved, 0)),
+            TestStep::Event(touch(AdapterClickPhase::Began, 29)),
+            TestStep::Event(touch(AdapterClickPhase::Moved, 0)),
+        ],
+    );
+
+    // Палец поднялся на 10, все строки сдвинулись вверх на 10
+    let rows = row_positions(&commands);
+    assert_eq!(rows[..10], (0..10).map(|i| i * 10 - 10).collect::<Vec<_>>());
+
+    // Содержимое длиной 100 в окне 30 прокручивается не дальше 70
+    assert_eq!(rows[rows.len() - 10], -70);
+}
+
+#[test]
+fn test_layout_scroll_drag_cancels_press() {
+    // Адаптер тестов для любой точки возвращает хэндл 0, то есть палец всё время над
+    // кнопкой
+    let commands = TestHarness::run_with_steps(
+        test_layout_scroll_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, 25)),
+            // Сдвиг в пределах TOUCH_SLOP остаётся нажатием на кнопку
+            TestStep::Event(touch(AdapterClickPhase::Moved, 20)),
+            // Дальше прокрутка забирает нажатие, кнопка получает on_cancel
+            TestStep::Event(touch(AdapterClickPhase::Moved, 15)),
+            TestStep::Tick(600),
+            TestStep::Event(touch(AdapterClickPhase::Ended, 15)),
+        ],
+    );
+
+    // Ни долгого нажатия, ни клика (он прокрутил бы к 50) после перехвата нет
+    let colors: Vec<_> = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetColor(..)))
+        .collect();
+
+    assert_eq!(colors, vec![&AdapterCommand::SetColor(0, (4, 0, 0, 255))]);
+
+    let rows = row_positions(&commands);
+    assert_eq!(rows, (0..10).map(|i| i * 10 - 10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_layout_scroll_fling() {
+    let commands = TestHarness::run_with_steps(
+        test_layout_scroll_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, 25)),
+            TestStep::Tick(16),
+            TestStep::Event(touch(AdapterClickPhase::Moved, 15)),
+            TestStep::Event(touch(AdapterClickPhase::Ended, 15)),
+            TestStep::Tick(16),
+            TestStep::Tick(16),
+            TestStep::Tick(16),
+        ],
+    );
+
+    // Каждый сдвиг переставляет все 10 строк, первая строка прохода стоит на минус
+    // прокрутке
+    let first_row: Vec<_> = row_positions(&commands).into_iter().step_by(10).collect();
+
+    // После отпускания прокрутка продолжается на кадрах и замедляется
+    assert_eq!(first_row[0], -10);
+    assert_eq!(first_row.len(), 4);
+
+    let steps: Vec<_> = first_row.windows(2).map(|pair| pair[0] - pair[1]).collect();
+    assert!(steps.iter().all(|step| *step > 0));
+    assert!(steps.windows(2).all(|pair| pair[1] <= pair[0]));
+}
+
+#[test]
+fn test_layout_scroll_to() {
+    let commands = TestHarness::run_with_steps(
+        test_layout_scroll_screen,
+        &[
+            TestStep::Event(touch(AdapterClickPhase::Began, 35)),
+            TestStep::Event(touch(AdapterClickPhase::Ended, 35)),
+        ],
+    );
+
+    // Присваивание спарку прокручивает к 50
+    let rows = row_positions(&commands);
+    assert_eq!(rows, (0..10).map(|i| i * 10 - 50).collect::<Vec<_>>());
+}
+
+// lazy_list внутри scroll под заголовком высотой 20, каждый кадр прокручивает на 30
+#[ui(reset_on_enter)]
+fn test_layout_lazy_scroll_screen() {
+    let mut top = spark!(0);
+
+    tick!(_dt, {
+        top += 30;
+    });
+
+    scroll! {
+        layout! {
+            viewport: 30,
+            offset: top,
+        }
+
+        rect! {
+            width: 10,
+            height: 20,
+        }
+
+        lazy_list! {
+            layout! {
+                extent: 10,
+            }
+
+            for i in 0..100 {
+                rect! {
+                    width: 10,
+                    height: 10,
+
+                    #[key_type(i32)]
+                    key: i,
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_lazy_scroll() {
+    let commands = TestHarness::run_with_steps(
+        test_layout_lazy_scroll_screen,
+        &[TestStep::Tick(16), TestStep::Tick(16), TestStep::Tick(16)],
+    );
+
+    // Окно берётся из scroll: на 30 видна строка 1, на 60 строка 4 и на 90 строка 7.
+    // Пул пополняется в конце прохода, поэтому первый кадр создаёт строку 3, второй
+    // берёт из пула одну строку и создаёт две, а третий берёт из пула все три
+    let created = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::NewRect { .. }))
+        .count();
+
+    let recycled = commands
+        .iter()
+        .filter(|command| matches!(command, AdapterCommand::SetVisible(_, true)))
+        .count();
+
+    assert_eq!(created, 3);
+    assert_eq!(recycled, 4);
+    assert!(!commands.contains(&AdapterCommand::Remove(0)));
+
+    // Последний кадр: строки 7, 8 и 9 стоят в окне, а заголовок ушёл за окно. Заголовок
+    // не кэширует позицию и ставится при каждой компоновке, поэтому он последний
+    let positions: Vec<_> = commands
+        .iter()
+        .filter_map(|command| match command {
+            AdapterCommand::SetPosition(_, (_, y)) => Some(*y),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(positions[positions.len() - 4..], [0, 10, 20, -90]);
+}
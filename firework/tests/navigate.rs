@@ -0,0 +1,411 @@
+// Часть проекта Firework с открытым исходным кодом.
+// Лицензия EPL 2.0, подробнее в файле LICENSE. Copyright (c) 2026 Firework
+
+mod common;
+
+use crate::common::{TestHarness, TestStep};
+use firework_adapter::TestCommand;
+use firework_ui::{AdapterClickPhase, CurrentEvent, Prop, Transition, navigate, ui};
+
+type AdapterCommand = TestCommand;
+
+fn click() -> [CurrentEvent; 2] {
+    let touch = |phase| CurrentEvent::Touch {
+        x: 0,
+        y: 0,
+        hit_object_id: Some(0),
+        phase,
+    };
+
+    [
+        touch(AdapterClickPhase::Began),
+        touch(AdapterClickPhase::Ended),
+    ]
+}
+
+fn new_rect() -> [AdapterCommand; 2] {
+    [
+        AdapterCommand::NewRect { layout: 1 },
+        AdapterCommand::SetHitGroup(0, 65535),
+    ]
+}
+
+fn counter_text(text: &str) -> [AdapterCommand; 2] {
+    [
+        AdapterCommand::ClearText(0),
+        AdapterCommand::PushText {
+            handle: 0,
+            text: text.to_string(),
+            mode: 0,
+        },
+    ]
+}
+
+#[ui]
+fn test_navigate_home_screen() {
+    let mut count = spark!(0u32);
+
+    text! {
+        content: count,
+    }
+
+    rect! {
+        on_click: || {
+            count += 1;
+            navigate::push(test_navigate_details_screen);
+        },
+    }
+}
+
+#[ui]
+fn test_navigate_details_screen() {
+    rect! {
+        color: (0, 0, 255),
+        on_click: || {
+            navigate::pop();
+        },
+    }
+}
+
+#[test]
+fn test_navigate_push_pop_preserves_sparks() {
+    let commands = TestHarness::run_with_events(test_navigate_home_screen, &click().repeat(2));
+
+    let mut expected = Vec::new();
+    expected.extend(counter_text("1"));
+
+    // Переход выполняется после события, целевой экран строится с нуля
+    expected.push(AdapterCommand::RemoveAll);
+    expected.extend(new_rect());
+    expected.push(AdapterCommand::SetColor(0, (0, 0, 255, 255)));
+
+    // Назад: виджеты создаются заново, спарк сохранил значение
+    expected.push(AdapterCommand::RemoveAll);
+    expected.push(AdapterCommand::NewText { layout: 1 });
+    expected.push(AdapterCommand::SetHitGroup(0, 65535));
+    expected.extend(counter_text("1"));
+    expected.extend(new_rect());
+
+    assert_eq!(commands, expected);
+}
+
+#[ui]
+fn test_navigate_root_screen() {
+    rect! {
+        color: (255, 0, 0),
+        on_click: || {
+            if !navigate::pop() {
+                navigate::push(test_navigate_first_screen);
+            }
+        },
+    }
+}
+
+#[ui]
+fn test_navigate_first_screen() {
+    rect! {
+        color: (0, 255, 0),
+        on_click: || navigate::replace(test_navigate_second_screen),
+    }
+}
+
+#[ui]
+fn test_navigate_second_screen() {
+    rect! {
+        color: (0, 0, 255),
+        on_click: || navigate::reset(test_navigate_root_screen),
+    }
+}
+
+#[test]
+fn test_navigate_replace_reset() {
+    let screen = |color| {
+        let mut commands = vec![AdapterCommand::RemoveAll];
+        commands.extend(new_rect());
+        commands.push(AdapterCommand::SetColor(0, color));
+        commands
+    };
+
+    // root -> first, first заменяется на second, second сбрасывает историю до root,
+    // после чего root снова может перейти только вперёд
+    let commands = TestHarness::run_with_events(test_navigate_root_screen, &click().repeat(4));
+
+    let mut expected = Vec::new();
+    expected.extend(screen((0, 255, 0, 255)));
+    expected.extend(screen((0, 0, 255, 255)));
+    expected.extend(screen((255, 0, 0, 255)));
+    expected.extend(screen((0, 255, 0, 255)));
+
+    assert_eq!(commands, expected);
+}
+
+#[ui]
+fn test_navigate_list_screen() {
+    let mut selected = spark!(0u32);
+
+    navigate::on_result(|value: u32| selected = value);
+
+    text! {
+        content: selected,
+    }
+
+    rect! {
+        on_click: || navigate::push_with(test_navigate_item_screen, 7),
+    }
+}
+
+#[ui]
+fn test_navigate_item_screen(item: Prop<u32>) {
+    let mut value = spark!(0u32);
+
+    if let Some(item) = item {
+        value = item;
+    }
+
+    text! {
+        content: value,
+    }
+
+    rect! {
+        on_click: || {
+            navigate::pop_with(value * 2);
+        },
+    }
+}
+
+#[test]
+fn test_navigate_params_and_result() {
+    let screen = |text: &str| {
+        let mut commands = vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewText { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+        ];
+        commands.extend(counter_text(text));
+        commands.extend(new_rect());
+        commands
+    };
+
+    let commands = TestHarness::run_with_events(test_navigate_list_screen, &click().repeat(2));
+
+    let mut expected = Vec::new();
+
+    // Параметр доступен экрану во время Build, присвоение спарку запускает ещё одну
+    // итерацию реактивного цикла
+    expected.extend(screen("7"));
+    expected.extend(counter_text("7"));
+
+    // Предыдущий экран восстанавливается и получает результат отдельным событием
+    expected.extend(screen("0"));
+    expected.extend(counter_text("14"));
+
+    assert_eq!(commands, expected);
+}
+
+#[ui]
+fn test_navigate_level_root_screen() {
+    rect! {
+        on_click: || navigate::push_with(test_navigate_level_screen, 1),
+    }
+}
+
+#[ui]
+fn test_navigate_level_screen(level: Prop<u32>) {
+    let mut value = spark!(0u32);
+
+    if let Some(level) = level {
+        value = level;
+    }
+
+    text! {
+        content: value,
+    }
+
+    rect! {
+        on_click: || navigate::push_with(test_navigate_level_screen, value + 1),
+    }
+}
+
+#[test]
+fn test_navigate_params_per_history_entry() {
+    let [began, ended] = click();
+    let commands = TestHarness::run_with_steps(
+        test_navigate_level_root_screen,
+        &[
+            TestStep::Event(began),
+            TestStep::Event(ended),
+            TestStep::Event(began),
+            TestStep::Event(ended),
+            TestStep::Call(|| {
+                navigate::pop();
+            }),
+            TestStep::Tick(16),
+        ],
+    );
+
+    let screen = |text: &str| {
+        let mut commands = vec![
+            AdapterCommand::RemoveAll,
+            AdapterCommand::NewText { layout: 1 },
+            AdapterCommand::SetHitGroup(0, 65535),
+        ];
+        commands.extend(counter_text(text));
+        commands.extend(new_rect());
+        commands.extend(counter_text(text));
+        commands
+    };
+
+    // Один экран дважды в истории, при возвращении назад он получает параметр своей
+    // записи, а не последний переданный
+    let mut expected = Vec::new();
+    expected.extend(screen("1"));
+    expected.extend(screen("2"));
+    expected.extend(screen("1"));
+
+    assert_eq!(commands, expected);
+}
+
+#[ui(reset_on_enter)]
+fn test_navigate_reset_on_enter_screen() {
+    let mut count = spark!(0u32);
+
+    text! {
+        content: count,
+    }
+
+    rect! {
+        on_click: || {
+            count += 1;
+            navigate::push(test_navigate_details_screen);
+        },
+    }
+}
+
+#[ui(drop_on_leave)]
+fn test_navigate_drop_on_leave_screen() {
+    let mut count = spark!(0u32);
+
+    text! {
+        content: count,
+    }
+
+    rect! {
+        on_click: || {
+            count += 1;
+            navigate::push(test_navigate_details_screen);
+        },
+    }
+}
+
+fn retention_expected() -> Vec<AdapterCommand> {
+    let mut expected = Vec::new();
+    expected.extend(counter_text("1"));
+
+    expected.push(AdapterCommand::RemoveAll);
+    expected.extend(new_rect());
+    expected.push(AdapterCommand::SetColor(0, (0, 0, 255, 255)));
+
+    // Экран строится заново, спарк вернулся к начальному значению
+    expected.push(AdapterCommand::RemoveAll);
+    expected.push(AdapterCommand::NewText { layout: 1 });
+    expected.push(AdapterCommand::SetHitGroup(0, 65535));
+    expected.extend(counter_text("0"));
+    expected.extend(new_rect());
+    expected
+}
+
+#[test]
+fn test_navigate_reset_on_enter() {
+    let commands =
+        TestHarness::run_with_events(test_navigate_reset_on_enter_screen, &click().repeat(2));
+
+    assert_eq!(commands, retention_expected());
+}
+
+#[test]
+fn test_navigate_drop_on_leave() {
+    let commands =
+        TestHarness::run_with_events(test_navigate_drop_on_leave_screen, &click().repeat(2));
+
+    assert_eq!(commands, retention_expected());
+}
+
+#[ui]
+fn test_navigate_slide_screen() {
+    navigate::set_transition(Transition::Slide { duration_ms: 100 });
+
+    rect! {
+        on_click: || navigate::push(test_navigate_details_screen),
+    }
+}
+
+#[test]
+fn test_navigate_slide_transition() {
+    let mut steps: Vec<TestStep> = click().into_iter().map(TestStep::Event).collect();
+    steps.push(TestStep::Tick(50));
+    steps.push(TestStep::Tick(50));
+    steps.extend(click().into_iter().map(TestStep::Event));
+
+    let commands = TestHarness::run_with_steps(test_navigate_slide_screen, &steps);
+
+    let mut expected = Vec::new();
+
+    // Старый экран уходит в отдельную сцену, новый начинает за правым краем окна
+    expected.push(AdapterCommand::DetachAll);
+    expected.push(AdapterCommand::SetSceneOffset(0, (720, 0)));
+    expected.push(AdapterCommand::SetSceneOffset(1, (0, 0)));
+    expected.extend(new_rect());
+    expected.push(AdapterCommand::SetColor(0, (0, 0, 255, 255)));
+
+    // Середина перехода с ease-out
+    expected.push(AdapterCommand::SetSceneOffset(0, (90, 0)));
+    expected.push(AdapterCommand::SetSceneOffset(1, (-630, 0)));
+
+    // Конец перехода
+    expected.push(AdapterCommand::RemoveDetached);
+    expected.push(AdapterCommand::SetSceneOffset(0, (0, 0)));
+    expected.push(AdapterCommand::SetSceneOpacity(0, 255));
+
+    // Назад экран въезжает слева
+    expected.push(AdapterCommand::DetachAll);
+    expected.push(AdapterCommand::SetSceneOffset(0, (-720, 0)));
+    expected.push(AdapterCommand::SetSceneOffset(1, (0, 0)));
+    expected.extend(new_rect());
+
+    assert_eq!(commands, expected);
+}
+
+#[ui]
+fn test_navigate_image_screen() {
+    image! {
+        bytes: b"\x89PNG",
+    }
+
+    rect! {
+        on_click: || navigate::push(test_navigate_details_screen),
+    }
+}
+
+#[test]
+fn test_navigate_frees_image_texture() {
+    let commands = TestHarness::run_with_events(test_navigate_image_screen, &click().repeat(2));
+
+    let mut expected = Vec::new();
+    expected.push(AdapterCommand::RemoveAll);
+    expected.extend(new_rect());
+    expected.push(AdapterCommand::SetColor(0, (0, 0, 255, 255)));
+
+    // Скин изображения сбрасывается при возвращении и освобождает свою текстуру до
+    // загрузки новой
+    expected.push(AdapterCommand::RemoveAll);
+    expected.push(AdapterCommand::FreeTexture(0));
+    expected.push(AdapterCommand::NewImage { layout: 1 });
+    expected.push(AdapterCommand::SetHitGroup(0, 65535));
+    expected.push(AdapterCommand::LoadImage {
+        bytes: b"\x89PNG".to_vec(),
+    });
+    expected.push(AdapterCommand::SetImage(0, 0));
+    expected.extend(new_rect());
+
+    assert_eq!(commands, expected);
+}